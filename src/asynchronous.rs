@@ -0,0 +1,100 @@
+//! Futures for managing temporary directories from async code, behind the
+//! `threaded-async` feature. This isn't a tokio integration: each future
+//! spawns a thread for its file system work and wakes its task when the
+//! thread is done, so the runtime polling it is never blocked. Not
+//! depending on a particular runtime, the futures work with tokio,
+//! async-std or a plain executor alike, at the cost of a thread per call.
+
+use std::collections::HashSet;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+use std::time::Duration;
+use crate::logging::info;
+use crate::{clean_directories_with, daemon_pass, CleanOptions, CleanReport, TempDirErrors, TemporaryDirectory};
+
+/// Like `TemporaryDirectory::try_create`
+pub async fn create(tempdir: TemporaryDirectory) -> Result<TemporaryDirectory, TempDirErrors> {
+    unblock(move || tempdir.try_create()).await
+}
+
+/// Like `TemporaryDirectory::save`
+pub async fn save(tempdir: TemporaryDirectory) -> Result<(), TempDirErrors> {
+    unblock(move || tempdir.save()).await
+}
+
+/// Like `TemporaryDirectory::delete_now`
+pub async fn delete(tempdir: TemporaryDirectory) -> Result<(), TempDirErrors> {
+    unblock(move || tempdir.delete_now()).await
+}
+
+/// Like `clean_directories_with`
+pub async fn clean(options: CleanOptions) -> Result<CleanReport, TempDirErrors> {
+    unblock(move || clean_directories_with(&options)).await
+}
+
+/// Like `run_daemon_with`, to be spawned as a task of an async application.
+/// Never completes, drop the future to stop it.
+pub async fn run_daemon(interval: Duration, options: CleanOptions) {
+    info!("Cleaning expired directories every {}s", interval.as_secs());
+    let options = Arc::new(options);
+    let mut notified = HashSet::new();
+    loop {
+        let pass_options = Arc::clone(&options);
+        // The pass and the wait for the next one share a thread
+        notified = unblock(move || {
+            daemon_pass(&pass_options, &mut notified);
+            thread::sleep(interval);
+            notified
+        })
+        .await;
+    }
+}
+
+/// Result of work running on another thread, or its panic, and the task
+/// waiting for it
+struct Shared<T> {
+    result: Option<thread::Result<T>>,
+    waker: Option<Waker>,
+}
+
+/// Completes with the result of `work`, which runs on a new thread. A panic
+/// of `work` is resumed in the task polling the future.
+struct Unblock<T> {
+    shared: Arc<Mutex<Shared<T>>>,
+}
+impl<T> Future for Unblock<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<T> {
+        let mut shared = self.shared.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        match shared.result.take() {
+            Some(Ok(result)) => Poll::Ready(result),
+            Some(Err(panic)) => {
+                drop(shared);
+                panic::resume_unwind(panic)
+            }
+            None => {
+                shared.waker = Some(context.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+fn unblock<T: Send + 'static>(work: impl FnOnce() -> T + Send + 'static) -> Unblock<T> {
+    let shared = Arc::new(Mutex::new(Shared { result: None, waker: None }));
+    let worker = Arc::clone(&shared);
+    thread::spawn(move || {
+        let result = panic::catch_unwind(AssertUnwindSafe(work));
+        let mut shared = worker.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        shared.result = Some(result);
+        if let Some(waker) = shared.waker.take() {
+            waker.wake();
+        }
+    });
+    Unblock { shared }
+}
@@ -0,0 +1,59 @@
+//! Source of the current time for expiry decisions, so tests can move time
+//! forward instead of sleeping until directories expire.
+
+use std::fmt;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The current time as a unix timestamp in seconds
+pub trait Clock: fmt::Debug + Send + Sync {
+    fn now(&self) -> i64;
+}
+
+/// The clock of the system, used unless another one is given
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+impl Clock for SystemClock {
+    fn now(&self) -> i64 {
+        chrono::offset::Local::now().timestamp()
+    }
+}
+
+/// A clock that only moves when told to. It starts at the current system
+/// time, so directories created with the system clock are fresh on it.
+#[derive(Debug)]
+pub struct ManualClock {
+    now: AtomicI64,
+}
+impl ManualClock {
+    /// A clock standing at the unix timestamp `now`
+    pub fn at(now: i64) -> ManualClock {
+        ManualClock { now: AtomicI64::new(now) }
+    }
+
+    /// Moves the clock forward by `duration`
+    pub fn advance(&self, duration: Duration) {
+        self.now.fetch_add(duration.as_secs() as i64, Ordering::SeqCst);
+    }
+
+    /// Moves the clock to the unix timestamp `now`
+    pub fn set(&self, now: i64) {
+        self.now.store(now, Ordering::SeqCst);
+    }
+}
+impl Default for ManualClock {
+    fn default() -> ManualClock {
+        ManualClock::at(SystemClock.now())
+    }
+}
+impl Clock for ManualClock {
+    fn now(&self) -> i64 {
+        self.now.load(Ordering::SeqCst)
+    }
+}
+
+/// The system clock, shared
+pub(crate) fn system() -> Arc<dyn Clock> {
+    Arc::new(SystemClock)
+}
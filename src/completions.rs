@@ -0,0 +1,246 @@
+//! Shell completion scripts, generated from the clap definition of the
+//! command line. The `--name` of commands working on existing directories
+//! completes with the names in the meta data store, read through
+//! `tempdir list --names`.
+
+use clap::{ArgAction, Command};
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+    Powershell,
+}
+
+/// Commands whose `--name` refers to a new directory rather than a tracked
+/// one, so it isn't completed from the store
+const NEW_NAME_COMMANDS: [&str; 2] = ["create", "exec"];
+
+/// Shell command printing the names of the tracked directories, one per line
+const LIST_NAMES: &str = "tempdir list --names 2>/dev/null";
+
+struct Subcommand {
+    name: String,
+    about: String,
+    options: Vec<Opt>,
+}
+
+struct Opt {
+    short: Option<char>,
+    long: Option<String>,
+    help: String,
+    takes_value: bool,
+    repeated: bool,
+    possible_values: Vec<String>,
+    /// Completes with the names of tracked directories
+    directory_name: bool,
+}
+impl Opt {
+    /// Every spelling, like `["-n", "--name"]`
+    fn spellings(&self) -> Vec<String> {
+        self.short.map(|short| format!("-{short}")).into_iter().chain(self.long.as_ref().map(|long| format!("--{long}"))).collect()
+    }
+}
+
+/// The completion script of `shell` for `command`
+pub fn generate(shell: Shell, mut command: Command) -> String {
+    command.build();
+    let global = options(&command, false);
+    let subcommands: Vec<Subcommand> = command
+        .get_subcommands()
+        .filter(|subcommand| !subcommand.is_hide_set())
+        .map(|subcommand| Subcommand {
+            name: subcommand.get_name().to_string(),
+            about: first_line(subcommand.get_about().map(ToString::to_string)),
+            options: options(subcommand, !NEW_NAME_COMMANDS.contains(&subcommand.get_name())),
+        })
+        .collect();
+
+    match shell {
+        Shell::Bash => bash(&global, &subcommands),
+        Shell::Zsh => zsh(&global, &subcommands),
+        Shell::Fish => fish(&global, &subcommands),
+        Shell::Powershell => powershell(&global, &subcommands),
+    }
+}
+
+fn options(command: &Command, complete_names: bool) -> Vec<Opt> {
+    command
+        .get_arguments()
+        .filter(|arg| !arg.is_positional() && !arg.is_hide_set())
+        .map(|arg| Opt {
+            short: arg.get_short(),
+            long: arg.get_long().map(str::to_string),
+            help: first_line(arg.get_help().map(ToString::to_string)),
+            takes_value: arg.get_action().takes_values(),
+            repeated: matches!(arg.get_action(), ArgAction::Append | ArgAction::Count),
+            possible_values: arg.get_possible_values().iter().filter(|value| !value.is_hide_set()).map(|value| value.get_name().to_string()).collect(),
+            directory_name: complete_names && arg.get_id() == "name",
+        })
+        .collect()
+}
+
+fn first_line(help: Option<String>) -> String {
+    help.unwrap_or_default().lines().next().unwrap_or_default().trim_end_matches('.').to_string()
+}
+
+fn bash(global: &[Opt], subcommands: &[Subcommand]) -> String {
+    let names: Vec<&str> = subcommands.iter().map(|subcommand| subcommand.name.as_str()).collect();
+    let global_words: Vec<String> = global.iter().flat_map(Opt::spellings).collect();
+
+    let mut script = String::from("_tempdir() {\n");
+    script.push_str("    local cur=\"${COMP_WORDS[COMP_CWORD]}\" prev=\"${COMP_WORDS[COMP_CWORD-1]}\" subcommand=\"\" word\n");
+    script.push_str(&format!("    for word in \"${{COMP_WORDS[@]:1:COMP_CWORD-1}}\"; do\n        case \" {} \" in *\" $word \"*) subcommand=\"$word\"; break;; esac\n    done\n", names.join(" ")));
+    script.push_str(&format!(
+        "    if [[ -z $subcommand ]]; then\n        COMPREPLY=($(compgen -W \"{} {}\" -- \"$cur\"))\n        return\n    fi\n",
+        global_words.join(" "),
+        names.join(" ")
+    ));
+
+    script.push_str("    local options\n    case \"$subcommand\" in\n");
+    for subcommand in subcommands {
+        script.push_str(&format!("        {})\n            case \"$prev\" in\n", subcommand.name));
+        for option in subcommand.options.iter().filter(|option| option.takes_value) {
+            let completion = if option.directory_name {
+                format!("COMPREPLY=($(compgen -W \"$({LIST_NAMES})\" -- \"$cur\"))")
+            } else if !option.possible_values.is_empty() {
+                format!("COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))", option.possible_values.join(" "))
+            } else {
+                // Left to the default completion of file names
+                "COMPREPLY=()".to_string()
+            };
+            script.push_str(&format!("                {}) {completion}; return;;\n", option.spellings().join("|")));
+        }
+        script.push_str("            esac\n");
+        let words: Vec<String> = subcommand.options.iter().flat_map(Opt::spellings).collect();
+        script.push_str(&format!("            options=\"{}\";;\n", words.join(" ")));
+    }
+    script.push_str("    esac\n");
+    script.push_str("    COMPREPLY=($(compgen -W \"$options\" -- \"$cur\"))\n}\n");
+    script.push_str("complete -o default -F _tempdir tempdir\n");
+    script
+}
+
+fn zsh(global: &[Opt], subcommands: &[Subcommand]) -> String {
+    let mut script = String::from("#compdef tempdir\n\n");
+    script.push_str(&format!("_tempdir_names() {{\n    local -a names\n    names=(${{(f)\"$({LIST_NAMES})\"}})\n    compadd -a names\n}}\n\n"));
+    script.push_str("_tempdir() {\n    local line state\n    _arguments -C \\\n");
+    for spec in global.iter().flat_map(zsh_specs) {
+        script.push_str(&format!("        {spec} \\\n"));
+    }
+    script.push_str("        '1: :->command' \\\n        '*:: :->arguments'\n\n");
+    script.push_str("    case $state in\n        command)\n            local -a commands\n            commands=(\n");
+    for subcommand in subcommands {
+        script.push_str(&format!("                '{}:{}'\n", subcommand.name, zsh_escape(&subcommand.about)));
+    }
+    script.push_str("            )\n            _describe command commands\n            ;;\n");
+    script.push_str("        arguments)\n            case $line[1] in\n");
+    for subcommand in subcommands {
+        script.push_str(&format!("                {})\n                    _arguments \\\n", subcommand.name));
+        for spec in subcommand.options.iter().flat_map(zsh_specs) {
+            script.push_str(&format!("                        {spec} \\\n"));
+        }
+        script.push_str("                        '*: :_files'\n                    ;;\n");
+    }
+    script.push_str("            esac\n            ;;\n    esac\n}\n\n_tempdir \"$@\"\n");
+    script
+}
+
+fn zsh_specs(option: &Opt) -> Vec<String> {
+    let help = zsh_escape(&option.help).replace(['[', ']'], "");
+    let value = if option.directory_name {
+        ":name:_tempdir_names".to_string()
+    } else if !option.possible_values.is_empty() {
+        format!(":value:({})", option.possible_values.join(" "))
+    } else if option.takes_value {
+        ":value:_files".to_string()
+    } else {
+        String::new()
+    };
+    let repeated = if option.repeated { "*" } else { "" };
+    option.spellings().into_iter().map(|spelling| format!("'{repeated}{spelling}[{help}]{value}'")).collect()
+}
+
+fn zsh_escape(text: &str) -> String {
+    text.replace('\'', "'\\''").replace(':', "\\:")
+}
+
+fn fish(global: &[Opt], subcommands: &[Subcommand]) -> String {
+    let mut script = String::new();
+    for option in global {
+        script.push_str(&fish_option("__fish_use_subcommand", option));
+    }
+    for subcommand in subcommands {
+        script.push_str(&format!(
+            "complete -c tempdir -n '__fish_use_subcommand' -f -a {} -d '{}'\n",
+            subcommand.name,
+            fish_escape(&subcommand.about)
+        ));
+    }
+    for subcommand in subcommands {
+        let condition = format!("__fish_seen_subcommand_from {}", subcommand.name);
+        for option in &subcommand.options {
+            script.push_str(&fish_option(&condition, option));
+        }
+    }
+    script
+}
+
+fn fish_option(condition: &str, option: &Opt) -> String {
+    let mut line = format!("complete -c tempdir -n '{condition}'");
+    if let Some(short) = option.short {
+        line.push_str(&format!(" -s {short}"));
+    }
+    if let Some(long) = &option.long {
+        line.push_str(&format!(" -l {long}"));
+    }
+    if option.directory_name {
+        line.push_str(&format!(" -r -f -a '({LIST_NAMES})'"));
+    } else if !option.possible_values.is_empty() {
+        line.push_str(&format!(" -r -f -a '{}'", option.possible_values.join(" ")));
+    } else if option.takes_value {
+        line.push_str(" -r");
+    }
+    line.push_str(&format!(" -d '{}'\n", fish_escape(&option.help)));
+    line
+}
+
+fn fish_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('\'', "\\'")
+}
+
+fn powershell(global: &[Opt], subcommands: &[Subcommand]) -> String {
+    let mut script = String::from("Register-ArgumentCompleter -Native -CommandName tempdir -ScriptBlock {\n");
+    script.push_str("    param($wordToComplete, $commandAst, $cursorPosition)\n");
+    script.push_str("    $words = @($commandAst.CommandElements | Select-Object -Skip 1 | ForEach-Object { $_.ToString() })\n");
+    script.push_str("    if ($wordToComplete) { $words = @($words | Select-Object -SkipLast 1) }\n");
+    let names: Vec<String> = subcommands.iter().map(|subcommand| format!("'{}'", subcommand.name)).collect();
+    script.push_str(&format!("    $subcommands = @({})\n", names.join(", ")));
+    script.push_str("    $subcommand = $words | Where-Object { $subcommands -contains $_ } | Select-Object -First 1\n");
+    script.push_str("    $previous = if ($words.Count -gt 0) { $words[-1] } else { '' }\n");
+    script.push_str("    $candidates = switch ($subcommand) {\n");
+    for subcommand in subcommands {
+        script.push_str(&format!("        '{}' {{\n", subcommand.name));
+        let name_options: Vec<String> = subcommand
+            .options
+            .iter()
+            .filter(|option| option.directory_name)
+            .flat_map(Opt::spellings)
+            .map(|spelling| format!("'{spelling}'"))
+            .collect();
+        if !name_options.is_empty() {
+            script.push_str(&format!(
+                "            if (@({}) -contains $previous) {{ tempdir list --names 2>$null; break }}\n",
+                name_options.join(", ")
+            ));
+        }
+        let words: Vec<String> = subcommand.options.iter().flat_map(Opt::spellings).map(|spelling| format!("'{spelling}'")).collect();
+        script.push_str(&format!("            @({})\n        }}\n", words.join(", ")));
+    }
+    let global_words: Vec<String> = global.iter().flat_map(Opt::spellings).map(|spelling| format!("'{spelling}'")).collect();
+    script.push_str(&format!("        default {{ @({}) + $subcommands }}\n    }}\n", global_words.join(", ")));
+    script.push_str("    $candidates | Where-Object { $_ -like \"$wordToComplete*\" } | ForEach-Object {\n");
+    script.push_str("        [System.Management.Automation.CompletionResult]::new($_, $_, 'ParameterValue', $_)\n    }\n}\n");
+    script
+}
@@ -0,0 +1,266 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::logging::{error, info};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use crate::{info_store_path, TempDirErrors};
+
+/// Defaults read from `~/.config/tempdir/config.toml`, or the file passed
+/// with `--config`. Any option given on the command line takes precedence
+/// over the file.
+///
+/// The file is read by a parser of its own for a subset of TOML: flat
+/// `key = value` pairs with bare keys, `[profiles.<name>]` sections for
+/// `--profile`, and `#` comments. Values are double-quoted strings without
+/// escape sequences, integers and booleans. Other TOML syntax, like
+/// single-quoted or multi-line strings, escapes, floats, dates, arrays,
+/// inline tables and dotted or quoted keys, is rejected with the number of
+/// the line it's on:
+///
+/// ```toml
+/// store = "/var/lib/tempdir"
+/// parent = "/tmp"
+/// base = "/srv/scratch"
+/// roots = "/tmp:/srv/scratch"
+/// default_duration = "1d"
+/// max_lifetime = "4w"
+/// auto_clean = true
+/// recursive_clean = true
+/// on_expire = "/usr/local/bin/backup-scratch $1"
+/// archive_to = "/srv/archive"
+/// keep_to = "/srv/results"
+/// active_window = "2h"
+/// max_total_size = "10G"
+/// min_free_space = "5G"
+///
+/// [profiles.ci]
+/// store = "/var/lib/tempdir-ci"
+/// parent = "/srv/ci"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Directory meta data files are stored in
+    pub store: Option<PathBuf>,
+    /// Directory new temporary directories are created in
+    pub parent: Option<PathBuf>,
+    /// Directory relative paths are resolved against
+    pub base: Option<PathBuf>,
+    /// Directories temporary directories have to lie within to be deleted,
+    /// separated like `PATH`
+    pub roots: Option<String>,
+    /// Duration used when none is given
+    pub default_duration: Option<String>,
+    /// Longest duration a directory may be created with
+    pub max_lifetime: Option<String>,
+    /// Clean expired directories before running any other command
+    #[serde(default)]
+    pub auto_clean: bool,
+    /// Delete expired directories together with their contents when cleaning
+    #[serde(default)]
+    pub recursive_clean: bool,
+    /// Command run before deleting expired directories that have no
+    /// `on_expire` hook of their own
+    pub on_expire: Option<String>,
+    /// Directory expired directories are archived to before deletion
+    pub archive_to: Option<PathBuf>,
+    /// Directory entries preserved by exclude globs are moved to on expiry
+    pub keep_to: Option<PathBuf>,
+    /// Expired directories modified within this window are extended
+    /// instead of deleted
+    pub active_window: Option<String>,
+    /// Directories are evicted, soonest expiring first, while all of them
+    /// together use more than this, e.g. "10G"
+    pub max_total_size: Option<String>,
+    /// Directories are evicted, soonest expiring first, while their file
+    /// system has less than this available, e.g. "5G"
+    pub min_free_space: Option<String>,
+    /// Stores and directories selected with `--profile`
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+}
+impl Config {
+    /// Loads the config file, falling back to the built-in defaults when
+    /// there is none.
+    pub fn load() -> Result<Config, TempDirErrors> {
+        match config_path() {
+            Some(path) if path.is_file() => Config::load_from(&path),
+            _ => {
+                info!("No config file found. Using defaults");
+                Ok(Config::default())
+            }
+        }
+    }
+
+    /// Loads the config file at `path`, which has to exist
+    pub fn load_from(path: &Path) -> Result<Config, TempDirErrors> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) => {
+                error!("{path:?} config file couldn't be read");
+                return Err(TempDirErrors::InvalidConfig(err.to_string()))
+            }
+        };
+        Config::parse(&contents)
+    }
+
+    pub fn parse(contents: &str) -> Result<Config, TempDirErrors> {
+        let mut values = Map::new();
+        let mut profiles: BTreeMap<String, Map<String, Value>> = BTreeMap::new();
+        // Profile whose section the following lines belong to
+        let mut section: Option<String> = None;
+        for (number, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if line.starts_with('[') {
+                match profile_section(line) {
+                    Some(name) => {
+                        profiles.entry(name.to_string()).or_default();
+                        section = Some(name.to_string());
+                        continue;
+                    }
+                    _ => {
+                        error!("Config file line {} is not a profile section", number + 1);
+                        return Err(TempDirErrors::InvalidConfig(format!("line {}: {line}", number + 1)))
+                    }
+                }
+            }
+
+            let parsed = match line.split_once('=') {
+                Some((key, value)) => parse_key(key.trim()).and_then(|key| Ok((key, parse_value(value.trim())?))),
+                None => Err("expected `key = value`"),
+            };
+            let (key, value) = match parsed {
+                Ok(pair) => pair,
+                Err(reason) => {
+                    error!("Config file line {} couldn't be parsed: {reason}", number + 1);
+                    return Err(TempDirErrors::InvalidConfig(format!("line {}: {reason}: {line}", number + 1)))
+                }
+            };
+            let values = match &section {
+                Some(name) => profiles.entry(name.clone()).or_default(),
+                None => &mut values,
+            };
+            values.insert(key.to_string(), value);
+        }
+
+        if !profiles.is_empty() {
+            let profiles = profiles.into_iter().map(|(name, profile)| (name, Value::Object(profile))).collect();
+            values.insert("profiles".to_string(), Value::Object(profiles));
+        }
+        match serde_json::from_value(Value::Object(values)) {
+            Ok(config) => Ok(config),
+            Err(err) => {
+                error!("Config file contains invalid settings");
+                Err(TempDirErrors::InvalidConfig(err.to_string()))
+            }
+        }
+    }
+
+    /// The locations of the profile called `name`. Profiles that aren't in
+    /// the config file, and those without a store of their own, keep their
+    /// meta data in `profiles/<name>` inside the default store.
+    pub fn profile(&self, name: &str) -> Result<Profile, TempDirErrors> {
+        check_profile_name(name)?;
+        let mut profile = self.profiles.get(name).cloned().unwrap_or_default();
+        if profile.store.is_none() {
+            profile.store = Some(info_store_path()?.join("profiles").join(name));
+        }
+        Ok(profile)
+    }
+}
+
+/// Locations of a profile, used instead of those at the top of the config
+/// file. A profile has a store of its own, so its directories don't show up
+/// in the `list` and `clean` of other profiles.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    /// Directory meta data files are stored in
+    pub store: Option<PathBuf>,
+    /// Directory new temporary directories are created in
+    pub parent: Option<PathBuf>,
+    /// Directory relative paths are resolved against
+    pub base: Option<PathBuf>,
+}
+
+/// Name of the profile of a `[profiles.<name>]` section header
+fn profile_section(line: &str) -> Option<&str> {
+    let header = line.split('#').next()?.trim().strip_prefix('[')?.strip_suffix(']')?;
+    let name = header.trim().strip_prefix("profiles.")?;
+    check_profile_name(name).ok()?;
+    Some(name)
+}
+
+/// Profile names are used as directory names, so only letters, digits, `-`
+/// and `_` are allowed
+fn check_profile_name(name: &str) -> Result<(), TempDirErrors> {
+    if name.is_empty() || !name.chars().all(|character| character.is_ascii_alphanumeric() || character == '-' || character == '_') {
+        error!("{name:?} is not a valid profile name");
+        return Err(TempDirErrors::InvalidProfile(name.to_string()))
+    }
+    Ok(())
+}
+
+/// A bare key, the only kind supported
+fn parse_key(key: &str) -> Result<&str, &'static str> {
+    if key.is_empty() {
+        return Err("missing key")
+    }
+    if !key.chars().all(|character| character.is_ascii_alphanumeric() || character == '-' || character == '_') {
+        return Err("dotted and quoted keys are not supported")
+    }
+    Ok(key)
+}
+
+/// Parses a string, integer or boolean value, ignoring a trailing comment.
+/// Fails with the reason for TOML syntax outside of the supported subset.
+fn parse_value(value: &str) -> Result<Value, &'static str> {
+    if value.starts_with("\"\"\"") || value.starts_with("'''") {
+        return Err("multi-line strings are not supported")
+    }
+    if value.starts_with('\'') {
+        return Err("single-quoted strings are not supported")
+    }
+    if value.starts_with('[') {
+        return Err("arrays are not supported")
+    }
+    if value.starts_with('{') {
+        return Err("inline tables are not supported")
+    }
+    if let Some(rest) = value.strip_prefix('"') {
+        let (string, trailing) = rest.split_once('"').ok_or("unterminated string")?;
+        if string.contains('\\') {
+            return Err("escape sequences in strings are not supported")
+        }
+        let trailing = trailing.trim();
+        if !trailing.is_empty() && !trailing.starts_with('#') {
+            return Err("unexpected characters after the string")
+        }
+        return Ok(Value::String(string.to_string()))
+    }
+
+    let value = value.split('#').next().unwrap_or_default().trim();
+    match value {
+        "true" => Ok(Value::Bool(true)),
+        "false" => Ok(Value::Bool(false)),
+        "" => Err("missing value"),
+        _ => value.parse::<i64>().map(Value::from).map_err(|_| "only strings, integers and booleans are supported as values"),
+    }
+}
+
+/// `$XDG_CONFIG_HOME/tempdir/config.toml`, defaulting to `~/.config`
+fn config_path() -> Option<PathBuf> {
+    let mut path = match env::var_os("XDG_CONFIG_HOME") {
+        Some(config) => PathBuf::from(config),
+        None => PathBuf::from(env::var_os("HOME")?).join(".config"),
+    };
+    path.push("tempdir");
+    path.push("config.toml");
+    Some(path)
+}
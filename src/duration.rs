@@ -0,0 +1,311 @@
+//! The duration string grammar: an amount followed by a unit, e.g. "90s",
+//! "30min" or "4w". Several of those pairs can be combined into a compound
+//! duration like "1d12h30min". ISO 8601 durations like "P1DT2H30M" are
+//! accepted as well.
+
+use chrono::{Local, Months, TimeZone};
+use crate::logging::error;
+use regex::Regex;
+use crate::TempDirErrors;
+
+/// Length of a year in ISO 8601 durations
+const ISO_YEAR: i64 = 31536000;
+
+enum PeriodStringValue {
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+}
+impl PeriodStringValue {
+    fn value(&self) -> i64 {
+        match self {
+            Self::Second => 1,
+            Self::Minute => 60,
+            Self::Hour => 3600,
+            Self::Day => 86400,
+            Self::Week => 604800,
+            Self::Month => 2678400,
+        }
+    }
+
+    fn suffix(&self) -> &'static str {
+        match self {
+            Self::Second => "s",
+            Self::Minute => "min",
+            Self::Hour => "h",
+            Self::Day => "d",
+            Self::Week => "w",
+            Self::Month => "m",
+        }
+    }
+
+    /// All periods, from largest to smallest
+    fn descending() -> [PeriodStringValue; 6] {
+        [Self::Month, Self::Week, Self::Day, Self::Hour, Self::Minute, Self::Second]
+    }
+}
+
+/// Formats an amount of seconds using the two largest units of the duration
+/// string grammar, e.g. "2d 3h" or "45min". Negative amounts are "expired".
+pub fn humanize(seconds: i64) -> String {
+    if seconds < 0 {
+        return "expired".to_string();
+    }
+
+    let periods = PeriodStringValue::descending();
+    let largest = match periods.iter().position(|period| seconds >= period.value()) {
+        Some(index) => index,
+        None => return "0s".to_string(),
+    };
+
+    let mut remainder = seconds;
+    let mut parts: Vec<String> = Vec::new();
+    for period in periods.iter().skip(largest).take(2) {
+        let amount = remainder / period.value();
+        remainder %= period.value();
+        if amount > 0 {
+            parts.push(format!("{amount}{}", period.suffix()));
+        }
+    }
+    parts.join(" ")
+}
+
+/// A duration split into calendar months, whose length depends on the date
+/// they are added to, and a fixed amount of seconds
+struct Components {
+    months: i64,
+    seconds: i64,
+}
+
+/// Parses a duration string into seconds. It consists of one or more pairs
+/// of an amount followed by a unit (s, min, h, d, w or m for months), which
+/// are summed together. Pairs may be separated by whitespace and appear in
+/// any order, but every unit may only be used once. Months count as 31
+/// days, use `end_time` for calendar months.
+///
+/// A leading `-` negates the whole duration, so `parse("-5m")` is minus
+/// five months. Negative durations are only meaningful as offsets; where
+/// a lifetime is expected, `end_time` and `parse_duration` reject them.
+pub fn parse(duration: &str) -> Result<i64, TempDirErrors> {
+    let (sign, components) = parse_components(duration)?;
+    components
+        .months
+        .checked_mul(PeriodStringValue::Month.value())
+        .and_then(|months| months.checked_add(components.seconds))
+        .and_then(|seconds| seconds.checked_mul(sign))
+        .ok_or_else(out_of_range)
+}
+
+/// Timestamp `duration` after `start`. Months are calendar months, so the
+/// end lands on the same day of the month, or the last day of shorter months.
+pub fn end_time(start: i64, duration: &str) -> Result<i64, TempDirErrors> {
+    let (sign, components) = parse_components(duration)?;
+    if sign < 0 {
+        error!("Unable to parse duration string: Duration can't be negative");
+        return Err(TempDirErrors::WrongDurationString)
+    }
+    if components.months == 0 {
+        return start.checked_add(components.seconds).ok_or_else(out_of_range)
+    }
+
+    let end = Local
+        .timestamp_opt(start, 0)
+        .single()
+        .map(|start| start.naive_local())
+        .and_then(|start| {
+            let months = Months::new(u32::try_from(components.months).ok()?);
+            Some(start.date().checked_add_months(months)?.and_time(start.time()))
+        })
+        .and_then(|end| Local.from_local_datetime(&end).earliest());
+    match end {
+        Some(end) => end.timestamp().checked_add(components.seconds).ok_or_else(out_of_range),
+        None => {
+            error!("Unable to parse duration string: End time out of range");
+            Err(TempDirErrors::WrongDurationString)
+        }
+    }
+}
+
+/// Parses a duration string into its sign and components
+fn parse_components(duration: &str) -> Result<(i64, Components), TempDirErrors> {
+    let duration = duration.trim();
+    let (sign, duration) = match duration.strip_prefix('-') {
+        Some(rest) => (-1, rest),
+        None => (1, duration),
+    };
+    if duration.starts_with('P') {
+        return Ok((sign, parse_iso(duration)?))
+    }
+
+    let regex_pair = Regex::new(r"\d+[A-Za-z]+").unwrap();
+    let mut components = Components { months: 0, seconds: 0 };
+    let mut units = Vec::new();
+    let mut end = 0;
+    for pair in regex_pair.find_iter(duration) {
+        if !duration[end..pair.start()].trim().is_empty() {
+            error!("Unable to parse duration string: Invalid duration string specified");
+            return Err(TempDirErrors::WrongDurationString)
+        }
+        end = pair.end();
+
+        let period = parse_period(pair.as_str()).unwrap_or(0);
+        if units.contains(&period) {
+            error!("Unable to parse duration string: Unit used more than once");
+            return Err(TempDirErrors::WrongDurationString)
+        }
+        units.push(period);
+        let seconds = parse_pair(pair.as_str())?;
+        if period == PeriodStringValue::Month.value() {
+            components.months = components.months.checked_add(seconds / period).ok_or_else(out_of_range)?;
+        } else {
+            components.seconds = components.seconds.checked_add(seconds).ok_or_else(out_of_range)?;
+        }
+    }
+
+    if units.is_empty() || !duration[end..].trim().is_empty() {
+        error!("Unable to parse duration string: Invalid duration string specified");
+        return Err(TempDirErrors::WrongDurationString)
+    }
+    Ok((sign, components))
+}
+
+/// Parses an ISO 8601 duration, `P[nY][nM][nW][nD][T[nH][nM][nS]]`. Years
+/// are 365 days. Only whole numbers are supported.
+fn parse_iso(duration: &str) -> Result<Components, TempDirErrors> {
+    let regex_iso = Regex::new(
+        r"^P(?:(\d+)Y)?(?:(\d+)M)?(?:(\d+)W)?(?:(\d+)D)?(?:T(?:(\d+)H)?(?:(\d+)M)?(?:(\d+)S)?)?$",
+    )
+    .unwrap();
+    let captures = match regex_iso.captures(duration) {
+        Some(captures) => captures,
+        None => {
+            error!("Unable to parse duration string: Invalid ISO 8601 duration specified");
+            return Err(TempDirErrors::WrongDurationString)
+        }
+    };
+
+    let units = [
+        ISO_YEAR,
+        PeriodStringValue::Month.value(),
+        PeriodStringValue::Week.value(),
+        PeriodStringValue::Day.value(),
+        PeriodStringValue::Hour.value(),
+        PeriodStringValue::Minute.value(),
+        PeriodStringValue::Second.value(),
+    ];
+    let mut components = Components { months: 0, seconds: 0 };
+    let mut time_components = 0;
+    for (index, unit) in units.iter().enumerate() {
+        let amount = match captures.get(index + 1) {
+            Some(amount) => amount.as_str(),
+            None => continue,
+        };
+        match amount.parse::<i64>() {
+            Ok(amount) if index == 1 => {
+                components.months = components.months.checked_add(amount).ok_or_else(out_of_range)?
+            }
+            Ok(amount) => {
+                components.seconds = amount
+                    .checked_mul(*unit)
+                    .and_then(|seconds| components.seconds.checked_add(seconds))
+                    .ok_or_else(out_of_range)?
+            }
+            Err(_) => {
+                error!("Unable to parse duration string: Invalid amount specified");
+                return Err(TempDirErrors::WrongDurationString)
+            }
+        }
+        if index >= 4 {
+            time_components += 1;
+        }
+    }
+
+    // "P" and "PT" alone, or a "T" without any time components, are invalid
+    let given = captures.iter().skip(1).flatten().count();
+    if given == 0 || (duration.contains('T') && time_components == 0) {
+        error!("Unable to parse duration string: ISO 8601 duration without components");
+        return Err(TempDirErrors::WrongDurationString)
+    }
+    Ok(components)
+}
+
+/// Parses a single amount followed by a unit into seconds
+fn parse_pair(duration: &str) -> Result<i64, TempDirErrors> {
+    let duration_amount = match parse_amount(duration) {
+        Ok(amount) => {
+            Ok(amount)
+        }
+        Err(TempDirErrors::WrongDurationString) => {
+            error!("Unable to parse duration string: Invalid duration string specified");
+            Err(TempDirErrors::WrongDurationString)
+        }
+        Err(TempDirErrors::WrongTimeAmount) => {
+            error!("Unable to parse duration string: Invalid amount specified");
+            Err(TempDirErrors::WrongDurationString)
+        }
+        _ => {
+            error!("Something unknown went wrong parsing the duration amount");
+            Err(TempDirErrors::WrongDurationString)
+        }
+    };
+    // return duration_amount;
+    let period_amount = match parse_period(duration) {
+        Ok(value) => {
+            Ok(value)
+        }
+        Err(TempDirErrors::WrongPeriodString) => {
+            error!("Unable to parse duration string: Invalid period specified");
+            Err(TempDirErrors::WrongDurationString)
+        }
+        Err(_) => {
+            error!("Something unknown went wrong parsing the duration period");
+            Err(TempDirErrors::WrongDurationString)
+        }
+    };
+
+    match (duration_amount, period_amount) {
+        (Ok(amount), Ok(period)) => amount.checked_mul(period).ok_or_else(out_of_range),
+        _ => Err(TempDirErrors::WrongDurationString),
+    }
+}
+
+/// Error for a duration too long to be represented in seconds
+fn out_of_range() -> TempDirErrors {
+    error!("Unable to parse duration string: Duration out of range");
+    TempDirErrors::WrongDurationString
+}
+
+fn parse_amount(duration: &str) -> Result<i64, TempDirErrors> {
+    let regex_amount = Regex::new(r"[A-Za-z]+").unwrap();
+    let amount_vec: Vec<&str> = regex_amount.split(duration).filter(|x| !x.is_empty()).collect();
+    if amount_vec.len() != 1 {
+        return Err(TempDirErrors::WrongDurationString);
+    }
+    let amount = amount_vec[0].parse::<i64>();
+    match amount {
+        Ok(value) => Ok(value),
+        Err(_) => Err(TempDirErrors::WrongTimeAmount),
+    }
+}
+fn parse_period(duration: &str) -> Result<i64, TempDirErrors> {
+    // Need to account for possible unwrap error
+    let period_string: String = duration
+        .chars()
+        .filter(|x| x.is_alphabetic())
+        .map(|x| x.to_lowercase().next().unwrap())
+        .collect();
+
+    let period_amount = match period_string.as_str() {
+        "s" => Ok(PeriodStringValue::Second.value()),
+        "min" => Ok(PeriodStringValue::Minute.value()),
+        "h" => Ok(PeriodStringValue::Hour.value()),
+        "d" => Ok(PeriodStringValue::Day.value()),
+        "w" => Ok(PeriodStringValue::Week.value()),
+        "m" => Ok(PeriodStringValue::Month.value()),
+        _ => Err(TempDirErrors::WrongPeriodString),
+    };
+    period_amount
+}
\ No newline at end of file
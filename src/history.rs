@@ -0,0 +1,141 @@
+//! The history of the store: every directory created, extended or deleted is
+//! appended as a JSON line to `events.jsonl` inside the store, so it can be
+//! told afterwards what happened to a directory, when and by whom.
+
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use crate::logging::{error, warn};
+use crate::{current_uid, info_store_path, manager, timestamp, TempDirErrors, TemporaryDirectory};
+
+pub(crate) const EVENTS_FILE: &str = "events.jsonl";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Action {
+    Create,
+    Extend,
+    Delete,
+}
+impl Action {
+    /// Name of the action as it is stored, like "create"
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Create => "create",
+            Action::Extend => "extend",
+            Action::Delete => "delete",
+        }
+    }
+}
+
+/// An entry of the history
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Event {
+    /// Seconds since the epoch, stored as RFC 3339 UTC timestamp
+    #[serde(with = "timestamp")]
+    pub at: i64,
+    pub action: Action,
+    pub name: String,
+    pub path: Option<PathBuf>,
+    /// Bytes freed by a deletion, unless the directory was moved to the trash
+    #[serde(default)]
+    pub size_freed: Option<u64>,
+    /// User that ran the command
+    #[serde(default)]
+    pub user: Option<String>,
+}
+
+/// The events of the store in use, oldest first, only those of directories
+/// called `name` if given. Lines that can't be parsed are skipped with a
+/// warning.
+pub fn history(name: Option<&str>) -> Result<Vec<Event>, TempDirErrors> {
+    let path = info_store_path()?.join(EVENTS_FILE);
+    let file = match fs::File::open(&path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => {
+            error!("{path:?} history couldn't be read");
+            return Err(TempDirErrors::MetaDataReadFailed(err))
+        }
+    };
+
+    let mut events = Vec::new();
+    for (number, line) in BufReader::new(file).lines().enumerate() {
+        let line = match line {
+            Ok(line) if line.trim().is_empty() => continue,
+            Ok(line) => line,
+            Err(err) => {
+                error!("{path:?} history couldn't be read");
+                return Err(TempDirErrors::MetaDataReadFailed(err))
+            }
+        };
+        match serde_json::from_str::<Event>(&line) {
+            Ok(event) if name.is_none_or(|name| event.name == name) => events.push(event),
+            Ok(_) => {}
+            Err(_) => warn!("{path:?} line {} couldn't be parsed. Skipping", number + 1),
+        }
+    }
+    Ok(events)
+}
+
+/// Appends an event about `tempdir` to the history of its store, and to the
+/// audit log at `TEMPDIR_AUDIT_LOG` if set. A history that can't be written
+/// to only produces a warning.
+///
+/// The audit log keeps its original format, one
+/// `{"event": ..., "name": ..., "at": <seconds since the epoch>}` per line
+/// for creations and deletions only, so existing readers of it keep
+/// working.
+pub(crate) fn record(action: Action, tempdir: &TemporaryDirectory, path: Option<&Path>, size_freed: Option<u64>) {
+    let event = Event {
+        at: chrono::offset::Local::now().timestamp(),
+        action,
+        name: tempdir.name().to_string(),
+        path: path.map(Path::to_path_buf),
+        size_freed,
+        user: current_user(),
+    };
+    let line = match serde_json::to_string(&event) {
+        Ok(line) => line,
+        Err(_) => return,
+    };
+
+    // The history lives in the store directory, which backends don't have
+    if !tempdir.in_store(manager::backend_in_use) {
+        match tempdir.in_store(info_store_path) {
+            Ok(store) if store.is_dir() => append(&store.join(EVENTS_FILE), &line),
+            _ => warn!("Store not found. {} wasn't recorded in the history", tempdir.name()),
+        }
+    }
+    if let (Some(audit_log), false) = (env::var_os("TEMPDIR_AUDIT_LOG"), action == Action::Extend) {
+        let record = serde_json::json!({
+            "event": action.name(),
+            "name": event.name,
+            "at": event.at,
+        });
+        append(Path::new(&audit_log), &record.to_string());
+    }
+}
+
+/// Lines are written in a single call to a file opened for appending, so
+/// concurrent writers don't interleave them
+fn append(path: &Path, line: &str) {
+    let written = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(format!("{line}\n").as_bytes()));
+    if written.is_err() {
+        warn!("{path:?} history couldn't be written to");
+    }
+}
+
+/// Name of the user running this process
+fn current_user() -> Option<String> {
+    env::var("USER")
+        .or_else(|_| env::var("USERNAME"))
+        .ok()
+        .or_else(|| current_uid().map(|uid| uid.to_string()))
+}
@@ -1,304 +1,2954 @@
-use std::path::{PathBuf};
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
 use serde::{Serialize, Deserialize};
-use log::{error, info};
+use crate::history::Action;
+use crate::logging::{error, info, warn};
+use std::fs;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
 use regex::Regex;
-use std::fs::{self, DirEntry};
-use std::fs::File;
 use thiserror::Error;
 use std::env;
+use std::collections::{HashMap, HashSet};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::panic::{self, AssertUnwindSafe};
+use std::process::Command;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "threaded-async")]
+pub mod asynchronous;
+pub mod clock;
+mod config;
+pub mod history;
+mod logging;
+mod manager;
+mod migration;
+mod store;
+mod timestamp;
+mod watch;
+pub mod duration;
+
+pub use clock::{Clock, ManualClock, SystemClock};
+pub use config::{Config, Profile};
+pub use manager::TempDirManager;
+pub use store::{JsonStore, MetadataStore, Store};
+pub use watch::watch_removals;
+#[cfg(feature = "test-util")]
+pub use store::InMemoryStore;
 
 #[derive(Error, Debug)]
 pub enum TempDirErrors {
-    #[error("Faled to create Temporary Directory")]
+    #[error("Failed to create Temporary Directory")]
     CreationFailed,
+    #[error("Temporary directory couldn't be created on disk")]
+    DirectoryCreationFailed(#[source] io::Error),
     #[error("Invalid duration string specified")]
     WrongDurationString,
     #[error("Invalid time period specified")]
     WrongPeriodString,
     #[error("Invalid time amount specified")]
     WrongTimeAmount,
-    #[error("Meta data storage directory couldn't be created/found")]
-    StoreFolderError
-}
-enum PeriodStringValue {
-    Second,
-    Minute,
-    Hour,
-    Day,
-    Week,
-    Month,
-}
-impl PeriodStringValue {
-    fn value(self) -> i64 {
-        match self {
-            Self::Second => 1,
-            Self::Minute => 60,
-            Self::Hour => 3600,
-            Self::Day => 86400,
-            Self::Week => 604800,
-            Self::Month => 2678400,
-        }
-    }
+    #[error("Invalid duration: {0}")]
+    InvalidDuration(String),
+    #[error("Invalid expiry time specified")]
+    InvalidDeadline,
+    #[error("Invalid size specified")]
+    WrongSizeString,
+    #[error("Invalid name filter: {0}")]
+    InvalidFilter(String),
+    #[error("Meta data storage directory location couldn't be determined")]
+    StorePathUnavailable,
+    #[error("Meta data storage directory couldn't be created/accessed: {0}")]
+    StoreFolderError(String),
+    #[error("Invalid config file: {0}")]
+    InvalidConfig(String),
+    #[error("Invalid profile name: {0}")]
+    InvalidProfile(String),
+    #[error("Invalid directory name specified")]
+    InvalidName,
+    #[error("No temporary directory with that name is tracked")]
+    NotFound,
+    #[error("Several temporary directories with that name are tracked, specify the parent")]
+    AmbiguousName,
+    #[error("Temporary directory couldn't be removed")]
+    RemovalFailed(#[source] io::Error),
+    #[error("Temporary directory contents couldn't be read")]
+    DirectoryReadFailed(#[source] io::Error),
+    #[error("Meta data file couldn't be read")]
+    MetaDataReadFailed(#[source] io::Error),
+    #[error("Meta data file couldn't be written")]
+    MetaDataWriteFailed(#[source] io::Error),
+    #[error("Meta data file couldn't be removed")]
+    MetaDataRemovalFailed(#[source] io::Error),
+    #[error("Command couldn't be started")]
+    CommandFailed(#[source] io::Error),
+    #[error("Temporary directory couldn't be archived")]
+    ArchiveFailed(#[source] io::Error),
+    #[error("Template couldn't be copied into the temporary directory")]
+    TemplateCopyFailed(#[source] io::Error),
+    #[error("Link to the temporary directory couldn't be created")]
+    LinkFailed(#[source] io::Error),
+    #[error("Refusing to delete {0:?}: {1}")]
+    UnsafeDeletion(PathBuf, String),
 }
+/// Seconds `created_at` may lie in the future before the clock is considered
+/// to have been wrong
+const CLOCK_TOLERANCE: i64 = 60;
+
+/// Times a random name is drawn before giving up on finding a free one
+const RANDOM_NAME_ATTEMPTS: u32 = 10;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TemporaryDirectory {
+    /// Version of the meta data format. Records are upgraded to the current
+    /// version when they are read, see `migration`.
+    #[serde(skip_deserializing, default = "migration::schema_version")]
+    version: u64,
     name: String,
     duration: String,
+    /// Seconds since the epoch, stored as RFC 3339 UTC timestamp
+    #[serde(with = "timestamp")]
     created_at: i64,
+    #[serde(with = "timestamp")]
     end_time: i64,
     path: Option<PathBuf>,
+    #[serde(default)]
+    on_expire: Option<String>,
+    /// `path` is stored relative to the base directory, see `base_path`
+    #[serde(default)]
+    relative: bool,
+    /// The lifetime restarts whenever the directory contents are modified
+    #[serde(default)]
+    renew_on_access: bool,
+    /// The contents are deleted along with the directory on expiry
+    #[serde(default)]
+    delete_contents: bool,
+    /// Directory the contents are archived to before deletion
+    #[serde(default)]
+    archive_to: Option<PathBuf>,
+    /// The directory is moved to the trash instead of being deleted
+    #[serde(default)]
+    trash: bool,
+    /// Globs of entries preserved on expiry, see `with_exclude`
+    #[serde(default)]
+    exclude: Vec<String>,
+    /// Directory preserved entries are moved to on expiry
+    #[serde(default)]
+    keep_to: Option<PathBuf>,
+    /// Only the contents are removed on expiry, see `with_contents_only`
+    #[serde(default)]
+    contents_only: bool,
+    /// Symlink to the directory, removed along with it, see `with_link`
+    #[serde(default)]
+    link: Option<PathBuf>,
+    /// Duration after which the files inside are removed individually, see
+    /// `with_file_max_age`
+    #[serde(default)]
+    file_max_age: Option<String>,
+    /// Cleaning skips the directory, even once it has expired
+    #[serde(default)]
+    pinned: bool,
+    /// User that created the directory, on Unix
+    #[serde(default)]
+    uid: Option<u32>,
+    /// Deleting the directory failed because its files were in use. The
+    /// next clean deletes it, whether it has expired or not.
+    #[serde(default)]
+    pending_deletion: bool,
+    /// Deadline the directory was created with, as given by the user
+    #[serde(default)]
+    until: Option<String>,
+    /// Labels to filter by, trimmed and lowercase
+    #[serde(default)]
+    tags: Vec<String>,
+    /// Directory to create the directory in, see `with_parent`
+    #[serde(skip)]
+    parent: Option<PathBuf>,
+    /// Directory tree copied into the directory on creation, see
+    /// `with_template`
+    #[serde(skip)]
+    template: Option<PathBuf>,
+    #[serde(skip)]
+    expand_placeholders: bool,
+    /// Key of this record in the store, remembered so records imported from
+    /// older meta data files keep their key
+    #[serde(skip)]
+    key: Option<String>,
+    /// Store the record lives in, set by the `TempDirManager` that created
+    /// or loaded the directory. `None` for the default store.
+    #[serde(skip)]
+    store: Option<manager::StoreLocation>,
+    /// Clock expiry is decided by, see `with_clock`
+    #[serde(skip, default = "clock::system")]
+    clock: Arc<dyn Clock>,
 }
 impl TemporaryDirectory {
     pub fn new(name: String, duration: String) -> Result<TemporaryDirectory, TempDirErrors> {
-        match parse_duration_string(&duration) {
-            Ok(value) => {
-                info!("Total lifetime: {value}");
-                let time = chrono::offset::Local::now();
-                let startime: i64 = time.timestamp();
-                let endtime: i64 = startime + value;
-                Ok(TemporaryDirectory {
-                    name: name,
-                    duration: duration,
-                    created_at: startime,
-                    end_time: endtime,
-                    path: None,
-                })
+        match parse_duration(&duration) {
+            Ok(lifetime) => {
+                let mut tempdir = TemporaryDirectory::new_with_duration(name, lifetime);
+                tempdir.end_time = duration::end_time(tempdir.created_at, &duration)?;
+                tempdir.duration = duration;
+                Ok(tempdir)
             }
-            Err(_) => {
+            Err(err) => {
                 error!("Failed to create Temporary Directory");
-                Err(TempDirErrors::CreationFailed)
+                Err(err)
             }
         }
     }
 
-    pub fn create(mut self) {
-        let directory = fs::create_dir(&self.name);
-        match directory {
-            Ok(_) => {
-                match PathBuf::from(&self.name).canonicalize() {
-                    Ok(path) => self.path = Some(path),
-                    Err(_) => error!("Something went wrong creating the path"),
-                }
-                info!("Directory created successfully");
-                self.save();
-            }
-            Err(_) => error!("Failed to create directory"),
+    /// Like `new`, but the directory is created inside `base`, which is
+    /// created as well if it doesn't exist yet
+    pub fn new_at(base: PathBuf, name: String, duration: String) -> Result<TemporaryDirectory, TempDirErrors> {
+        Ok(TemporaryDirectory::new(name, duration)?.with_parent(Some(base)))
+    }
+
+    /// Like `new`, but the directory expires at the deadline `until`, an RFC
+    /// 3339 timestamp with or without offset, e.g. "2024-12-31T18:00:00". The
+    /// deadline is kept for display, the duration is the time left until it.
+    pub fn new_until(name: String, until: String) -> Result<TemporaryDirectory, TempDirErrors> {
+        let deadline = parse_deadline(&until)?;
+        let now = chrono::offset::Local::now().timestamp();
+        if deadline <= now {
+            error!("{until} lies in the past");
+            return Err(TempDirErrors::InvalidDeadline)
         }
+
+        let mut tempdir = TemporaryDirectory::starting_at(name, Duration::from_secs((deadline - now) as u64), now);
+        tempdir.until = Some(until);
+        Ok(tempdir)
     }
 
-    pub fn save(self) {
-        let mut path = match info_store_path() {
-            Ok(path) => path,
-            Err(_) => {
-                error!("Meta data directory couldn't be found. Temporary directory cannot be created");
-                self.delete();
-                return
+    /// Starts tracking an existing directory that has no meta data, so it
+    /// expires after `duration` like any other temporary directory
+    pub fn adopt(path: &Path, duration: String) -> Result<TemporaryDirectory, TempDirErrors> {
+        let path = match path.canonicalize() {
+            Ok(path) if path.is_dir() => path,
+            _ => {
+                error!("{path:?} is not an existing directory");
+                return Err(TempDirErrors::NotFound)
             }
         };
+        let name = match path.file_name().and_then(|name| name.to_str()) {
+            Some(name) => name.to_string(),
+            None => return Err(TempDirErrors::InvalidName),
+        };
 
-        match fs::read_dir(&path).ok() {
-            Some(_) => {},
-            None => {
-                info!("Meta data directory not found. Creating it now");
-                // this line is bad
-                match fs::create_dir(&path) {
-                    Ok(()) => info!("Meta data directory created"),
-                    Err(_) => {
-                        error!("Meta data directory couldn't be created. Temporary directory couldn't be created");
-                        self.delete();
-                        return
-                    }
-                }
+        let mut tempdir = TemporaryDirectory::new(name, duration)?;
+        tempdir.path = Some(path);
+        tempdir.write_meta_data()?;
+        info!("Adopted {}", tempdir.name);
+        Ok(tempdir)
+    }
+
+    /// Like `new`, but takes the lifetime as a `Duration` instead of a duration
+    /// string. The duration is stored in seconds, e.g. "90s".
+    pub fn new_with_duration(name: String, duration: Duration) -> TemporaryDirectory {
+        TemporaryDirectory::starting_at(name, duration, chrono::offset::Local::now().timestamp())
+    }
+
+    /// Like `new_with_duration`, with the lifetime starting at the unix
+    /// timestamp `startime`
+    fn starting_at(name: String, duration: Duration, startime: i64) -> TemporaryDirectory {
+        let lifetime = duration.as_secs() as i64;
+        info!("Total lifetime: {lifetime}");
+        TemporaryDirectory {
+            version: migration::SCHEMA_VERSION,
+            name,
+            duration: format!("{lifetime}s"),
+            created_at: startime,
+            end_time: startime + lifetime,
+            path: None,
+            on_expire: None,
+            relative: false,
+            renew_on_access: false,
+            delete_contents: false,
+            archive_to: None,
+            trash: false,
+            exclude: Vec::new(),
+            keep_to: None,
+            contents_only: false,
+            file_max_age: None,
+            link: None,
+            pinned: false,
+            uid: current_uid(),
+            pending_deletion: false,
+            until: None,
+            tags: Vec::new(),
+            parent: None,
+            template: None,
+            expand_placeholders: false,
+            key: None,
+            store: None,
+            clock: clock::system(),
+        }
+    }
+
+    /// Sets a command to run before the directory is deleted on expiry. It
+    /// runs through `sh -c`, with the directory path as `$1`, e.g.
+    /// `cp -r "$1" /srv/backup`.
+    pub fn with_on_expire(mut self, command: Option<String>) -> TemporaryDirectory {
+        self.on_expire = command;
+        self
+    }
+
+    /// Stores the path relative to the base directory instead of as an
+    /// absolute path, so the store and directories can be relocated together.
+    /// The base is `TEMPDIR_BASE`, or the directory containing the store.
+    pub fn with_relative_path(mut self, relative: bool) -> TemporaryDirectory {
+        self.relative = relative;
+        self
+    }
+
+    /// Deletes the directory together with its contents on expiry. Without
+    /// this, only empty directories are deleted.
+    pub fn with_delete_contents(mut self, delete_contents: bool) -> TemporaryDirectory {
+        self.delete_contents = delete_contents;
+        self
+    }
+
+    /// Compresses the contents into a timestamped `.tar.gz` inside
+    /// `archive_to` before the directory is deleted on expiry
+    pub fn with_archive_to(mut self, archive_to: Option<PathBuf>) -> TemporaryDirectory {
+        self.archive_to = archive_to;
+        self
+    }
+
+    /// Preserves entries matching one of the globs on expiry: everything
+    /// else is deleted and the directory is kept with only them, unless
+    /// `with_keep_to` moves them elsewhere. A glob without `/`, like "*.log",
+    /// matches names at any depth, others like "results/**" match paths
+    /// relative to the directory. `*` doesn't match `/`, `**` does.
+    pub fn with_exclude(mut self, exclude: Vec<String>) -> TemporaryDirectory {
+        self.exclude = exclude;
+        self
+    }
+
+    /// Moves the entries preserved by `with_exclude` into `keep_to`, below a
+    /// directory named like this one, and deletes the directory on expiry
+    pub fn with_keep_to(mut self, keep_to: Option<PathBuf>) -> TemporaryDirectory {
+        self.keep_to = keep_to;
+        self
+    }
+
+    /// Empties the directory on expiry instead of deleting it, and restarts
+    /// its lifetime, for scratch folders that are purged periodically
+    pub fn with_contents_only(mut self, contents_only: bool) -> TemporaryDirectory {
+        self.contents_only = contents_only;
+        self
+    }
+
+    /// Every clean removes the files inside that weren't modified for
+    /// `file_max_age`, e.g. "7d", along with the directories that leaves
+    /// empty, while the directory itself lives on until it expires. Ages
+    /// that aren't longer than zero are rejected, as they would remove every
+    /// file.
+    pub fn with_file_max_age(mut self, file_max_age: Option<String>) -> Result<TemporaryDirectory, TempDirErrors> {
+        if let Some(file_max_age) = &file_max_age {
+            parse_file_max_age(file_max_age)?;
+        }
+        self.file_max_age = file_max_age;
+        Ok(self)
+    }
+
+    /// Moves the directory to the trash of the user on expiry, instead of
+    /// deleting it permanently, see `move_to_trash`
+    pub fn with_trash(mut self, trash: bool) -> TemporaryDirectory {
+        self.trash = trash;
+        self
+    }
+
+    /// Creates the directory inside `parent` instead of the default creation
+    /// parent (`TEMPDIR_PARENT`, or the current working directory). Missing
+    /// parent directories are created.
+    pub fn with_parent(mut self, parent: Option<PathBuf>) -> TemporaryDirectory {
+        self.parent = parent;
+        self
+    }
+
+    /// Places a symlink at `link` pointing at the directory when it is
+    /// created, replacing a symlink already there, e.g. one to the previous
+    /// scratch directory. The link is removed along with the directory.
+    pub fn with_link(mut self, link: Option<PathBuf>) -> TemporaryDirectory {
+        self.link = link;
+        self
+    }
+
+    /// Copies the directory tree at `template` into the directory when it is
+    /// created, e.g. a skeleton project layout
+    pub fn with_template(mut self, template: Option<PathBuf>) -> TemporaryDirectory {
+        self.template = template;
+        self
+    }
+
+    /// Replaces `{{name}}` with the directory name and `{{date}}` with the
+    /// creation date in the contents of text files copied from the template
+    pub fn with_template_placeholders(mut self, expand_placeholders: bool) -> TemporaryDirectory {
+        self.expand_placeholders = expand_placeholders;
+        self
+    }
+
+    /// Labels the directory with `tags`, which `list` and `clean` can filter
+    /// by. Tags are trimmed and lowercased, so "Build " and "build" are the
+    /// same tag.
+    pub fn with_tags(mut self, tags: Vec<String>) -> TemporaryDirectory {
+        self.tags = Vec::new();
+        for tag in tags.iter().map(|tag| normalize_tag(tag)) {
+            if !tag.is_empty() && !self.tags.contains(&tag) {
+                self.tags.push(tag);
             }
-        };
+        }
+        self
+    }
+
+    /// Restarts the lifetime whenever the contents are modified, so only
+    /// directories that are no longer used expire
+    pub fn with_renew_on_access(mut self, renew: bool) -> TemporaryDirectory {
+        self.renew_on_access = renew;
+        self
+    }
+
+    /// Decides expiry and restarts lifetimes by `clock` instead of the
+    /// system clock, e.g. a `ManualClock` in tests. The creation time is
+    /// kept, it was taken from the system clock.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> TemporaryDirectory {
+        self.clock = clock;
+        self
+    }
+
+    /// For directories that renew on access, moves `end_time` to the last
+    /// modification plus the original duration. Returns whether that is
+    /// still in the future.
+    fn renew(&mut self) -> bool {
+        match self.renewed_end_time() {
+            Some(end_time) => self.renew_until(end_time),
+            None => false,
+        }
+    }
 
-        // Create meta data file
-        path.push(format!("{}.json", self.name));
+    /// Moves `end_time` to the last modification plus `window`, when that is
+    /// still in the future. Returns whether it is.
+    fn extend_while_active(&mut self, window: Duration) -> bool {
+        match self.active_end_time(window) {
+            Some(end_time) => self.renew_until(end_time),
+            None => false,
+        }
+    }
+
+    fn renew_until(&mut self, end_time: i64) -> bool {
+        self.end_time = end_time;
+        match self.write_meta_data() {
+            Ok(()) => info!("{} was modified recently, lifetime renewed", self.name),
+            Err(_) => error!("Renewed lifetime of {} couldn't be saved", self.name),
+        }
+        true
+    }
+
+    /// The last modification plus the original duration, if that is still in
+    /// the future
+    fn renewed_end_time(&self) -> Option<i64> {
+        let last_modified = self.resolved_path().and_then(|path| last_modification(&path))?;
+
+        let end_time = duration::end_time(last_modified, &self.duration).ok()?;
+        if end_time <= self.clock.now() {
+            return None
+        }
+        Some(end_time)
+    }
+
+    /// The last modification plus `window`, if that is still in the future
+    fn active_end_time(&self, window: Duration) -> Option<i64> {
+        let last_modified = self.resolved_path().and_then(|path| last_modification(&path))?;
+
+        let end_time = last_modified + window.as_secs() as i64;
+        if end_time <= self.clock.now() {
+            return None
+        }
+        Some(end_time)
+    }
+
+    /// Absolute path of the directory, resolving relative paths against the
+    /// current base directory
+    fn resolved_path(&self) -> Option<PathBuf> {
+        let path = self.path.as_ref()?;
+        if !self.relative {
+            return Some(path.clone());
+        }
 
-        let file = match File::create(&path) {
-            Ok(file) => file,
+        match self.in_store(base_path) {
+            Ok(base) => Some(base.join(path)),
             Err(_) => {
-                error!("Meta data file couldn't be created. Temporary directory couldn't be created");
-                self.delete();
-                return
+                error!("Base directory couldn't be determined. Relative path can't be resolved");
+                None
             }
+        }
+    }
+
+    /// Seconds left until the directory expires, negative once it has expired.
+    /// When the directory was created in the future, the full lifetime is left.
+    pub fn remaining_seconds(&self) -> i64 {
+        let now = self.clock.now();
+        if self.created_in_future(now) {
+            return self.end_time - self.created_at
+        }
+        self.end_time - now
+    }
+
+    /// Whether the lifetime has passed, according to the clock of the
+    /// directory, see `is_expired_at`
+    pub fn is_expired(&self) -> bool {
+        self.is_expired_at(self.clock.now())
+    }
+
+    /// Whether the lifetime has passed at the unix timestamp `now`. A
+    /// directory created in the future, which happens when the clock was
+    /// wrong, is never considered expired.
+    pub fn is_expired_at(&self, now: i64) -> bool {
+        if self.created_in_future(now) {
+            return false
+        }
+        now > self.end_time
+    }
+
+    /// Whether `created_at` lies further in the future than the clock
+    /// tolerance, meaning the clock was wrong at creation time
+    fn created_in_future(&self, current_time: i64) -> bool {
+        if self.created_at > current_time + CLOCK_TOLERANCE {
+            warn!("{} was created in the future, the clock might have been wrong", self.name);
+            return true
+        }
+        false
+    }
+
+    /// Human readable remaining lifetime, see `format_remaining`
+    pub fn format_remaining(&self) -> String {
+        format_remaining(self.remaining_seconds())
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Unix timestamp of the moment the directory was created
+    pub fn created_at(&self) -> i64 {
+        self.created_at
+    }
+
+    /// Unix timestamp of the moment the directory expires
+    pub fn end_time(&self) -> i64 {
+        self.end_time
+    }
+
+    /// Local time the directory expires at
+    pub fn expires_at(&self) -> DateTime<Local> {
+        Local.timestamp(self.end_time, 0)
+    }
+
+    /// Time left until the directory expires, zero once it has expired
+    pub fn remaining(&self) -> Duration {
+        Duration::from_secs(self.remaining_seconds().max(0) as u64)
+    }
+
+    /// Duration the directory was created with, like "1d"
+    pub fn duration(&self) -> &str {
+        &self.duration
+    }
+
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    /// Whether cleaning skips the directory, see `set_pinned`
+    pub fn is_pinned(&self) -> bool {
+        self.pinned
+    }
+
+    /// Whether the directory belongs to the user running this process:
+    /// they own it, or created its record. Always true on other platforms
+    /// than Unix, and when the directory doesn't exist.
+    pub fn is_owned_by_current_user(&self) -> bool {
+        let uid = match current_uid() {
+            Some(uid) => uid,
+            None => return true,
         };
+        if self.uid == Some(uid) {
+            return true
+        }
+        match self.resolved_path().map(fs::symlink_metadata) {
+            Some(Ok(metadata)) => file_owner(&metadata) == Some(uid),
+            _ => true,
+        }
+    }
 
-        match serde_json::to_writer(&file, &self) {
-            Ok(_) => info!("Temporary directory saved"),
-            Err(_) => {
-                error!("Failed to save meta data file. Temporary directory couldn't be created");
-                self.delete();
-                return
+    /// Whether the directory carries every one of `tags`, compared after
+    /// trimming and lowercasing
+    pub fn has_tags(&self, tags: &[String]) -> bool {
+        tags.iter().all(|tag| self.tags.contains(&normalize_tag(tag)))
+    }
+
+    /// Deadline the directory was created with, if it was given one
+    pub fn until(&self) -> Option<&str> {
+        self.until.as_deref()
+    }
+
+    /// Absolute path of the directory, once it has been created
+    pub fn path(&self) -> Option<PathBuf> {
+        self.resolved_path()
+    }
+
+    /// Loads the meta data of the directory called `name`. When directories
+    /// with that name exist in several parents, `parent` has to tell them apart.
+    pub fn load(name: &str, parent: Option<&Path>) -> Result<TemporaryDirectory, TempDirErrors> {
+        find_meta_data(name, parent)
+    }
+
+    /// Adds `duration` to the lifetime of the directory. The meta data is
+    /// reloaded first, so extensions made by other processes aren't lost.
+    pub fn extend(&mut self, duration: &str) -> Result<(), TempDirErrors> {
+        let extension = parse_duration(duration)?.as_secs() as i64;
+        self.update_meta_data(|tempdir| {
+            tempdir.end_time = duration::end_time(tempdir.end_time, duration).unwrap_or(tempdir.end_time + extension)
+        })?;
+        info!("{} extended by {duration}", self.name);
+        history::record(Action::Extend, self, self.resolved_path().as_deref(), None);
+        Ok(())
+    }
+
+    /// Restarts the lifetime: the directory now expires after its original
+    /// duration, counted from now
+    pub fn touch(&mut self) -> Result<(), TempDirErrors> {
+        let end_time = duration::end_time(self.clock.now(), &self.duration)?;
+        self.update_meta_data(|tempdir| tempdir.end_time = end_time)?;
+        info!("Lifetime of {} restarted", self.name);
+        Ok(())
+    }
+
+    /// Pins the directory, so `clean_directories` and the daemon skip it
+    /// until it is unpinned. It keeps its end time meanwhile.
+    pub fn set_pinned(&mut self, pinned: bool) -> Result<(), TempDirErrors> {
+        self.update_meta_data(|tempdir| tempdir.pinned = pinned)?;
+        if pinned {
+            info!("{} pinned", self.name);
+        } else {
+            info!("{} unpinned", self.name);
+        }
+        Ok(())
+    }
+
+    /// Applies `change` to the stored record and saves it, with the store
+    /// locked throughout so changes of other processes aren't lost
+    fn update_meta_data<F: FnOnce(&mut TemporaryDirectory)>(&mut self, change: F) -> Result<(), TempDirErrors> {
+        let mut store = self.in_store(Store::open)?;
+        let mut tempdir = match store.get(&self.store_key()) {
+            Some(tempdir) => tempdir.clone(),
+            None => {
+                error!("No meta data found for {}", self.name);
+                return Err(TempDirErrors::NotFound)
             }
         };
+        change(&mut tempdir);
+        store.insert(tempdir.clone());
+        store.save()?;
+
+        self.replace_record(tempdir);
+        Ok(())
     }
 
-    pub fn delete(self) {
-        match self.path {
-            Some(path) => {
-                match fs::remove_dir(&path) {
-                    Ok(_) => info!("Removed directory"),
-                    Err(_) => error!("Unable to remove directory"),
-                }
+    /// Takes over the stored `record`, keeping the store and clock of this
+    /// handle
+    fn replace_record(&mut self, record: TemporaryDirectory) {
+        let (store, clock) = (self.store.take(), self.clock.clone());
+        *self = record;
+        self.store = store;
+        self.clock = clock;
+    }
+
+    /// Re-reads the meta data of this directory from the store, picking up
+    /// changes made by other processes
+    pub fn reload(&mut self) -> Result<(), TempDirErrors> {
+        match self.in_store(Store::open)?.get(&self.store_key()) {
+            Some(tempdir) => {
+                self.replace_record(tempdir.clone());
+                Ok(())
             }
             None => {
-                error!("Directory can't be removed, path is not specified")
+                error!("No meta data found for {}", self.name);
+                Err(TempDirErrors::NotFound)
             }
         }
     }
-}
 
-// Proper error handling
-pub fn clean_directories() {
-    let path = match info_store_path() {
-        Ok(path) => path,
-        Err(_) => {
-            error!("Meta data directory couldn't be found. Temporary directories cannot be deleted");
-            return
+    /// Total size in bytes of the files inside the directory. A directory that
+    /// no longer exists uses no space, which is only logged as a warning.
+    pub fn disk_usage(&self) -> Result<u64, TempDirErrors> {
+        match self.resolved_path() {
+            Some(path) if path.exists() => directory_size(&path),
+            _ => {
+                warn!("Directory of {} doesn't exist. Assuming it uses no space", self.name);
+                Ok(0)
+            }
         }
-    };
+    }
 
-    let temporary_directory_files = match fs::read_dir(&path).ok() {
-        Some(dir) => dir,
-        None => {
-            info!("Meta data directory couln't be opened. Temporary directories cannot be deleted");
-            return
+    /// Creates the directory on disk, saves its meta data and returns the
+    /// absolute path of the directory. If the meta data can't be written,
+    /// the directory is removed again. The handle stays usable, to query or
+    /// delete the directory later. A handle whose directory already exists
+    /// can't be created again.
+    pub fn create(&mut self) -> Result<PathBuf, TempDirErrors> {
+        if let Some(path) = self.resolved_path() {
+            error!("{} was already created at {path:?}", self.name);
+            return Err(TempDirErrors::DirectoryCreationFailed(io::Error::new(io::ErrorKind::AlreadyExists, format!("already created at {path:?}"))))
+        }
+        if let Some(template) = self.template.as_ref().filter(|template| !template.is_dir()) {
+            error!("Template {template:?} is not a directory");
+            return Err(TempDirErrors::TemplateCopyFailed(io::Error::new(io::ErrorKind::NotFound, format!("{template:?} is not a directory"))))
         }
-    };
-    let mut deleted_directory_files: Vec<DirEntry> = Vec::new();
-    for temporary_directory_file in temporary_directory_files {
-        match temporary_directory_file {
-            Ok(file_name) => {
-                let path = file_name.path();
-                // This could fail
-                let file = match File::open(path) {
-                    Ok(file) => file,
-                    Err(_) => {
-                        error!("Meta data file couldn't be read. Continuing");
-                        continue;
-                    }
-                };
-                
-                let temporary_directory: TemporaryDirectory = match serde_json::from_reader(file) {
-                    Ok(data) => data,
-                    Err(_) => {
-                        error!("Temporary directory couldn't be parsed. Continuing");
-                        continue;
-                    }
-                };
 
-                if check_temporary_directory(&temporary_directory) {
-                    temporary_directory.delete();
+        // A failed attempt leaves the handle as it was, so it can be retried
+        let name = self.name.clone();
+        if let Err(err) = self.make_directory() {
+            self.name = name;
+            return Err(err)
+        }
+        let created = self.copy_template().and_then(|_| self.create_link()).and_then(|_| self.save());
+        let path = match (created, self.resolved_path()) {
+            (Ok(()), Some(path)) => path,
+            (result, path) => {
+                if let Some(path) = path.filter(|path| check_deletable(path).is_ok()) {
+                    let _ = remove_recursively(&path, false);
                 }
-                deleted_directory_files.push(file_name);
-            },
-            Err(_) => {
-                info!("No meta data files stored")
+                self.name = name;
+                self.path = None;
+                return Err(result.err().unwrap_or(TempDirErrors::CreationFailed))
+            }
+        };
+        history::record(Action::Create, self, Some(&path), None);
+        Ok(path)
+    }
+
+    /// Creates the directory like `create`, but returns a guard that removes
+    /// it and its meta data when dropped. The meta data is registered, so
+    /// `clean_directories` still removes the directory on expiry if the
+    /// process is killed before the guard is dropped.
+    pub fn create_scoped(self) -> Result<TempDirGuard, TempDirErrors> {
+        let tempdir = self.try_create()?;
+        Ok(ScopedTempDir { tempdir })
+    }
+
+    /// Like `create`, but when a directory with this name already exists in
+    /// the parent, that one is used instead and its path returned: the
+    /// handle takes over its record, or the directory is tracked with the
+    /// settings of this handle if it had no record. Names with random
+    /// characters always get a new directory.
+    pub fn create_if_not_exists(&mut self) -> Result<PathBuf, TempDirErrors> {
+        let (name, random) = expand_name(&self.name)?;
+        if random {
+            return self.create()
+        }
+        let parent = self.resolved_parent()?;
+        let path = parent.join(&name);
+        if !path.is_dir() {
+            return self.create()
+        }
+
+        match self.in_store(|| find_meta_data(&name, Some(&parent))) {
+            Ok(existing) => {
+                info!("{name} already exists at {path:?}");
+                self.replace_record(existing);
             }
+            Err(TempDirErrors::NotFound) => {
+                info!("{path:?} already exists but isn't tracked. Tracking it now");
+                self.name = name;
+                self.path = Some(self.stored_path(&path)?);
+                self.write_meta_data()?;
+            }
+            Err(err) => return Err(err),
         }
+        Ok(path)
+    }
+
+    /// Like `create`, but takes the handle and returns it with its path set
+    /// on success, to finish a chain of `with_*` calls
+    pub fn try_create(mut self) -> Result<TemporaryDirectory, TempDirErrors> {
+        self.create()?;
+        Ok(self)
     }
 
-    for deleted_file in deleted_directory_files {
-        let path = deleted_file.path();
-        match fs::remove_file(&path) {
-            Ok(()) => info!("{path:?} meta data file deleted"),
-            Err(_) => error!("{path:?} meta data file couldn't be deleted"),
+    /// Writes the meta data of an already created directory. The directory
+    /// is removed when that fails, so it isn't left behind untracked.
+    pub fn save(&self) -> Result<(), TempDirErrors> {
+        if let Err(err) = self.write_meta_data() {
+            let _ = self.remove_directory();
+            return Err(err)
         }
+        Ok(())
     }
-}
 
-fn check_temporary_directory(tempdir: &TemporaryDirectory) -> bool {
-    let current_time: i64 = chrono::offset::Local::now().timestamp();
-    let directory_end_time = tempdir.end_time;
+    /// Removes the directory, leaving its meta data in place
+    pub fn delete(&self) -> Result<(), TempDirErrors> {
+        self.remove_directory()
+    }
 
-    current_time > directory_end_time
-}
+    /// Deletes the directory and its meta data right away, regardless of
+    /// the remaining lifetime. The meta data is kept if the directory can't
+    /// be removed.
+    pub fn delete_now(&self) -> Result<(), TempDirErrors> {
+        self.delete_now_with(None)
+    }
 
-fn info_store_path() -> Result<PathBuf, TempDirErrors> {
-    let path_to_exe = match env::current_exe() {
-        Ok(path) => path,
-        Err(_) => return Err(TempDirErrors::StoreFolderError)
-    };
+    /// Like `delete_now`, calling `progress` while the contents are removed.
+    /// When the directory is still in use after several attempts, it is
+    /// marked to be deleted by the next clean.
+    pub fn delete_now_with(&self, progress: Option<&Progress>) -> Result<(), TempDirErrors> {
+        match self.remove_directory_with(self.delete_contents, false, self.trash, progress) {
+            Ok(()) => {}
+            Err(TempDirErrors::RemovalFailed(err)) if is_in_use(&err) => {
+                if self.clone().update_meta_data(|tempdir| tempdir.pending_deletion = true).is_ok() {
+                    warn!("{} is in use. It will be deleted by the next clean", self.name);
+                }
+                return Err(TempDirErrors::RemovalFailed(err))
+            }
+            Err(err) => return Err(err),
+        }
 
-    match path_to_exe.parent() {
-        Some(path) => {
-            let mut folder = PathBuf::from(path);
-            folder.push("temporary_directories");
-            return Ok(folder)
+        self.in_store(|| manager::with_backend(|backend| backend.remove(self)))?;
+        info!("Meta data of {} deleted", self.name);
+        Ok(())
+    }
+
+    fn make_directory(&mut self) -> Result<(), TempDirErrors> {
+        let template = self.name.clone();
+        let (name, random) = expand_name(&template)?;
+        self.name = name;
+
+        let parent = self.resolved_parent()?;
+
+        // A random name that is taken is drawn again
+        let mut attempts = 0;
+        let path = loop {
+            let path = parent.join(&self.name);
+            match fs::create_dir(&path) {
+                Ok(()) => break path,
+                Err(err) if random && err.kind() == io::ErrorKind::AlreadyExists && attempts < RANDOM_NAME_ATTEMPTS => {
+                    attempts += 1;
+                    self.name = expand_name(&template)?.0;
+                }
+                Err(err) => {
+                    error!("Failed to create directory");
+                    return Err(TempDirErrors::DirectoryCreationFailed(err))
+                }
+            }
+        };
+
+        match self.stored_path(&path) {
+            Ok(stored_path) => self.path = Some(stored_path),
+            Err(err) => {
+                let _ = fs::remove_dir(&path);
+                return Err(err)
+            }
         }
-        None => return Err(TempDirErrors::StoreFolderError)
+
+        info!("Directory created successfully");
+        Ok(())
     }
-}
 
-pub fn parse_duration_string(duration: &str) -> Result<i64, TempDirErrors> {
-    let duration_amount = match parse_amount(duration) {
-        Ok(amount) => {
-            Ok(amount)
+    /// Absolute path of the directory to create the directory in, which is
+    /// created if it doesn't exist. It is resolved up front so the stored
+    /// path doesn't depend on the working directory at cleanup time.
+    fn resolved_parent(&self) -> Result<PathBuf, TempDirErrors> {
+        let parent = self.parent.clone().unwrap_or_else(creation_parent);
+        if !parent.exists() && fs::create_dir_all(&parent).is_ok() {
+            info!("Created parent directory {parent:?}");
         }
-        Err(TempDirErrors::WrongDurationString) => {
-            error!("Unable to parse duration string: Invalid duration string specified");
-            Err(TempDirErrors::WrongDurationString)
+        match parent.canonicalize() {
+            Ok(parent) => Ok(parent),
+            Err(err) => {
+                error!("Parent directory couldn't be resolved. Failed to create directory");
+                Err(TempDirErrors::DirectoryCreationFailed(err))
+            }
         }
-        Err(TempDirErrors::WrongTimeAmount) => {
-            error!("Unable to parse duration string: Invalid amount specified");
-            Err(TempDirErrors::WrongDurationString)
+    }
+
+    /// The path to store for the directory at the absolute `path`, relative
+    /// to the base directory if this directory uses relative paths
+    fn stored_path(&self, path: &Path) -> Result<PathBuf, TempDirErrors> {
+        if !self.relative {
+            return Ok(path.to_path_buf())
         }
-        _ => {
-            error!("Something unknown went wrong parsing the duration amount");
-            Err(TempDirErrors::WrongDurationString)
+        let relative_path = self
+            .in_store(base_path)
+            .and_then(|base| base.canonicalize().map_err(|_| TempDirErrors::CreationFailed))
+            .map(|base| path.strip_prefix(base).map(PathBuf::from));
+        match relative_path {
+            Ok(Ok(relative_path)) => Ok(relative_path),
+            _ => {
+                error!("Directory is not inside the base directory. Failed to create directory");
+                Err(TempDirErrors::CreationFailed)
+            }
         }
-    };
-    // return duration_amount;
-    let period_amount = match parse_period(duration) {
-        Ok(value) => {
-            Ok(value)
+    }
+
+    /// Points the link, if any, at the created directory. Only symlinks are
+    /// replaced, anything else at the location is left alone.
+    fn create_link(&self) -> Result<(), TempDirErrors> {
+        let (link, path) = match (&self.link, self.resolved_path()) {
+            (Some(link), Some(path)) => (link, path),
+            _ => return Ok(()),
+        };
+        let linked = match fs::symlink_metadata(link) {
+            Ok(metadata) if metadata.file_type().is_symlink() => {
+                info!("Retargeting {link:?}");
+                fs::remove_file(link).or_else(|_| fs::remove_dir(link)).and_then(|_| symlink_directory(&path, link))
+            }
+            Ok(_) => Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("{link:?} exists and is not a symlink"))),
+            Err(_) => symlink_directory(&path, link),
+        };
+        match linked {
+            Ok(()) => {
+                info!("Linked {link:?} to the directory");
+                Ok(())
+            }
+            Err(err) => {
+                error!("{link:?} couldn't be linked to the directory");
+                Err(TempDirErrors::LinkFailed(err))
+            }
         }
-        Err(TempDirErrors::WrongPeriodString) => {
-            error!("Unable to parse duration string: Invalid period specified");
-            Err(TempDirErrors::WrongDurationString)
+    }
+
+    /// Removes the link, if any, unless it was retargeted at another
+    /// directory since
+    fn remove_link(&self, path: &Path) {
+        let link = match &self.link {
+            Some(link) => link,
+            None => return,
+        };
+        if fs::read_link(link).is_ok_and(|target| target == path) {
+            match fs::remove_file(link).or_else(|_| fs::remove_dir(link)) {
+                Ok(()) => info!("Removed link {link:?}"),
+                Err(_) => warn!("Link {link:?} couldn't be removed"),
+            }
         }
-        Err(_) => {
-            error!("Something unknown went wrong parsing the duration period");
-            Err(TempDirErrors::WrongDurationString)
+    }
+
+    /// Copies the template, if any, into the created directory
+    fn copy_template(&self) -> Result<(), TempDirErrors> {
+        let (template, path) = match (&self.template, self.resolved_path()) {
+            (Some(template), Some(path)) => (template, path),
+            _ => return Ok(()),
+        };
+        let placeholders = [
+            ("{{name}}", self.name.clone()),
+            ("{{date}}", Local.timestamp(self.created_at, 0).format("%Y-%m-%d").to_string()),
+        ];
+        let placeholders = if self.expand_placeholders { &placeholders[..] } else { &[] };
+        match copy_template(template, &path, placeholders) {
+            Ok(()) => {
+                info!("Copied template {template:?}");
+                Ok(())
+            }
+            Err(err) => {
+                error!("Template {template:?} couldn't be copied");
+                Err(TempDirErrors::TemplateCopyFailed(err))
+            }
         }
-    };
+    }
 
-    if !duration_amount.is_err() && !period_amount.is_err() {
-        return Ok(duration_amount.unwrap() * period_amount.unwrap());
-    } else {
-        return Err(TempDirErrors::WrongDurationString);
+    fn write_meta_data(&self) -> Result<(), TempDirErrors> {
+        // A record without a path could never be cleaned up
+        if self.path.is_none() {
+            error!("Directory path is not specified. Temporary directory cannot be saved");
+            return Err(TempDirErrors::CreationFailed)
+        }
+
+        match self.in_store(|| manager::with_backend(|backend| backend.save(&self.detached()))) {
+            Ok(()) => {
+                info!("Temporary directory saved");
+                Ok(())
+            }
+            Err(err) => {
+                error!("Meta data couldn't be saved. Temporary directory cannot be saved");
+                Err(err)
+            }
+        }
     }
-}
-fn parse_amount(duration: &str) -> Result<i64, TempDirErrors> {
-    let regex_amount = Regex::new(r"[A-Za-z]+").unwrap();
-    let amount_vec: Vec<&str> = regex_amount.split(duration).filter(|x| *x != "").collect();
-    if amount_vec.len() != 1 {
-        return Err(TempDirErrors::WrongDurationString);
+
+    /// Key of this directory in the store. Records are keyed on the name and
+    /// path, so directories with the same name in different parents don't
+    /// collide.
+    fn store_key(&self) -> String {
+        if let Some(key) = &self.key {
+            return key.clone()
+        }
+
+        match &self.path {
+            Some(path) => format!("{}-{:016x}", self.name, fnv1a(path.as_os_str().as_encoded_bytes())),
+            None => self.name.clone(),
+        }
     }
-    let amount = amount_vec[0].parse::<i64>();
-    match amount {
-        Ok(value) => Ok(value),
-        Err(_) => Err(TempDirErrors::WrongTimeAmount),
+
+    /// Copy of the record for a backend, which mustn't hold on to the store
+    /// it is kept in
+    fn detached(&self) -> TemporaryDirectory {
+        let mut record = self.clone();
+        record.store = None;
+        record
     }
-}
-fn parse_period(duration: &str) -> Result<i64, TempDirErrors> {
-    // Need to account for possible unwrap error
-    let period_string: String = duration
-        .chars()
-        .filter(|x| x.is_alphabetic())
-        .map(|x| x.to_lowercase().next().unwrap())
-        .collect();
 
-    let period_amount = match period_string.as_str() {
-        "s" => Ok(PeriodStringValue::Second.value()),
-        "min" => Ok(PeriodStringValue::Minute.value()),
-        "h" => Ok(PeriodStringValue::Hour.value()),
-        "d" => Ok(PeriodStringValue::Day.value()),
-        "w" => Ok(PeriodStringValue::Week.value()),
-        "m" => Ok(PeriodStringValue::Month.value()),
-        _ => Err(TempDirErrors::WrongPeriodString),
-    };
-    period_amount
-}
\ No newline at end of file
+    /// Runs `f` with the store of this directory in use
+    fn in_store<T>(&self, f: impl FnOnce() -> T) -> T {
+        manager::with_store(self.store.as_ref(), f)
+    }
+
+    fn remove_directory(&self) -> Result<(), TempDirErrors> {
+        self.remove_directory_with(self.delete_contents, false, self.trash, None)
+    }
+
+    /// Deletes the contents at `path` except the entries preserved by the
+    /// exclude globs, which are moved below `keep_to` if it is given.
+    /// Returns whether entries were kept in place.
+    fn prune(&self, path: &Path, keep_to: Option<&Path>) -> Result<bool, TempDirErrors> {
+        check_deletable(path)?;
+        let exclude = self.exclude.iter().map(|glob| exclude_pattern(glob)).collect::<Result<Vec<Regex>, TempDirErrors>>()?;
+        let keep_to = keep_to.map(|keep_to| keep_to.join(&self.name));
+        match prune_recursively(path, path, &exclude, keep_to.as_deref()) {
+            Ok(kept) => Ok(kept),
+            Err(err) => {
+                error!("Unable to remove the contents of {path:?}");
+                Err(TempDirErrors::RemovalFailed(err))
+            }
+        }
+    }
+
+    /// Removes the directory, including its contents when `recursive` is set.
+    /// Symlinks inside it are only traversed with `follow_symlinks`. With
+    /// `trash`, the directory and its contents are moved to the trash instead.
+    /// `progress` is called after every removed file.
+    fn remove_directory_with(&self, recursive: bool, follow_symlinks: bool, trash: bool, progress: Option<&Progress>) -> Result<(), TempDirErrors> {
+        match self.resolved_path() {
+            Some(path) if fs::symlink_metadata(&path).is_err() => {
+                info!("{path:?} was already removed");
+                Ok(())
+            }
+            Some(path) => {
+                check_deletable(&path)?;
+                let mut freed = RemovalProgress::default();
+                let removed = if trash {
+                    move_to_trash(&path)
+                } else if recursive {
+                    let report = |removed: &RemovalProgress| {
+                        if let Some(progress) = progress {
+                            (progress.0)(self, removed)
+                        }
+                    };
+                    retry_removal(|| remove_recursively_with(&path, follow_symlinks, &mut freed, &report))
+                } else {
+                    retry_removal(|| fs::remove_dir(&path))
+                };
+                match removed {
+                    Ok(_) => {
+                        info!("Removed directory");
+                        history::record(Action::Delete, self, Some(&path), (!trash).then_some(freed.bytes));
+                        self.remove_link(&path);
+                        Ok(())
+                    }
+                    Err(err) => {
+                        error!("Unable to remove directory");
+                        Err(TempDirErrors::RemovalFailed(err))
+                    }
+                }
+            }
+            None => {
+                error!("Directory can't be removed, path is not specified");
+                Err(TempDirErrors::RemovalFailed(io::Error::new(io::ErrorKind::NotFound, "path is not specified")))
+            }
+        }
+    }
+}
+
+/// Summary like "scratch at /tmp/scratch, 2h 5min left"
+impl fmt::Display for TemporaryDirectory {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{}", self.name)?;
+        if let Some(path) = self.resolved_path() {
+            write!(formatter, " at {}", path.display())?;
+        }
+        if self.is_expired() {
+            write!(formatter, ", expired")?;
+        } else {
+            write!(formatter, ", {} left", self.format_remaining())?;
+        }
+        if self.pinned {
+            write!(formatter, ", pinned")?;
+        }
+        Ok(())
+    }
+}
+
+/// A temporary directory that is removed, together with its meta data, as soon
+/// as it goes out of scope. One that may not be deleted, e.g. because it lies
+/// outside of the allowed roots, is kept together with its meta data.
+pub struct ScopedTempDir {
+    tempdir: TemporaryDirectory,
+}
+impl ScopedTempDir {
+    /// Creates a temporary directory like `create_temp_dir`, but bound to the
+    /// lifetime of the returned guard.
+    pub fn scoped(name: &str, duration: &str) -> Result<ScopedTempDir, TempDirErrors> {
+        let tempdir = create_temp_dir(name, duration)?;
+        Ok(ScopedTempDir { tempdir })
+    }
+
+    pub fn directory(&self) -> &TemporaryDirectory {
+        &self.tempdir
+    }
+}
+/// Name of `ScopedTempDir` familiar from the `tempdir` and `tempfile` crates
+pub type TempDirGuard = ScopedTempDir;
+
+impl Drop for ScopedTempDir {
+    fn drop(&mut self) {
+        if let Some(path) = self.tempdir.resolved_path().filter(|path| fs::symlink_metadata(path).is_ok()) {
+            // A directory that mustn't be deleted stays tracked, so it isn't
+            // left behind unnoticed
+            if check_deletable(&path).is_err() {
+                return
+            }
+            match remove_recursively(&path, false) {
+                Ok(_) => {
+                    info!("Removed directory");
+                    history::record(Action::Delete, &self.tempdir, Some(&path), None);
+                }
+                Err(_) => error!("Unable to remove directory"),
+            }
+        }
+
+        match self.tempdir.in_store(Store::open) {
+            Ok(mut store) => {
+                store.remove(&self.tempdir);
+                match store.save() {
+                    Ok(()) => info!("Meta data of {} deleted", self.tempdir.name),
+                    Err(_) => error!("Meta data of {} couldn't be deleted", self.tempdir.name),
+                }
+            }
+            Err(_) => error!("Meta data of {} couldn't be deleted", self.tempdir.name),
+        }
+    }
+}
+
+/// Expands the placeholders in a directory name: `%d` as the current date,
+/// `%t` as the unix timestamp, `%pid` as the process id, `%r` as six random
+/// characters and `%%` as `%`. Also returns whether the name is random.
+fn expand_name(name: &str) -> Result<(String, bool), TempDirErrors> {
+    let mut random = false;
+    let mut expanded = String::new();
+    let mut rest = name;
+    while let Some(index) = rest.find('%') {
+        expanded.push_str(&rest[..index]);
+        let token = &rest[index + 1..];
+        let (value, length) = if token.starts_with("pid") {
+            (std::process::id().to_string(), 3)
+        } else if token.starts_with('d') {
+            (chrono::offset::Local::now().format("%Y-%m-%d").to_string(), 1)
+        } else if token.starts_with('t') {
+            (chrono::offset::Local::now().timestamp().to_string(), 1)
+        } else if token.starts_with('r') {
+            random = true;
+            (random_suffix(), 1)
+        } else if token.starts_with('%') {
+            ("%".to_string(), 1)
+        } else {
+            error!("Unknown placeholder in directory name {name}");
+            return Err(TempDirErrors::InvalidName)
+        };
+        expanded.push_str(&value);
+        rest = &token[length..];
+    }
+    expanded.push_str(rest);
+    Ok((expanded, random))
+}
+
+/// Six random lowercase letters and digits, like the suffix of `mktemp`
+fn random_suffix() -> String {
+    const ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+    // Every RandomState is seeded differently, which is random enough for
+    // names that are checked for collisions anyway
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(SystemTime::now().duration_since(UNIX_EPOCH).map(|time| time.as_nanos()).unwrap_or_default());
+    let mut value = hasher.finish();
+    (0..6)
+        .map(|_| {
+            let character = ALPHABET[(value % ALPHABET.len() as u64) as usize];
+            value /= ALPHABET.len() as u64;
+            character as char
+        })
+        .collect()
+}
+
+fn normalize_tag(tag: &str) -> String {
+    tag.trim().to_lowercase()
+}
+
+/// Creates a temporary directory named `name` in the creation parent that
+/// lives for `duration`, and returns the handle with its path set.
+pub fn create_temp_dir(name: &str, duration: &str) -> Result<TemporaryDirectory, TempDirErrors> {
+    TempDirManager::default().create(TemporaryDirectory::new(name.to_string(), duration.to_string())?)
+}
+
+/// An entry of the meta data store that couldn't be processed
+#[derive(Debug)]
+pub struct CleanError {
+    pub path: PathBuf,
+    pub reason: String,
+}
+
+/// Files and bytes removed so far while deleting a directory
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RemovalProgress {
+    pub files: u64,
+    pub bytes: u64,
+}
+
+/// Callback reporting the progress of deleting the contents of a directory.
+/// It is called from the threads doing the deletion.
+#[derive(Clone)]
+pub struct Progress(pub Arc<ProgressCallback>);
+pub type ProgressCallback = dyn Fn(&TemporaryDirectory, &RemovalProgress) + Send + Sync;
+impl fmt::Debug for Progress {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("Progress")
+    }
+}
+
+/// Settings for a `clean_directories_with` pass
+#[derive(Clone, Debug, Default)]
+pub struct CleanOptions {
+    /// Hook run for expired directories without an `on_expire` hook of
+    /// their own. Directories whose hook fails are never deleted.
+    pub on_expire: Option<String>,
+    /// Archive directory for expired directories without one of their own
+    pub archive_to: Option<PathBuf>,
+    /// Move expired directories to the trash instead of deleting them
+    pub trash: bool,
+    /// Directory entries preserved by `exclude` globs are moved to, for
+    /// directories without one of their own
+    pub keep_to: Option<PathBuf>,
+    /// Also delete directories of other users, see
+    /// `TemporaryDirectory::is_owned_by_current_user`
+    pub ignore_ownership: bool,
+    /// Extend expired directories modified within this window, instead of
+    /// deleting them, until they have been left alone that long
+    pub active_window: Option<Duration>,
+    /// Evict directories that haven't expired yet, soonest expiring first,
+    /// until all of them together use at most this many bytes
+    pub max_total_size: Option<u64>,
+    /// Evict directories that haven't expired yet, soonest expiring first,
+    /// while their file system has less than this many bytes available
+    pub min_free_space: Option<u64>,
+    /// Number of directories cleaned at the same time, 0 and 1 clean them
+    /// one after another
+    pub jobs: usize,
+    /// Called while the contents of expired directories are deleted
+    pub progress: Option<Progress>,
+    /// Also delete expired directories that still have contents
+    pub recursive: bool,
+    /// Delete the contents of symlinked directories during recursive
+    /// deletion, instead of only removing the symlinks
+    pub follow_symlinks: bool,
+    /// Only report the directories that would be deleted, without running
+    /// hooks or touching the file system
+    pub dry_run: bool,
+    /// Only clean directories carrying all of these tags
+    pub tags: Vec<String>,
+    /// Only clean directories whose name matches, see `glob_pattern`
+    pub name: Option<Regex>,
+    /// Only delete directories that expired at least this long ago
+    pub older_than: Option<Duration>,
+    /// Clock expiry is decided by, the system clock if not given
+    pub clock: Option<Arc<dyn Clock>>,
+    /// Daemon only: send a desktop notification this long before a
+    /// directory expires, see `notify_expiry`
+    pub notify_before: Option<Duration>,
+}
+
+impl CleanOptions {
+    /// Whether the tag and name filters select `tempdir`
+    fn selects(&self, tempdir: &TemporaryDirectory) -> bool {
+        tempdir.has_tags(&self.tags) && self.name.as_ref().is_none_or(|name| name.is_match(&tempdir.name))
+    }
+
+    /// Whether `tempdir` expired, at least `older_than` ago if given
+    fn expired_long_enough(&self, tempdir: &TemporaryDirectory) -> bool {
+        match self.older_than {
+            Some(older_than) => tempdir.is_expired() && tempdir.remaining_seconds() <= -(older_than.as_secs() as i64),
+            None => tempdir.is_expired(),
+        }
+    }
+}
+
+/// An expired directory deleted by a `clean_directories` pass, or that
+/// would be deleted in a dry run
+#[derive(Debug)]
+pub struct CleanedDir {
+    pub directory: TemporaryDirectory,
+    /// Where the directory was deleted from
+    pub path: PathBuf,
+}
+
+/// An expired directory a `clean_directories` pass kept, and why
+#[derive(Debug)]
+pub enum SkipReason {
+    /// Its contents were modified recently, so its lifetime was renewed
+    Renewed(TemporaryDirectory),
+    /// Its `on_expire` hook failed
+    HookFailed(TemporaryDirectory),
+    /// It is pinned
+    Pinned(TemporaryDirectory),
+    /// It belongs to another user and `ignore_ownership` isn't set
+    NotOwned(TemporaryDirectory),
+    /// Only its contents were removed and its lifetime restarted, see
+    /// `TemporaryDirectory::with_contents_only`
+    Emptied(TemporaryDirectory),
+    /// Its record was changed by another process since the store was read,
+    /// e.g. it was extended while its expiry hook ran
+    Changed(TemporaryDirectory),
+}
+impl SkipReason {
+    pub fn directory(&self) -> &TemporaryDirectory {
+        match self {
+            SkipReason::Renewed(directory)
+            | SkipReason::HookFailed(directory)
+            | SkipReason::Pinned(directory)
+            | SkipReason::NotOwned(directory)
+            | SkipReason::Emptied(directory)
+            | SkipReason::Changed(directory) => directory,
+        }
+    }
+}
+
+/// A file removed from a directory that is still alive, because it wasn't
+/// modified for the `file_max_age` of the directory
+#[derive(Debug)]
+pub struct ExpiredFile {
+    /// Name of the temporary directory it was in
+    pub directory: String,
+    pub path: PathBuf,
+    pub size: u64,
+    /// Last modification, in seconds since the epoch
+    pub modified: i64,
+}
+
+/// Outcome of a `clean_directories` pass
+#[derive(Debug, Default)]
+pub struct CleanReport {
+    pub deleted: Vec<CleanedDir>,
+    pub skipped: Vec<SkipReason>,
+    /// Files removed, or that would be in a dry run, see `ExpiredFile`
+    pub expired_files: Vec<ExpiredFile>,
+    pub errors: Vec<CleanError>,
+}
+
+/// Records in the store, keyed like the index, and the meta data files that
+/// couldn't be imported
+struct StoreScan {
+    entries: Vec<(String, TemporaryDirectory)>,
+    errors: Vec<CleanError>,
+}
+
+/// Reads the store without holding on to its lock, so hooks and removals can
+/// run while other processes use the store
+fn scan_store() -> Result<StoreScan, TempDirErrors> {
+    let (entries, errors) = Store::open()?.into_parts();
+    Ok(StoreScan { entries, errors })
+}
+
+/// All temporary directories tracked in the meta data store. Meta data that
+/// can't be read is logged and skipped.
+pub fn list_directories() -> Result<Vec<TemporaryDirectory>, TempDirErrors> {
+    TempDirManager::default().list()
+}
+
+/// The directories of the store in use, sorted by name
+pub(crate) fn read_directories() -> Result<Vec<TemporaryDirectory>, TempDirErrors> {
+    let scan = scan_store()?;
+    let mut directories: Vec<TemporaryDirectory> = scan.entries.into_iter().map(|(_, tempdir)| tempdir).collect();
+    directories.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(directories)
+}
+
+/// Order of the directories returned by `list_directories_with`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    #[default]
+    Name,
+    /// Soonest expiring first
+    Remaining,
+    /// Oldest first
+    Created,
+    /// Smallest first
+    Size,
+}
+
+/// Which directories `list_directories_with` returns, and in which order
+#[derive(Debug, Default)]
+pub struct ListOptions {
+    pub sort: SortKey,
+    /// Reverse the order
+    pub reverse: bool,
+    /// Only directories whose name matches, see `glob_pattern`
+    pub name: Option<Regex>,
+    /// Only directories carrying all of these tags
+    pub tags: Vec<String>,
+    /// Only expired directories
+    pub expired: bool,
+    /// Only directories that haven't expired yet
+    pub active: bool,
+    /// Clock expiry is decided by, the system clock if not given
+    pub clock: Option<Arc<dyn Clock>>,
+}
+
+/// The tracked directories selected by `options`, in their order
+pub fn list_directories_with(options: &ListOptions) -> Result<Vec<TemporaryDirectory>, TempDirErrors> {
+    let mut directories: Vec<TemporaryDirectory> = list_directories()?
+        .into_iter()
+        .map(|tempdir| match &options.clock {
+            Some(clock) => tempdir.with_clock(clock.clone()),
+            None => tempdir,
+        })
+        .filter(|tempdir| options.name.as_ref().is_none_or(|name| name.is_match(&tempdir.name)))
+        .filter(|tempdir| tempdir.has_tags(&options.tags))
+        .filter(|tempdir| !options.expired || tempdir.is_expired())
+        .filter(|tempdir| !options.active || !tempdir.is_expired())
+        .collect();
+
+    match options.sort {
+        // Already sorted by name
+        SortKey::Name => {}
+        SortKey::Remaining => directories.sort_by_key(|tempdir| tempdir.remaining_seconds()),
+        SortKey::Created => directories.sort_by_key(|tempdir| tempdir.created_at),
+        SortKey::Size => directories.sort_by_cached_key(|tempdir| tempdir.disk_usage().unwrap_or(0)),
+    }
+    if options.reverse {
+        directories.reverse();
+    }
+    Ok(directories)
+}
+
+/// Regular expression matching whole names against a glob like "build-*",
+/// where `*` matches any characters and `?` a single one
+pub fn glob_pattern(glob: &str) -> Result<Regex, TempDirErrors> {
+    let mut pattern = String::from("^");
+    for character in glob.chars() {
+        match character {
+            '*' => pattern.push_str(".*"),
+            '?' => pattern.push('.'),
+            _ => pattern.push_str(&regex::escape(&character.to_string())),
+        }
+    }
+    pattern.push('$');
+    Regex::new(&pattern).map_err(|err| TempDirErrors::InvalidFilter(err.to_string()))
+}
+
+/// Regular expression matching paths relative to a directory against an
+/// exclude glob, see `TemporaryDirectory::with_exclude`
+pub fn exclude_pattern(glob: &str) -> Result<Regex, TempDirErrors> {
+    let glob = glob.trim_start_matches("./").trim_start_matches('/');
+    // Globs without a separator match names at any depth
+    let mut pattern = String::from(if glob.contains('/') { "^" } else { "^(?:.*/)?" });
+    let mut characters = glob.chars().peekable();
+    while let Some(character) = characters.next() {
+        match character {
+            '*' if characters.peek() == Some(&'*') => {
+                characters.next();
+                if characters.peek() == Some(&'/') {
+                    characters.next();
+                    pattern.push_str("(?:.*/)?");
+                } else {
+                    pattern.push_str(".*");
+                }
+            }
+            '*' => pattern.push_str("[^/]*"),
+            '?' => pattern.push_str("[^/]"),
+            _ => pattern.push_str(&regex::escape(&character.to_string())),
+        }
+    }
+    pattern.push('$');
+    Regex::new(&pattern).map_err(|err| TempDirErrors::InvalidFilter(err.to_string()))
+}
+
+pub fn clean_directories() -> Result<CleanReport, TempDirErrors> {
+    clean_directories_with(&CleanOptions::default())
+}
+
+pub fn clean_directories_with(options: &CleanOptions) -> Result<CleanReport, TempDirErrors> {
+    TempDirManager::with_default_store(options.clone()).clean()
+}
+
+/// Deletes the expired directories of the store in use
+pub(crate) fn clean_store(options: &CleanOptions) -> Result<CleanReport, TempDirErrors> {
+    let scan = scan_store()?;
+    let mut report = CleanReport { errors: scan.errors, ..CleanReport::default() };
+
+    let (expired, alive): (Vec<_>, Vec<_>) = scan
+        .entries
+        .into_iter()
+        .map(|(key, tempdir)| match &options.clock {
+            Some(clock) => (key, tempdir.with_clock(clock.clone())),
+            None => (key, tempdir),
+        })
+        .filter(|(_, tempdir)| options.selects(tempdir))
+        .partition(|(_, tempdir)| options.expired_long_enough(tempdir) || tempdir.pending_deletion);
+
+    // Keys of deleted directories, or of those that would be in a dry run
+    let mut deleted_keys: Vec<String> = Vec::new();
+    if options.jobs <= 1 || expired.len() <= 1 {
+        for (key, temporary_directory) in expired {
+            deleted_keys.extend(clean_expired(key, temporary_directory, options, &mut report));
+        }
+    } else {
+        // Workers take directories off a shared queue, each with a report
+        // of its own that is merged once they are done
+        let queue = Mutex::new(expired.into_iter());
+        let store = manager::store_override();
+        let workers: Vec<thread::Result<(CleanReport, Vec<String>)>> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..options.jobs)
+                .map(|_| {
+                    scope.spawn(|| {
+                        manager::with_store(store.as_ref(), || {
+                            let mut worker_report = CleanReport::default();
+                            let mut worker_keys = Vec::new();
+                            loop {
+                                let next = queue.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).next();
+                                let (key, temporary_directory) = match next {
+                                    Some(next) => next,
+                                    None => break,
+                                };
+                                // A panic loses only this directory, not what the
+                                // worker already recorded
+                                let path = temporary_directory.resolved_path().unwrap_or_default();
+                                let cleaned = panic::catch_unwind(AssertUnwindSafe(|| clean_expired(key, temporary_directory, options, &mut worker_report)));
+                                match cleaned {
+                                    Ok(deleted) => worker_keys.extend(deleted),
+                                    Err(_) => {
+                                        error!("Cleaning {path:?} panicked");
+                                        worker_report.errors.push(CleanError { path, reason: "cleaning it panicked".to_string() });
+                                    }
+                                }
+                            }
+                            (worker_report, worker_keys)
+                        })
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|handle| handle.join()).collect()
+        });
+        for worker in workers {
+            let (worker_report, worker_keys) = match worker {
+                Ok(worker) => worker,
+                Err(_) => {
+                    error!("A clean worker panicked");
+                    report.errors.push(CleanError { path: PathBuf::new(), reason: "a clean worker panicked".to_string() });
+                    continue;
+                }
+            };
+            report.deleted.extend(worker_report.deleted);
+            report.skipped.extend(worker_report.skipped);
+            report.errors.extend(worker_report.errors);
+            deleted_keys.extend(worker_keys);
+        }
+    }
+
+    for (_, temporary_directory) in &alive {
+        let max_age = match &temporary_directory.file_max_age {
+            Some(max_age) if !temporary_directory.pinned => max_age,
+            _ => continue,
+        };
+        if options.ignore_ownership || temporary_directory.is_owned_by_current_user() {
+            remove_old_files(temporary_directory, max_age, options.dry_run, &mut report);
+        }
+    }
+
+    if let Some(max_total_size) = options.max_total_size {
+        evict_over_quota(max_total_size, options, &mut deleted_keys, &mut report)?;
+    }
+    if let Some(min_free_space) = options.min_free_space {
+        evict_for_free_space(min_free_space, options, &mut deleted_keys, &mut report)?;
+    }
+
+    if deleted_keys.is_empty() || options.dry_run {
+        return Ok(report)
+    }
+
+    // Only the records as they were read go, a directory extended or
+    // recreated under the same key in the meantime keeps its new record
+    let read: HashMap<String, &TemporaryDirectory> = report.deleted.iter().map(|cleaned| (cleaned.directory.store_key(), &cleaned.directory)).collect();
+    let mut store = Store::open()?;
+    for key in deleted_keys {
+        match (store.get(&key), read.get(&key)) {
+            (Some(current), Some(read)) if !same_record(current, read) => {
+                warn!("{key} changed since the store was read, keeping its meta data");
+            }
+            _ => {
+                if let Some(tempdir) = store.remove_key(&key) {
+                    info!("Meta data of {} deleted", tempdir.name);
+                }
+            }
+        }
+    }
+    if let Err(err) = store.save() {
+        error!("Meta data of the deleted directories couldn't be removed");
+        report.errors.push(CleanError { path: store.index_path(), reason: err.to_string() });
+    }
+
+    Ok(report)
+}
+
+/// Deletes an expired directory unless it is pinned or was renewed, recording
+/// the outcome in `report`. Returns the key of the directory if it was (or
+/// would be in a dry run) deleted.
+fn clean_expired(key: String, mut temporary_directory: TemporaryDirectory, options: &CleanOptions, report: &mut CleanReport) -> Option<String> {
+    if temporary_directory.pinned {
+        report.skipped.push(SkipReason::Pinned(temporary_directory));
+        return None
+    }
+    if !options.ignore_ownership && !temporary_directory.is_owned_by_current_user() {
+        warn!("Skipping {} because it belongs to another user", temporary_directory.name);
+        report.skipped.push(SkipReason::NotOwned(temporary_directory));
+        return None
+    }
+
+    if options.dry_run {
+        let active = options.active_window.and_then(|window| temporary_directory.active_end_time(window));
+        if active.is_some() || (temporary_directory.renew_on_access && temporary_directory.renewed_end_time().is_some()) {
+            report.skipped.push(SkipReason::Renewed(temporary_directory));
+            return None
+        }
+        let path = temporary_directory.resolved_path().unwrap_or_default();
+        let recursive = options.recursive || temporary_directory.delete_contents;
+        if !recursive && fs::read_dir(&path).is_ok_and(|mut entries| entries.next().is_some()) {
+            report.errors.push(CleanError { path, reason: "directory is not empty".to_string() });
+            return None
+        }
+        report.deleted.push(CleanedDir { directory: temporary_directory, path });
+        return Some(key)
+    }
+
+    // Directories marked for deletion were deleted on purpose, so they are
+    // never renewed
+    let renewable = !temporary_directory.pending_deletion;
+    let active = renewable && options.active_window.is_some_and(|window| temporary_directory.extend_while_active(window));
+    if active || (renewable && temporary_directory.renew_on_access && temporary_directory.renew()) {
+        report.skipped.push(SkipReason::Renewed(temporary_directory));
+        return None
+    }
+
+    let recursive = options.recursive || temporary_directory.delete_contents;
+    delete_expired(temporary_directory, recursive, options, report).then_some(key)
+}
+
+/// Runs the expiry hook, archives and deletes a directory, recording the
+/// outcome in `report`. A failing hook aborts the deletion. Returns whether
+/// the directory was deleted.
+fn delete_expired(mut temporary_directory: TemporaryDirectory, recursive: bool, options: &CleanOptions, report: &mut CleanReport) -> bool {
+    if let Some(hook) = temporary_directory.on_expire.as_ref().or(options.on_expire.as_ref()) {
+        if !run_expiry_hook(hook, &temporary_directory) {
+            info!("Keeping {} because its expiry hook failed", temporary_directory.name);
+            report.skipped.push(SkipReason::HookFailed(temporary_directory));
+            return false
+        }
+    }
+
+    let path = temporary_directory.resolved_path().unwrap_or_default();
+    if let Some(archive_to) = temporary_directory.archive_to.as_ref().or(options.archive_to.as_ref()) {
+        if let Err(err) = archive_directory(&temporary_directory, &path, archive_to) {
+            // Never delete what couldn't be archived
+            report.errors.push(CleanError { path, reason: err.to_string() });
+            return false
+        }
+    }
+
+    // The store was read without its lock, and the hook may have taken a
+    // while, so check nobody extended or recreated the directory since
+    match temporary_directory.in_store(Store::open) {
+        Ok(store) if store.get(&temporary_directory.store_key()).is_some_and(|current| same_record(current, &temporary_directory)) => {}
+        Ok(_) => {
+            info!("Keeping {} because its record changed since the store was read", temporary_directory.name);
+            report.skipped.push(SkipReason::Changed(temporary_directory));
+            return false
+        }
+        Err(err) => {
+            report.errors.push(CleanError { path, reason: err.to_string() });
+            return false
+        }
+    }
+
+    let keep_to = temporary_directory.keep_to.clone().or(options.keep_to.clone());
+    // Directories marked for deletion go completely
+    if temporary_directory.contents_only && !temporary_directory.pending_deletion {
+        if let Err(err) = temporary_directory.prune(&path, keep_to.as_deref()).and_then(|_| temporary_directory.touch()) {
+            report.errors.push(CleanError { path, reason: err.to_string() });
+            return false
+        }
+        info!("Emptied {}", temporary_directory.name);
+        report.skipped.push(SkipReason::Emptied(temporary_directory));
+        return false
+    }
+
+    if !temporary_directory.exclude.is_empty() {
+        match temporary_directory.prune(&path, keep_to.as_deref()) {
+            Ok(true) => {
+                info!("Kept the preserved entries of {} in place", temporary_directory.name);
+                report.deleted.push(CleanedDir { directory: temporary_directory, path });
+                return true
+            }
+            // Nothing is left, so the directory itself goes as well
+            Ok(false) => {}
+            Err(err) => {
+                report.errors.push(CleanError { path, reason: err.to_string() });
+                return false
+            }
+        }
+    }
+
+    let trash = options.trash || temporary_directory.trash;
+    if let Err(err) = temporary_directory.remove_directory_with(recursive, options.follow_symlinks, trash, options.progress.as_ref()) {
+        // Keep the meta data so a later pass can try again
+        report.errors.push(CleanError { path, reason: err.to_string() });
+        return false
+    }
+    report.deleted.push(CleanedDir { directory: temporary_directory, path });
+    true
+}
+
+/// Whether `current` is still the record `read` was read from, rather than
+/// an extended or recreated one
+fn same_record(current: &TemporaryDirectory, read: &TemporaryDirectory) -> bool {
+    current.created_at == read.created_at && current.end_time == read.end_time
+}
+
+/// Removes the files of a directory that weren't modified for `max_age`,
+/// along with the directories that leaves empty. In a dry run they are only
+/// reported.
+fn remove_old_files(temporary_directory: &TemporaryDirectory, max_age: &str, dry_run: bool, report: &mut CleanReport) {
+    let path = match temporary_directory.resolved_path() {
+        Some(path) if path.is_dir() => path,
+        _ => return,
+    };
+    let cutoff = match parse_file_max_age(max_age) {
+        Ok(max_age) => temporary_directory.clock.now() - max_age,
+        Err(err) => {
+            error!("Invalid file max age {max_age:?} for {}", temporary_directory.name);
+            report.errors.push(CleanError { path, reason: err.to_string() });
+            return
+        }
+    };
+    if let Err(err) = check_deletable(&path) {
+        report.errors.push(CleanError { path, reason: err.to_string() });
+        return
+    }
+    remove_files_before(&temporary_directory.name, &path, cutoff, dry_run, report);
+}
+
+/// Parses the file max age of a directory into seconds, which have to be
+/// more than zero
+fn parse_file_max_age(max_age: &str) -> Result<i64, TempDirErrors> {
+    match duration::parse(max_age)? {
+        seconds if seconds > 0 => Ok(seconds),
+        _ => {
+            error!("File max age {max_age:?} isn't longer than zero");
+            Err(TempDirErrors::InvalidDuration(format!("file max age {max_age:?} has to be longer than zero")))
+        }
+    }
+}
+
+/// Removes the files below `directory` last modified before `cutoff`.
+/// Symlinks are removed like files, never followed. Returns whether any
+/// were removed.
+fn remove_files_before(name: &str, directory: &Path, cutoff: i64, dry_run: bool, report: &mut CleanReport) -> bool {
+    let entries = match fs::read_dir(directory) {
+        Ok(entries) => entries,
+        Err(err) => {
+            report.errors.push(CleanError { path: directory.to_path_buf(), reason: err.to_string() });
+            return false
+        }
+    };
+
+    let mut removed = false;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let metadata = match fs::symlink_metadata(&path) {
+            Ok(metadata) => metadata,
+            Err(_) => continue,
+        };
+        if metadata.is_dir() {
+            // Remove directories only once the files that were in them are gone
+            let emptied = remove_files_before(name, &path, cutoff, dry_run, report);
+            if emptied && !dry_run && fs::read_dir(&path).is_ok_and(|mut entries| entries.next().is_none()) {
+                let _ = fs::remove_dir(&path);
+            }
+            removed |= emptied;
+            continue;
+        }
+
+        let modified = match metadata.modified().ok().and_then(|modified| modified.duration_since(UNIX_EPOCH).ok()) {
+            Some(modified) => modified.as_secs() as i64,
+            None => continue,
+        };
+        if modified >= cutoff {
+            continue;
+        }
+        if !dry_run {
+            if let Err(err) = fs::remove_file(&path) {
+                report.errors.push(CleanError { path, reason: err.to_string() });
+                continue;
+            }
+            info!("Removed {path:?}, last modified {}", timestamp::format(modified).unwrap_or_default());
+        }
+        report.expired_files.push(ExpiredFile { directory: name.to_string(), path, size: metadata.len(), modified });
+        removed = true;
+    }
+    removed
+}
+
+/// Deletes directories that haven't expired yet, soonest expiring first,
+/// until all tracked directories together use at most `max_total_size`
+/// bytes. Pinned directories count towards the total but are never evicted.
+fn evict_over_quota(max_total_size: u64, options: &CleanOptions, deleted_keys: &mut Vec<String>, report: &mut CleanReport) -> Result<(), TempDirErrors> {
+    let remaining = remaining_directories(deleted_keys)?;
+    let mut total: u64 = remaining.iter().map(|(_, _, size)| size).sum();
+    if total <= max_total_size {
+        return Ok(())
+    }
+    info!("Directories use {total} bytes, evicting until they use at most {max_total_size}");
+
+    for (key, temporary_directory, size) in evictable(remaining, options) {
+        if total <= max_total_size {
+            break;
+        }
+        if evict(key, temporary_directory, size, options, deleted_keys, report) {
+            total -= size;
+        }
+    }
+    if total > max_total_size {
+        warn!("Directories still use {total} bytes, more than the quota of {max_total_size}");
+    }
+    Ok(())
+}
+
+/// Deletes directories that haven't expired yet, soonest expiring first,
+/// while the file system holding them has less than `min_free_space` bytes
+/// available. Pinned directories are never evicted.
+fn evict_for_free_space(min_free_space: u64, options: &CleanOptions, deleted_keys: &mut Vec<String>, report: &mut CleanReport) -> Result<(), TempDirErrors> {
+    let remaining = remaining_directories(deleted_keys)?;
+    // A dry run frees nothing, so count what it would have freed
+    let mut freed: u64 = 0;
+    for (key, temporary_directory, size) in evictable(remaining, options) {
+        let path = temporary_directory.resolved_path().unwrap_or_default();
+        let available = match available_space(&path) {
+            Ok(available) => available.saturating_add(freed),
+            Err(err) => {
+                warn!("Free space of {path:?} couldn't be determined: {err}");
+                continue;
+            }
+        };
+        if available >= min_free_space {
+            continue;
+        }
+        info!("Only {available} bytes free around {path:?}, evicting {key}");
+        if evict(key, temporary_directory, size, options, deleted_keys, report) && options.dry_run {
+            freed += size;
+        }
+    }
+    Ok(())
+}
+
+/// Tracked directories that weren't deleted, with their size in bytes
+fn remaining_directories(deleted_keys: &[String]) -> Result<Vec<(String, TemporaryDirectory, u64)>, TempDirErrors> {
+    Ok(scan_store()?
+        .entries
+        .into_iter()
+        .filter(|(key, _)| !deleted_keys.contains(key))
+        .map(|(key, tempdir)| {
+            let size = tempdir.disk_usage().unwrap_or(0);
+            (key, tempdir, size)
+        })
+        .collect())
+}
+
+/// The directories of `remaining` that may be evicted, soonest expiring first
+fn evictable(mut remaining: Vec<(String, TemporaryDirectory, u64)>, options: &CleanOptions) -> Vec<(String, TemporaryDirectory, u64)> {
+    remaining.retain(|(_, tempdir, size)| {
+        *size > 0 && !tempdir.pinned && options.selects(tempdir) && (options.ignore_ownership || tempdir.is_owned_by_current_user())
+    });
+    remaining.sort_by(|a, b| a.1.end_time.cmp(&b.1.end_time).then(a.1.created_at.cmp(&b.1.created_at)));
+    remaining
+}
+
+/// Deletes a directory before it expires, together with its contents, or
+/// only reports it in a dry run. Returns whether it was (or would be) deleted.
+fn evict(key: String, temporary_directory: TemporaryDirectory, size: u64, options: &CleanOptions, deleted_keys: &mut Vec<String>, report: &mut CleanReport) -> bool {
+    if options.dry_run {
+        let path = temporary_directory.resolved_path().unwrap_or_default();
+        report.deleted.push(CleanedDir { directory: temporary_directory, path });
+    } else if !delete_expired(temporary_directory, true, options, report) {
+        return false
+    }
+    info!("Evicted {key}, freeing {size} bytes");
+    deleted_keys.push(key);
+    true
+}
+
+/// Bytes available to unprivileged users on the file system holding `path`
+#[cfg(unix)]
+fn available_space(path: &Path) -> io::Result<u64> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = CString::new(path.as_os_str().as_bytes())?;
+    let mut stats: libc::statvfs = unsafe { std::mem::zeroed() };
+    // SAFETY: `path` is a valid C string and `stats` is a writable statvfs
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stats) } != 0 {
+        return Err(io::Error::last_os_error())
+    }
+    Ok(stats.f_bavail as u64 * stats.f_frsize as u64)
+}
+
+/// Bytes available to the current user on the volume holding `path`
+#[cfg(windows)]
+fn available_space(path: &Path) -> io::Result<u64> {
+    use std::os::windows::ffi::OsStrExt;
+
+    extern "system" {
+        fn GetDiskFreeSpaceExW(directory: *const u16, available: *mut u64, total: *mut u64, free: *mut u64) -> i32;
+    }
+    let path: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+    let mut available = 0;
+    // SAFETY: `path` is nul terminated, the other pointers may be null
+    if unsafe { GetDiskFreeSpaceExW(path.as_ptr(), &mut available, std::ptr::null_mut(), std::ptr::null_mut()) } == 0 {
+        return Err(io::Error::last_os_error())
+    }
+    Ok(available)
+}
+
+/// Stays resident and cleans expired directories every `interval`, so they
+/// are removed without anyone having to run `clean`. Never returns.
+pub fn run_daemon(interval: Duration) -> ! {
+    run_daemon_with(interval, &CleanOptions::default())
+}
+
+pub fn run_daemon_with(interval: Duration, options: &CleanOptions) -> ! {
+    info!("Cleaning expired directories every {}s", interval.as_secs());
+    let mut notified = HashSet::new();
+    loop {
+        daemon_pass(options, &mut notified);
+        thread::sleep(interval);
+    }
+}
+
+/// One pass of `run_daemon_with`, for applications running the loop
+/// themselves: notifies about soon expiring directories and cleans expired
+/// ones. `notified` holds the directories already notified about, with the
+/// end time they were notified for, so extended directories are notified
+/// again. It has to be kept from one pass to the next.
+pub fn daemon_pass(options: &CleanOptions, notified: &mut HashSet<(String, i64)>) {
+    if let Some(notify_before) = options.notify_before {
+        let window = notify_before.as_secs() as i64;
+        for tempdir in list_directories().unwrap_or_default() {
+            let tempdir = match &options.clock {
+                Some(clock) => tempdir.with_clock(clock.clone()),
+                None => tempdir,
+            };
+            let remaining = tempdir.remaining_seconds();
+            let expiring = remaining > 0 && remaining <= window && !tempdir.pinned;
+            if expiring && notified.insert((tempdir.store_key(), tempdir.end_time)) {
+                notify_expiry(&tempdir);
+            }
+        }
+    }
+    match clean_directories_with(options) {
+        Ok(report) => {
+            for failure in report.errors {
+                warn!("{:?} couldn't be cleaned: {}", failure.path, failure.reason);
+            }
+        }
+        Err(err) => error!("Cleaning expired directories failed: {err}"),
+    }
+}
+
+/// Outcome of a `repair` pass
+#[derive(Debug, Default)]
+pub struct RepairReport {
+    /// Records removed because their directory no longer exists
+    pub removed: usize,
+    /// Records removed because another record tracks the same directory
+    pub deduplicated: usize,
+    /// Records that couldn't be read or have missing/invalid fields. These
+    /// are only reported, never removed.
+    pub invalid: Vec<CleanError>,
+}
+
+/// Reconciles the meta data store with the file system. Records of
+/// directories that no longer exist and duplicate records are removed, no
+/// directory is ever deleted.
+pub fn repair() -> Result<RepairReport, TempDirErrors> {
+    let mut store = Store::open()?;
+    let mut report = RepairReport { invalid: store.take_invalid(), ..RepairReport::default() };
+    let index = store.index_path();
+
+    let mut stale: Vec<String> = Vec::new();
+    let mut tracked: Vec<(PathBuf, String, i64)> = Vec::new();
+    for temporary_directory in store.directories() {
+        let key = temporary_directory.store_key();
+        let directory = match temporary_directory.resolved_path() {
+            Some(directory) => directory,
+            None => {
+                warn!("{key} has no directory path");
+                report.invalid.push(CleanError { path: index.clone(), reason: format!("{key}: no directory path") });
+                continue;
+            }
+        };
+        if temporary_directory.end_time < temporary_directory.created_at {
+            warn!("{key} expires before it was created");
+            report.invalid.push(CleanError { path: index.clone(), reason: format!("{key}: end time before creation time") });
+            continue;
+        }
+
+        if !directory.exists() {
+            info!("{directory:?} no longer exists, removing its meta data");
+            stale.push(key);
+            continue;
+        }
+        tracked.push((directory, key, temporary_directory.end_time));
+    }
+    for key in stale {
+        store.remove_key(&key);
+        report.removed += 1;
+    }
+
+    // Of several records for the same directory, keep the one expiring last
+    tracked.sort_by(|a, b| a.0.cmp(&b.0).then(b.2.cmp(&a.2)));
+    for duplicate in tracked.windows(2).filter(|pair| pair[0].0 == pair[1].0) {
+        info!("{} is a duplicate record, removing it", duplicate[1].1);
+        store.remove_key(&duplicate[1].1);
+        report.deduplicated += 1;
+    }
+
+    if report.removed + report.deduplicated > 0 {
+        store.save()?;
+    }
+    Ok(report)
+}
+
+/// Kind of problem `diagnose` found in the meta data store
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Problem {
+    /// Meta data that couldn't be read, or a record without directory path
+    Unparsable,
+    /// The tracked directory no longer exists
+    MissingDirectory,
+    /// The tracked directory lies outside all of the allowed roots
+    OutsideRoots,
+    /// Another record tracks the same directory
+    DuplicateRecord,
+    /// Several directories with this name are tracked, so commands taking a
+    /// name need the parent as well
+    AmbiguousName,
+    /// The directory expires before it was created, or was created in the
+    /// future
+    ClockAnomaly,
+}
+impl Problem {
+    /// Whether `repair` fixes this problem
+    pub fn is_fixable(&self) -> bool {
+        matches!(self, Problem::MissingDirectory | Problem::DuplicateRecord)
+    }
+}
+
+/// A problem with a single record of the meta data store
+#[derive(Debug)]
+pub struct Finding {
+    pub problem: Problem,
+    /// The tracked directory, or the meta data file that couldn't be read
+    pub path: PathBuf,
+    pub detail: String,
+}
+
+/// Checks the integrity of the meta data store without changing anything.
+/// When `roots` isn't empty, every tracked directory has to lie in one of
+/// them. Findings are sorted by path.
+pub fn diagnose(roots: &[PathBuf]) -> Result<Vec<Finding>, TempDirErrors> {
+    let (entries, invalid) = Store::open()?.into_parts();
+    let mut findings: Vec<Finding> = invalid
+        .into_iter()
+        .map(|invalid| Finding { problem: Problem::Unparsable, path: invalid.path, detail: invalid.reason })
+        .collect();
+    let roots: Vec<PathBuf> = roots.iter().map(|root| root.canonicalize().unwrap_or(root.clone())).collect();
+
+    let mut tracked: Vec<(PathBuf, String)> = Vec::new();
+    for (key, temporary_directory) in entries {
+        let path = match temporary_directory.resolved_path() {
+            Some(path) => path.canonicalize().unwrap_or(path),
+            None => {
+                let path = PathBuf::from(&temporary_directory.name);
+                findings.push(Finding { problem: Problem::Unparsable, path, detail: format!("{key}: no directory path") });
+                continue;
+            }
+        };
+
+        if temporary_directory.end_time < temporary_directory.created_at {
+            let detail = format!("{key}: end time before creation time");
+            findings.push(Finding { problem: Problem::ClockAnomaly, path: path.clone(), detail });
+        } else if temporary_directory.created_in_future(temporary_directory.clock.now()) {
+            let detail = format!("{key}: created in the future");
+            findings.push(Finding { problem: Problem::ClockAnomaly, path: path.clone(), detail });
+        }
+        if !path.exists() {
+            findings.push(Finding { problem: Problem::MissingDirectory, path, detail: format!("{key}: directory doesn't exist") });
+            continue;
+        }
+        if !roots.is_empty() && !roots.iter().any(|root| path.starts_with(root)) {
+            let detail = format!("{key}: outside of the allowed roots");
+            findings.push(Finding { problem: Problem::OutsideRoots, path: path.clone(), detail });
+        }
+        tracked.push((path, temporary_directory.name));
+    }
+
+    tracked.sort();
+    for pair in tracked.windows(2).filter(|pair| pair[0].0 == pair[1].0) {
+        let detail = format!("{}: tracked more than once", pair[1].1);
+        findings.push(Finding { problem: Problem::DuplicateRecord, path: pair[1].0.clone(), detail });
+    }
+    tracked.dedup_by(|a, b| a.0 == b.0);
+    for (path, name) in &tracked {
+        if tracked.iter().filter(|(_, other)| other == name).count() > 1 {
+            let detail = format!("{name}: several directories with this name are tracked");
+            findings.push(Finding { problem: Problem::AmbiguousName, path: path.clone(), detail });
+        }
+    }
+
+    findings.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(findings)
+}
+
+/// Directories directly inside `root` that no meta data record points to.
+/// The store itself is never reported.
+pub fn untracked_directories(root: &Path) -> Result<Vec<PathBuf>, TempDirErrors> {
+    let store = info_store_path()?.canonicalize().ok();
+    let tracked: Vec<PathBuf> = list_directories()?
+        .iter()
+        .filter_map(TemporaryDirectory::resolved_path)
+        .map(|path| path.canonicalize().unwrap_or(path))
+        .collect();
+
+    let entries = match fs::read_dir(root) {
+        Ok(entries) => entries,
+        Err(err) => {
+            error!("{root:?} couldn't be read");
+            return Err(TempDirErrors::DirectoryReadFailed(err))
+        }
+    };
+
+    let mut untracked = Vec::new();
+    for entry in entries {
+        let path = match entry {
+            Ok(entry) => entry.path(),
+            Err(err) => {
+                error!("Entry of {root:?} couldn't be read");
+                return Err(TempDirErrors::DirectoryReadFailed(err))
+            }
+        };
+        // Symlinks are never adopted or deleted
+        if !fs::symlink_metadata(&path).is_ok_and(|metadata| metadata.is_dir()) {
+            continue;
+        }
+        let path = path.canonicalize().unwrap_or(path);
+        if Some(&path) == store.as_ref() || tracked.contains(&path) {
+            continue;
+        }
+        untracked.push(path);
+    }
+    untracked.sort();
+    Ok(untracked)
+}
+
+/// Deletes a directory found by `untracked_directories` with its contents.
+/// Symlinks inside it are removed, not followed. Paths `check_deletable`
+/// refuses are left alone.
+pub fn delete_untracked(path: &Path) -> Result<(), TempDirErrors> {
+    check_deletable(path)?;
+    match remove_recursively(path, false) {
+        Ok(()) => {
+            info!("Deleted untracked directory {path:?}");
+            Ok(())
+        }
+        Err(err) => {
+            error!("Unable to remove {path:?}");
+            Err(TempDirErrors::RemovalFailed(err))
+        }
+    }
+}
+
+/// Finds the meta data of the directory called `name`. When directories with
+/// that name exist in several parents, `parent` has to tell them apart.
+fn find_meta_data(name: &str, parent: Option<&Path>) -> Result<TemporaryDirectory, TempDirErrors> {
+    let parent = match parent.map(Path::canonicalize) {
+        Some(Ok(parent)) => Some(parent),
+        Some(Err(_)) => {
+            error!("Parent directory couldn't be resolved");
+            return Err(TempDirErrors::NotFound)
+        }
+        None => None,
+    };
+
+    let mut matches: Vec<TemporaryDirectory> = scan_store()?
+        .entries
+        .into_iter()
+        .map(|(_, tempdir)| tempdir)
+        .filter(|tempdir| tempdir.name == name)
+        .filter(|tempdir| match &parent {
+            Some(parent) => tempdir.resolved_path().is_some_and(|path| path.parent() == Some(parent)),
+            None => true,
+        })
+        .collect();
+
+    match matches.len() {
+        0 => {
+            error!("No meta data found for {name}");
+            Err(TempDirErrors::NotFound)
+        }
+        1 => Ok(matches.remove(0)),
+        _ => {
+            error!("Several directories called {name} are tracked, specify the parent");
+            Err(TempDirErrors::AmbiguousName)
+        }
+    }
+}
+
+/// Stops tracking the temporary directory with the given name. Only the meta
+/// data is removed, the directory itself is left untouched so it will never
+/// be deleted by `clean_directories`.
+pub fn cancel_expiry(name: &str, parent: Option<&Path>) -> Result<(), TempDirErrors> {
+    manager::with_backend(|backend| {
+        let tempdir = backend.load(name, parent)?;
+        backend.remove(&tempdir)
+    })?;
+    info!("{name} will no longer expire");
+    Ok(())
+}
+
+/// Most recent modification time, as a timestamp, of `path` or anything
+/// below it. Symlinks are not followed.
+fn last_modification(path: &Path) -> Option<i64> {
+    let metadata = fs::symlink_metadata(path).ok()?;
+    let mut latest = metadata
+        .modified()
+        .ok()?
+        .duration_since(UNIX_EPOCH)
+        .ok()?
+        .as_secs() as i64;
+
+    if metadata.is_dir() {
+        for entry in fs::read_dir(path).ok()?.flatten() {
+            if let Some(modified) = last_modification(&entry.path()) {
+                latest = latest.max(modified);
+            }
+        }
+    }
+    Some(latest)
+}
+
+/// Removes `path` and everything below it. Symlinks are removed themselves
+/// and never traversed, unless `follow_symlinks` is set, in which case the
+/// contents of symlinked directories are deleted as well.
+fn remove_recursively(path: &Path, follow_symlinks: bool) -> io::Result<()> {
+    remove_recursively_with(path, follow_symlinks, &mut RemovalProgress::default(), &|_| {})
+}
+
+/// Like `remove_recursively`, counting removed files in `removed` and
+/// calling `progress` after each of them
+fn remove_recursively_with(path: &Path, follow_symlinks: bool, removed: &mut RemovalProgress, progress: &dyn Fn(&RemovalProgress)) -> io::Result<()> {
+    let metadata = fs::symlink_metadata(path)?;
+    let file_type = metadata.file_type();
+    if file_type.is_symlink() {
+        if follow_symlinks && path.is_dir() {
+            for entry in fs::read_dir(path)? {
+                remove_recursively_with(&entry?.path(), follow_symlinks, removed, progress)?;
+            }
+        }
+        // Directory symlinks on Windows have to be removed as directories
+        return fs::remove_file(path).or_else(|_| fs::remove_dir(path))
+    }
+
+    if !file_type.is_dir() {
+        fs::remove_file(path)?;
+        removed.files += 1;
+        removed.bytes += metadata.len();
+        progress(removed);
+        return Ok(())
+    }
+
+    for entry in fs::read_dir(path)? {
+        remove_recursively_with(&entry?.path(), follow_symlinks, removed, progress)?;
+    }
+    fs::remove_dir(path)
+}
+
+/// Removes everything in `directory` except entries whose path relative to
+/// `root` matches one of `exclude`. Symlinks are removed, never followed.
+fn prune_recursively(root: &Path, directory: &Path, exclude: &[Regex], keep_to: Option<&Path>) -> io::Result<bool> {
+    let mut kept = false;
+    for entry in fs::read_dir(directory)? {
+        let path = entry?.path();
+        let relative = path.strip_prefix(root).unwrap_or(&path);
+        let relative_name = relative.components().map(|component| component.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/");
+        if exclude.iter().any(|pattern| pattern.is_match(&relative_name)) {
+            match keep_to {
+                Some(keep_to) => move_entry(&path, &keep_to.join(relative))?,
+                None => kept = true,
+            }
+            continue;
+        }
+
+        if fs::symlink_metadata(&path)?.is_dir() {
+            if prune_recursively(root, &path, exclude, keep_to)? {
+                kept = true;
+            } else {
+                fs::remove_dir(&path)?;
+            }
+        } else {
+            fs::remove_file(&path)?;
+        }
+    }
+    Ok(kept)
+}
+
+/// Moves a file or directory, copying it when `target` is on another file
+/// system
+fn move_entry(path: &Path, target: &Path) -> io::Result<()> {
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    match fs::rename(path, target) {
+        Err(err) if err.kind() == io::ErrorKind::CrossesDevices => {
+            copy_recursively(path, target)?;
+            remove_recursively(path, false)
+        }
+        result => result,
+    }
+}
+
+#[cfg(unix)]
+fn symlink_directory(target: &Path, link: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+#[cfg(windows)]
+fn symlink_directory(target: &Path, link: &Path) -> io::Result<()> {
+    std::os::windows::fs::symlink_dir(target, link)
+}
+
+/// Copies the contents of `template` into the existing directory `target`,
+/// replacing `placeholders` in the contents of UTF-8 files. Symlinks are
+/// copied as links on Unix.
+fn copy_template(template: &Path, target: &Path, placeholders: &[(&str, String)]) -> io::Result<()> {
+    for entry in fs::read_dir(template)? {
+        let entry = entry?;
+        let (path, target) = (entry.path(), target.join(entry.file_name()));
+        let metadata = fs::symlink_metadata(&path)?;
+        if metadata.is_dir() {
+            fs::create_dir(&target)?;
+            copy_template(&path, &target, placeholders)?;
+        } else if metadata.file_type().is_symlink() || placeholders.is_empty() {
+            copy_recursively(&path, &target)?;
+        } else {
+            match String::from_utf8(fs::read(&path)?) {
+                Ok(mut contents) => {
+                    for (placeholder, value) in placeholders {
+                        contents = contents.replace(placeholder, value);
+                    }
+                    fs::write(&target, contents)?;
+                    fs::set_permissions(&target, metadata.permissions())?;
+                }
+                Err(_) => copy_recursively(&path, &target)?,
+            }
+        }
+    }
+    Ok(())
+}
+
+fn copy_recursively(path: &Path, target: &Path) -> io::Result<()> {
+    let metadata = fs::symlink_metadata(path)?;
+    if metadata.is_dir() {
+        fs::create_dir_all(target)?;
+        for entry in fs::read_dir(path)? {
+            let entry = entry?;
+            copy_recursively(&entry.path(), &target.join(entry.file_name()))?;
+        }
+        return Ok(())
+    }
+    if metadata.file_type().is_symlink() {
+        #[cfg(unix)]
+        return std::os::unix::fs::symlink(fs::read_link(path)?, target);
+    }
+    fs::copy(path, target).map(|_| ())
+}
+
+/// Attempts at removing a directory. On Windows virus scanners and editors
+/// briefly keep files open, which makes removal fail.
+const REMOVAL_ATTEMPTS: u32 = 5;
+/// Wait before the second attempt, doubled for every further one
+const REMOVAL_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Runs `remove`, retrying with exponential backoff while it fails because
+/// files are in use
+fn retry_removal(mut remove: impl FnMut() -> io::Result<()>) -> io::Result<()> {
+    let mut backoff = REMOVAL_BACKOFF;
+    for attempt in 1..REMOVAL_ATTEMPTS {
+        match remove() {
+            Err(err) if is_in_use(&err) => {
+                warn!("Removal attempt {attempt} failed, files are in use: {err}. Retrying in {backoff:?}");
+                thread::sleep(backoff);
+                backoff *= 2;
+            }
+            result => return result,
+        }
+    }
+    remove()
+}
+
+/// Whether removal failed because another process has files open, which
+/// only prevents removal on Windows: access denied, sharing and lock
+/// violations, or a directory still holding files pending deletion
+fn is_in_use(err: &io::Error) -> bool {
+    cfg!(windows) && matches!(err.raw_os_error(), Some(5 | 32 | 33 | 145))
+}
+
+/// User id of this process, on Unix
+#[cfg(unix)]
+fn current_uid() -> Option<u32> {
+    Some(unsafe { libc::getuid() })
+}
+
+#[cfg(not(unix))]
+fn current_uid() -> Option<u32> {
+    None
+}
+
+#[cfg(unix)]
+fn file_owner(metadata: &fs::Metadata) -> Option<u32> {
+    use std::os::unix::fs::MetadataExt;
+    Some(metadata.uid())
+}
+
+#[cfg(not(unix))]
+fn file_owner(_metadata: &fs::Metadata) -> Option<u32> {
+    None
+}
+
+/// Roots temporary directories have to lie within to be deleted, from
+/// `TEMPDIR_ROOTS`, separated like `PATH`. Empty when no roots are set.
+pub fn allowed_roots() -> Vec<PathBuf> {
+    match env::var_os("TEMPDIR_ROOTS") {
+        Some(roots) => env::split_paths(&roots).filter(|root| !root.as_os_str().is_empty()).collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Refuses to delete `path` unless it lies within one of the `allowed_roots`,
+/// when there are any, and isn't a file system root, the home directory or a
+/// mount point. Guards against meta data that was corrupted or edited to
+/// point somewhere else. A symlink is checked as the link itself, which is
+/// all that gets deleted.
+fn check_deletable(path: &Path) -> Result<(), TempDirErrors> {
+    let refuse = |reason: &str| {
+        error!("Refusing to delete {path:?}: {reason}");
+        Err(TempDirErrors::UnsafeDeletion(path.to_path_buf(), reason.to_string()))
+    };
+
+    let (parent, name) = match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) => (parent, name),
+        _ => return refuse("it is a file system root"),
+    };
+    let parent = if parent.as_os_str().is_empty() { Path::new(".") } else { parent };
+    let canonical = match parent.canonicalize() {
+        Ok(parent) => parent.join(name),
+        Err(err) => return refuse(&format!("its location couldn't be resolved: {err}")),
+    };
+
+    let home = env::var_os("HOME").and_then(|home| PathBuf::from(home).canonicalize().ok());
+    if home.as_ref() == Some(&canonical) {
+        return refuse("it is the home directory")
+    }
+    if is_mount_point(&canonical) {
+        return refuse("it is a mount point")
+    }
+
+    let roots: Vec<PathBuf> = allowed_roots().iter().map(|root| root.canonicalize().unwrap_or(root.clone())).collect();
+    if roots.contains(&canonical) {
+        return refuse("it is one of the allowed roots")
+    }
+    if !roots.is_empty() && !roots.iter().any(|root| canonical.starts_with(root)) {
+        return refuse("it is outside of the allowed roots")
+    }
+    Ok(())
+}
+
+/// Whether a file system is mounted at `path`, which is the case when it
+/// lies on another device than its parent. Symlinks are never mount points.
+#[cfg(unix)]
+fn is_mount_point(path: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    let parent = match path.parent() {
+        Some(parent) => parent,
+        None => return true,
+    };
+    match (fs::symlink_metadata(path), fs::metadata(parent)) {
+        (Ok(metadata), Ok(parent)) => metadata.is_dir() && metadata.dev() != parent.dev(),
+        _ => false,
+    }
+}
+
+#[cfg(not(unix))]
+fn is_mount_point(path: &Path) -> bool {
+    path.parent().is_none()
+}
+
+/// Moves `path` into the trash of the user, following the freedesktop.org
+/// trash specification (`$XDG_DATA_HOME/Trash`), or `~/.Trash` on macOS. The
+/// trash has to be on the same file system as `path`.
+fn move_to_trash(path: &Path) -> io::Result<()> {
+    if cfg!(windows) {
+        return Err(io::Error::new(io::ErrorKind::Unsupported, "moving to the trash isn't supported on Windows"))
+    }
+    let home = env::var_os("HOME").map(PathBuf::from);
+    let trash = match (env::var_os("XDG_DATA_HOME"), home) {
+        (_, Some(home)) if cfg!(target_os = "macos") => home.join(".Trash"),
+        (Some(data), _) => PathBuf::from(data).join("Trash"),
+        (None, Some(home)) => home.join(".local").join("share").join("Trash"),
+        (None, None) => return Err(io::Error::new(io::ErrorKind::NotFound, "trash location couldn't be determined")),
+    };
+    let path = path.canonicalize()?;
+    let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+
+    if cfg!(target_os = "macos") {
+        fs::create_dir_all(&trash)?;
+        let mut target = trash.join(&name);
+        for attempt in 2.. {
+            if fs::symlink_metadata(&target).is_err() {
+                break;
+            }
+            target = trash.join(format!("{name}.{attempt}"));
+        }
+        return fs::rename(&path, target)
+    }
+
+    let files = trash.join("files");
+    let info = trash.join("info");
+    fs::create_dir_all(&files)?;
+    fs::create_dir_all(&info)?;
+
+    // The info file is created first and exclusively, claiming the name
+    let mut trashed_name = name.clone();
+    let mut attempt = 1;
+    let (info_path, mut info_file) = loop {
+        let info_path = info.join(format!("{trashed_name}.trashinfo"));
+        if fs::symlink_metadata(files.join(&trashed_name)).is_err() {
+            match OpenOptions::new().write(true).create_new(true).open(&info_path) {
+                Ok(file) => break (info_path, file),
+                Err(err) if err.kind() != io::ErrorKind::AlreadyExists => return Err(err),
+                Err(_) => {}
+            }
+        }
+        attempt += 1;
+        trashed_name = format!("{name}.{attempt}");
+    };
+    let deletion_date = chrono::offset::Local::now().format("%Y-%m-%dT%H:%M:%S");
+    let written = writeln!(info_file, "[Trash Info]\nPath={}\nDeletionDate={deletion_date}", path.display());
+    if let Err(err) = written.and_then(|_| fs::rename(&path, files.join(&trashed_name))) {
+        let _ = fs::remove_file(&info_path);
+        return Err(err)
+    }
+    Ok(())
+}
+
+/// Sums the sizes of all files below `path`, without following symlinks
+fn directory_size(path: &Path) -> Result<u64, TempDirErrors> {
+    let entries = match fs::read_dir(path) {
+        Ok(entries) => entries,
+        Err(err) => {
+            error!("{path:?} couldn't be read");
+            return Err(TempDirErrors::DirectoryReadFailed(err))
+        }
+    };
+
+    let mut size = 0;
+    for entry in entries {
+        let entry_path = match entry {
+            Ok(entry) => entry.path(),
+            Err(err) => {
+                error!("Entry of {path:?} couldn't be read");
+                return Err(TempDirErrors::DirectoryReadFailed(err))
+            }
+        };
+        match fs::symlink_metadata(&entry_path) {
+            Ok(metadata) if metadata.is_dir() => size += directory_size(&entry_path)?,
+            Ok(metadata) => size += metadata.len(),
+            Err(err) => {
+                error!("{entry_path:?} couldn't be read");
+                return Err(TempDirErrors::DirectoryReadFailed(err))
+            }
+        }
+    }
+    Ok(size)
+}
+
+/// Compresses the contents of `path` into `<name>-<timestamp>.tar.gz` inside
+/// `archive_to` with the system `tar`, returning the archive
+fn archive_directory(tempdir: &TemporaryDirectory, path: &Path, archive_to: &Path) -> Result<PathBuf, TempDirErrors> {
+    if let Err(err) = fs::create_dir_all(archive_to) {
+        error!("Archive directory {archive_to:?} couldn't be created");
+        return Err(TempDirErrors::ArchiveFailed(err))
+    }
+    let timestamp = chrono::offset::Local::now().format("%Y%m%dT%H%M%S");
+    let archive = archive_to.join(format!("{}-{timestamp}.tar.gz", tempdir.name));
+
+    let status = Command::new("tar").arg("-czf").arg(&archive).arg("-C").arg(path).arg(".").status();
+    match status {
+        Ok(status) if status.success() => {
+            info!("{} archived to {archive:?}", tempdir.name);
+            Ok(archive)
+        }
+        Ok(status) => {
+            error!("Archiving {} failed with {status}", tempdir.name);
+            let _ = fs::remove_file(&archive);
+            Err(TempDirErrors::ArchiveFailed(io::Error::other(format!("tar exited with {status}"))))
+        }
+        Err(err) => {
+            error!("tar couldn't be started to archive {}", tempdir.name);
+            Err(TempDirErrors::ArchiveFailed(err))
+        }
+    }
+}
+
+/// Tells the user `tempdir` is about to expire, with a desktop notification
+/// when built with the `notifications` feature. It is always logged.
+fn notify_expiry(tempdir: &TemporaryDirectory) {
+    let path = tempdir.resolved_path().unwrap_or_default();
+    let message = format!("{} ({}) will be deleted in {}", tempdir.name, path.display(), tempdir.format_remaining());
+    warn!("{message}");
+
+    #[cfg(feature = "notifications")]
+    {
+        let mut command = if cfg!(target_os = "macos") {
+            let mut command = Command::new("osascript");
+            command.arg("-e").arg(format!("display notification {message:?} with title \"tempdir\""));
+            command
+        } else {
+            let mut command = Command::new("notify-send");
+            command.arg("tempdir").arg(&message);
+            command
+        };
+        if !command.status().is_ok_and(|status| status.success()) {
+            error!("Desktop notification for {} couldn't be sent", tempdir.name);
+        }
+    }
+}
+
+/// Runs the `on_expire` hook of a temporary directory, returns whether it succeeded
+fn run_expiry_hook(hook: &str, tempdir: &TemporaryDirectory) -> bool {
+    if hook.trim().is_empty() {
+        error!("Expiry hook of {} is empty", tempdir.name);
+        return false
+    }
+
+    match hook_command(hook, tempdir.resolved_path().as_deref()).status() {
+        Ok(status) if status.success() => {
+            info!("Expiry hook of {} finished", tempdir.name);
+            true
+        }
+        Ok(status) => {
+            error!("Expiry hook of {} failed with {status}", tempdir.name);
+            false
+        }
+        Err(_) => {
+            error!("Expiry hook of {} couldn't be started", tempdir.name);
+            false
+        }
+    }
+}
+
+/// The shell running `hook`, with `path` as its first argument `$1`, so
+/// quoting, pipes and variables work like on the command line
+#[cfg(unix)]
+fn hook_command(hook: &str, path: Option<&Path>) -> Command {
+    let mut command = Command::new("sh");
+    command.arg("-c").arg(hook).arg("tempdir").args(path);
+    command
+}
+
+/// `cmd` running `hook`, with `path` as its last argument
+#[cfg(not(unix))]
+fn hook_command(hook: &str, path: Option<&Path>) -> Command {
+    let mut command = Command::new("cmd");
+    command.arg("/C").arg(hook).args(path);
+    command
+}
+
+fn read_meta_data(path: &Path) -> Result<TemporaryDirectory, TempDirErrors> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(err) => {
+            error!("{path:?} meta data file couldn't be read");
+            return Err(TempDirErrors::MetaDataReadFailed(err))
+        }
+    };
+
+    let record = match serde_json::from_reader::<_, serde_json::Value>(file) {
+        Ok(record) => record,
+        Err(err) => {
+            error!("{path:?} temporary directory couldn't be parsed");
+            return Err(TempDirErrors::MetaDataReadFailed(err.into()))
+        }
+    };
+    match migration::read_record(record) {
+        Ok(data) => Ok(data),
+        Err(err) => {
+            error!("{path:?} temporary directory couldn't be parsed");
+            Err(TempDirErrors::MetaDataReadFailed(err))
+        }
+    }
+}
+
+/// Files in the store that are not meta data of a temporary directory, and
+/// the folder holding the stores of profiles, see `Config::profile`
+const STORE_HELPER_FILES: [&str; 5] = ["tempdir.lock", "index.json", "index.json.tmp", "events.jsonl", "profiles"];
+
+/// Whether a file in the store holds the meta data of a temporary directory.
+/// Known helper files are skipped silently, anything else with a warning.
+fn is_meta_data_file(path: &Path) -> bool {
+    let file_name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+    if STORE_HELPER_FILES.contains(&file_name) {
+        return false
+    }
+
+    if !path.is_file() || path.extension().is_none_or(|extension| extension != "json") {
+        warn!("{path:?} is not a meta data file. Skipping");
+        return false
+    }
+    true
+}
+
+/// 64 bit FNV-1a hash, which unlike the std hashers is stable across releases
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Fails when something other than a directory occupies the store location
+fn check_store_folder(path: &Path) -> Result<(), TempDirErrors> {
+    if path.exists() && !path.is_dir() {
+        error!("{path:?} exists but is not a directory. It can't be used as meta data directory");
+        return Err(TempDirErrors::StoreFolderError(format!("{path:?} exists but is not a directory")))
+    }
+    Ok(())
+}
+
+/// Directory that relative paths are resolved against: `TEMPDIR_BASE` if set,
+/// otherwise the directory containing the meta data store
+fn base_path() -> Result<PathBuf, TempDirErrors> {
+    if let Some(base) = env::var_os("TEMPDIR_BASE") {
+        return Ok(PathBuf::from(base))
+    }
+
+    match info_store_path()?.parent() {
+        Some(path) => Ok(path.to_path_buf()),
+        None => Err(TempDirErrors::StorePathUnavailable),
+    }
+}
+
+/// Directory new temporary directories are created in. Precedence is a
+/// command line flag, then the `TEMPDIR_PARENT` environment variable, then
+/// the current working directory.
+fn creation_parent() -> PathBuf {
+    match env::var_os("TEMPDIR_PARENT") {
+        Some(parent) => PathBuf::from(parent),
+        None => PathBuf::from("."),
+    }
+}
+
+/// Directory meta data files are stored in. Precedence is the store of a
+/// `TempDirManager`, then a command line flag, then the `TEMPDIR_STORE`
+/// environment variable, then the platform data directory. Meta data found
+/// in the old `temporary_directories` folder next to the executable is moved
+/// to the data directory. A manager with a backend of its own has no store
+/// directory.
+fn info_store_path() -> Result<PathBuf, TempDirErrors> {
+    match manager::store_override() {
+        Some(manager::StoreLocation::Path(store)) => return Ok(store),
+        Some(manager::StoreLocation::Backend(_)) => return Err(TempDirErrors::StorePathUnavailable),
+        None => {}
+    }
+    if let Some(store) = env::var_os("TEMPDIR_STORE") {
+        return Ok(PathBuf::from(store))
+    }
+
+    let legacy_store = legacy_store_path();
+    let store = match data_store_path() {
+        Some(store) => store,
+        None => {
+            warn!("Data directory couldn't be determined. Storing meta data next to the executable");
+            return legacy_store
+        }
+    };
+
+    if let Ok(legacy_store) = legacy_store {
+        if legacy_store.is_dir() && !store.exists() {
+            return match migrate_store(&legacy_store, &store) {
+                Ok(()) => Ok(store),
+                Err(_) => {
+                    error!("Meta data couldn't be moved to {store:?}. Using {legacy_store:?}");
+                    Ok(legacy_store)
+                }
+            }
+        }
+    }
+    Ok(store)
+}
+
+/// `tempdir` folder in the platform data directory: `$XDG_DATA_HOME` or
+/// `~/.local/share` on Linux, `~/Library/Application Support` on macOS and
+/// `%APPDATA%` on Windows
+fn data_store_path() -> Option<PathBuf> {
+    let data = if cfg!(windows) {
+        PathBuf::from(env::var_os("APPDATA")?)
+    } else if cfg!(target_os = "macos") {
+        PathBuf::from(env::var_os("HOME")?).join("Library").join("Application Support")
+    } else {
+        match env::var_os("XDG_DATA_HOME") {
+            Some(data) => PathBuf::from(data),
+            None => PathBuf::from(env::var_os("HOME")?).join(".local").join("share"),
+        }
+    };
+    Some(data.join("tempdir"))
+}
+
+/// The `temporary_directories` folder next to the executable, where meta data
+/// used to be stored
+fn legacy_store_path() -> Result<PathBuf, TempDirErrors> {
+    let path_to_exe = match env::current_exe() {
+        Ok(path) => path,
+        Err(_) => {
+            error!("Path to the executable couldn't be determined");
+            return Err(TempDirErrors::StorePathUnavailable)
+        }
+    };
+
+    match path_to_exe.parent() {
+        Some(path) => {
+            let mut folder = PathBuf::from(path);
+            folder.push("temporary_directories");
+            Ok(folder)
+        }
+        None => {
+            error!("Executable has no parent directory to store meta data in");
+            Err(TempDirErrors::StorePathUnavailable)
+        }
+    }
+}
+
+/// Moves all meta data files from the old store to the new one
+fn migrate_store(legacy_store: &Path, store: &Path) -> io::Result<()> {
+    info!("Moving meta data from {legacy_store:?} to {store:?}");
+    fs::create_dir_all(store)?;
+    for entry in fs::read_dir(legacy_store)? {
+        let source = entry?.path();
+        let target = store.join(source.file_name().unwrap_or_default());
+        // Renaming fails across file systems, fall back to copying
+        if fs::rename(&source, &target).is_err() {
+            fs::copy(&source, &target)?;
+            fs::remove_file(&source)?;
+        }
+    }
+    let _ = fs::remove_dir(legacy_store);
+    Ok(())
+}
+
+/// Formats an amount of seconds using the two largest units of the duration
+/// string grammar, see `duration::humanize`
+pub fn format_remaining(seconds: i64) -> String {
+    duration::humanize(seconds)
+}
+
+/// Parses a size like "10G" into bytes. Units are B, K, M, G and T, in
+/// powers of 1024, with an optional trailing "B" or "iB" ("10GB", "10GiB"),
+/// and fractions are allowed ("1.5G"). A bare number is in bytes.
+pub fn parse_size(size: &str) -> Result<u64, TempDirErrors> {
+    let size = size.trim();
+    let split = size.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(size.len());
+    let (amount, unit) = size.split_at(split);
+    let unit = unit.trim().to_ascii_uppercase();
+    let unit = unit.strip_suffix("IB").or_else(|| unit.strip_suffix('B')).unwrap_or(&unit);
+
+    let exponent = match unit {
+        "" => 0,
+        "K" => 1,
+        "M" => 2,
+        "G" => 3,
+        "T" => 4,
+        _ => {
+            error!("Unable to parse size: Unknown unit {unit}");
+            return Err(TempDirErrors::WrongSizeString)
+        }
+    };
+    match amount.parse::<f64>() {
+        Ok(amount) if amount.is_finite() => Ok((amount * 1024f64.powi(exponent)) as u64),
+        _ => {
+            error!("Unable to parse size: Invalid amount {amount:?}");
+            Err(TempDirErrors::WrongSizeString)
+        }
+    }
+}
+
+/// Parses a duration string like `parse_duration_string`, returning the
+/// lifetime as a `Duration`. Negative amounts are rejected.
+pub fn parse_duration(duration: &str) -> Result<Duration, TempDirErrors> {
+    let seconds = parse_duration_string(duration)?;
+    match u64::try_from(seconds) {
+        Ok(seconds) => Ok(Duration::from_secs(seconds)),
+        Err(_) => {
+            error!("Unable to parse duration string: Duration can't be negative");
+            Err(TempDirErrors::WrongDurationString)
+        }
+    }
+}
+
+/// Parses an RFC 3339 timestamp into a unix timestamp. Without an offset,
+/// e.g. "2024-12-31T18:00:00", the time is taken as local time.
+pub fn parse_deadline(until: &str) -> Result<i64, TempDirErrors> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(until) {
+        return Ok(time.timestamp())
+    }
+
+    let local_time = NaiveDateTime::parse_from_str(until, "%Y-%m-%dT%H:%M:%S")
+        .ok()
+        .and_then(|time| Local.from_local_datetime(&time).earliest());
+    match local_time {
+        Some(time) => Ok(time.timestamp()),
+        None => {
+            error!("Unable to parse expiry time {until}");
+            Err(TempDirErrors::InvalidDeadline)
+        }
+    }
+}
+
+/// Parses a duration string like "4w" into seconds, see `duration::parse`.
+/// A leading `-` gives a negative amount of seconds.
+pub fn parse_duration_string(duration: &str) -> Result<i64, TempDirErrors> {
+    duration::parse(duration)
+}
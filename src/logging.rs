@@ -0,0 +1,33 @@
+//! Log macros of the library. With the `log` feature they forward to the
+//! `log` crate, without it they compile to nothing, so library users that
+//! don't log don't have to depend on it.
+
+#[cfg(feature = "log")]
+pub(crate) use log::{error, info, warn};
+
+// The arguments are still passed to `format_args!`, so variables that are
+// only logged don't turn into unused warnings. `warn` is the name of a
+// built-in attribute, hence the prefixed names.
+#[cfg(not(feature = "log"))]
+macro_rules! log_error {
+    ($($arg:tt)+) => {{
+        let _ = format_args!($($arg)+);
+    }};
+}
+
+#[cfg(not(feature = "log"))]
+macro_rules! log_info {
+    ($($arg:tt)+) => {{
+        let _ = format_args!($($arg)+);
+    }};
+}
+
+#[cfg(not(feature = "log"))]
+macro_rules! log_warn {
+    ($($arg:tt)+) => {{
+        let _ = format_args!($($arg)+);
+    }};
+}
+
+#[cfg(not(feature = "log"))]
+pub(crate) use {log_error as error, log_info as info, log_warn as warn};
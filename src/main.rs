@@ -1,48 +1,1201 @@
-use clap::Parser;
-use env_logger::Env;
-use tempdir::{TemporaryDirectory, clean_directories};
+mod completions;
+
+use chrono::{Local, TimeZone};
+use clap::{CommandFactory, Parser};
+use env_logger::{Env, Target};
+use log::{error, info, warn, LevelFilter};
+use std::env;
+use std::error::Error;
+use std::io::{self, IsTerminal};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode};
+use serde_json::{json, Value};
+use regex::Regex;
+use completions::Shell;
+use tempdir::{allowed_roots, history, CleanError, CleanOptions, Config, ListOptions, Profile, Problem, Progress, RemovalProgress, SkipReason, SortKey, TempDirErrors, TemporaryDirectory, cancel_expiry, clean_directories_with, delete_untracked, diagnose, glob_pattern, list_directories_with, parse_duration, parse_duration_string, parse_size, repair, run_daemon_with, untracked_directories, watch_removals};
 
 /// A program to create a temporary directory. The directory
 /// deletes itself after the specified amount of time
 #[derive(Parser)]
-#[clap(author, version, about, long_about = None)]
+#[clap(author, version, about, long_about = None, after_help = EXIT_CODES)]
 struct Args {
     #[command(subcommand)]
     action: Actions,
+
+    /// Don't log anything, not even errors
+    #[clap(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// Log more: -v for progress, -vv for details, -vvv for everything.
+    /// Only warnings and errors are logged by default
+    #[clap(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    /// Log messages of this level and above, overrides -q and -v
+    #[clap(long, global = true, value_enum)]
+    log_level: Option<LogLevel>,
+
+    /// Directory to keep the meta data in, overrides TEMPDIR_STORE and the
+    /// config file
+    #[clap(long, global = true, value_parser)]
+    store_path: Option<PathBuf>,
+
+    /// Use the store and directories of this profile of the config file.
+    /// Profiles not in the config file keep their meta data in
+    /// profiles/<name> inside the default store
+    #[clap(long, global = true, value_parser)]
+    profile: Option<String>,
+
+    /// Output format of list, status, clean and history
+    #[clap(long, global = true, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Config file to use instead of ~/.config/tempdir/config.toml
+    #[clap(long, global = true, value_parser)]
+    config: Option<PathBuf>,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+enum LogLevel {
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+impl LogLevel {
+    fn filter(self) -> LevelFilter {
+        match self {
+            LogLevel::Off => LevelFilter::Off,
+            LogLevel::Error => LevelFilter::Error,
+            LogLevel::Warn => LevelFilter::Warn,
+            LogLevel::Info => LevelFilter::Info,
+            LogLevel::Debug => LevelFilter::Debug,
+            LogLevel::Trace => LevelFilter::Trace,
+        }
+    }
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+enum SortOrder {
+    Name,
+    /// Soonest expiring first
+    Remaining,
+    /// Oldest first
+    Created,
+    /// Smallest first
+    Size,
 }
 
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+enum Format {
+    /// Tab separated columns and plain text
+    Text,
+    /// JSON, for scripts
+    Json,
+}
+
+// Parsed once per run, so the size of `Create` doesn't matter
+#[allow(clippy::large_enum_variant)]
 #[derive(clap::Subcommand, Debug)]
 enum Actions {
     Create {
-        /// Name of the tempory folder to create
+        /// Name of the tempory folder to create, a random name is generated
+        /// when it is left out. Placeholders: %d date, %t timestamp,
+        /// %pid process id, %r random characters
         #[clap(short, long, value_parser)]
+        name: Option<String>,
+
+        /// Start of the generated random name
+        #[clap(long, value_parser, conflicts_with = "name", default_value = "tmp-")]
+        prefix: String,
+
+        /// Duration the directory will live, defaults to the
+        /// `default_duration` of the config file.
+        /// Examples: 1d, 4w, 8m, 1h30min, P1DT2H
+        #[clap(short, long, value_parser)]
+        duration: Option<String>,
+
+        /// Expire at this time instead of after a duration, as RFC 3339
+        /// with or without offset. Example: 2024-12-31T18:00:00
+        #[clap(long, value_parser, conflicts_with = "duration")]
+        until: Option<String>,
+
+        /// Command to run before the directory is deleted on expiry, through
+        /// `sh -c` with the directory path as $1, e.g. 'cp -r "$1" /srv/backup'
+        #[clap(long, value_parser)]
+        on_expire: Option<String>,
+
+        /// Store the path relative to the base directory (TEMPDIR_BASE, or
+        /// the directory containing the meta data store)
+        #[clap(long)]
+        relative: bool,
+
+        /// Restart the lifetime whenever the directory contents are modified
+        #[clap(long)]
+        renew_on_access: bool,
+
+        /// Delete the directory on expiry even if it still has contents
+        #[clap(long)]
+        delete_contents: bool,
+
+        /// Compress the contents into a timestamped .tar.gz in this directory
+        /// before deleting it on expiry
+        #[clap(long, value_parser)]
+        archive_to: Option<PathBuf>,
+
+        /// Move the directory to the trash on expiry instead of deleting it
+        #[clap(long)]
+        trash: bool,
+
+        /// Place a symlink to the new temporary folder here, replacing a
+        /// symlink to a previous one. It is removed along with the folder
+        #[clap(long, value_parser)]
+        link: Option<PathBuf>,
+
+        /// Copy this directory tree into the new temporary folder
+        #[clap(long, value_parser)]
+        template: Option<PathBuf>,
+
+        /// Replace {{name}} and {{date}} in the contents of text files
+        /// copied from the template
+        #[clap(long, requires = "template")]
+        expand_placeholders: bool,
+
+        /// Remove files inside the directory on every clean once they weren't
+        /// modified for this long. Examples: 7d, 12h
+        #[clap(long, value_parser)]
+        file_max_age: Option<String>,
+
+        /// Only delete the contents on expiry, keeping the directory and
+        /// restarting its lifetime
+        #[clap(long)]
+        contents_only: bool,
+
+        /// Keep entries matching this glob on expiry, deleting only the rest,
+        /// can be given several times. Examples: '*.log', 'results/**'
+        #[clap(long, value_parser)]
+        exclude: Vec<String>,
+
+        /// Move the entries kept by --exclude into this directory on expiry
+        /// and delete the temporary folder
+        #[clap(long, value_parser, requires = "exclude")]
+        keep_to: Option<PathBuf>,
+
+        /// Directory to create the temporary folder in, created if missing.
+        /// Defaults to TEMPDIR_PARENT, the config file, or the current directory
+        #[clap(short, long, visible_alias = "path", value_parser)]
+        parent: Option<PathBuf>,
+
+        /// Label to filter `list` and `clean` by, can be given several times
+        #[clap(long = "tag", value_parser)]
+        tags: Vec<String>,
+
+        /// Succeed with the path of the folder when it already exists,
+        /// tracking it if it isn't tracked yet, instead of failing
+        #[clap(long)]
+        if_not_exists: bool,
+
+        /// Restart the lifetime of a folder that already exists
+        #[clap(long, requires = "if_not_exists")]
+        refresh: bool,
+    },
+    Clean {
+        /// Also delete expired directories that still have contents
+        #[clap(short, long)]
+        recursive: bool,
+
+        /// Delete the contents of symlinked directories when deleting
+        /// recursively, instead of only removing the symlinks
+        #[clap(long)]
+        follow_symlinks: bool,
+
+        /// Only list the directories that would be deleted, with their
+        /// size and expiry time
+        #[clap(long)]
+        dry_run: bool,
+
+        /// Move expired directories to the trash instead of deleting them
+        #[clap(long)]
+        trash: bool,
+
+        /// Also delete expired directories that belong to other users
+        #[clap(long)]
+        ignore_ownership: bool,
+
+        /// Extend expired directories modified within this window instead
+        /// of deleting them. Examples: 30min, 2h
+        #[clap(long, value_parser)]
+        keep_active: Option<String>,
+
+        /// Also delete directories that haven't expired yet, soonest
+        /// expiring first, until all of them together use at most this
+        /// much. Examples: 500M, 10G
+        #[clap(long, value_parser)]
+        max_total_size: Option<String>,
+
+        /// Number of directories to delete at the same time
+        #[clap(short, long, value_parser, default_value_t = 1)]
+        jobs: usize,
+
+        /// Only clean directories with this tag, can be given several times
+        #[clap(long = "tag", value_parser)]
+        tags: Vec<String>,
+
+        /// Only delete directories that expired at least this long ago.
+        /// Examples: 30d, 12h
+        #[clap(long, value_parser)]
+        older_than: Option<String>,
+
+        /// Only clean directories whose name matches this glob, where * matches
+        /// any characters and ? a single one. Example: 'ci-*'
+        #[clap(long, value_parser)]
+        name_glob: Option<String>,
+    },
+    /// Run a command inside a new temporary directory, which is deleted
+    /// once the command exits
+    Exec {
+        /// Name of the temporary folder to create
+        #[clap(short, long, value_parser, default_value = "exec-%pid")]
         name: String,
 
-        /// Duration the directory will live.
-        /// Examples: 1d, 4w, 8m
+        /// Duration the directory will live, defaults to the
+        /// `default_duration` of the config file.
+        /// Examples: 1d, 4w, 8m, 1h30min, P1DT2H
+        #[clap(short, long, value_parser)]
+        duration: Option<String>,
+
+        /// Directory to create the temporary folder in, created if missing.
+        /// Defaults to TEMPDIR_PARENT, the config file, or the current directory
+        #[clap(short, long, value_parser)]
+        parent: Option<PathBuf>,
+
+        /// Leave the directory in place until it expires instead of deleting
+        /// it when the command exits
+        #[clap(long)]
+        keep_until_expiry: bool,
+
+        /// Command to run, with the directory as working directory and TMPDIR
+        #[clap(last = true, required = true)]
+        command: Vec<String>,
+    },
+    /// Keep running and clean expired directories periodically
+    Daemon {
+        /// Time between two clean passes.
+        /// Examples: 30s, 5min, 1h
+        #[clap(short, long, value_parser, default_value = "5min")]
+        interval: String,
+
+        /// Also delete expired directories that still have contents
+        #[clap(short, long)]
+        recursive: bool,
+
+        /// Delete the contents of symlinked directories when deleting
+        /// recursively, instead of only removing the symlinks
+        #[clap(long)]
+        follow_symlinks: bool,
+
+        /// Notify this long before a directory expires, so it can still be
+        /// extended. Examples: 10min, 1h
+        #[clap(long, value_parser)]
+        notify_before: Option<String>,
+
+        /// Move expired directories to the trash instead of deleting them
+        #[clap(long)]
+        trash: bool,
+
+        /// Also delete expired directories that belong to other users
+        #[clap(long)]
+        ignore_ownership: bool,
+
+        /// Extend expired directories modified within this window instead
+        /// of deleting them. Examples: 30min, 2h
+        #[clap(long, value_parser)]
+        keep_active: Option<String>,
+
+        /// Also delete directories that haven't expired yet, soonest
+        /// expiring first, while their file system has less than this
+        /// available. Examples: 1G, 500M
+        #[clap(long, value_parser)]
+        min_free_space: Option<String>,
+
+        /// Number of directories to delete at the same time
+        #[clap(short, long, value_parser, default_value_t = 1)]
+        jobs: usize,
+    },
+    /// Keep running and drop the meta data of tracked directories as soon
+    /// as they are removed by hand. No directories are deleted
+    Watch {
+        /// Time between two checks for removed directories.
+        /// Examples: 30s, 5min, 1h
+        #[clap(short, long, value_parser, default_value = "1min")]
+        interval: String,
+
+        /// Wait for file system notifications of removals (inotify on
+        /// Linux) instead of checking every interval. Falls back to
+        /// checking when they are unavailable
+        #[clap(long)]
+        notify: bool,
+    },
+    /// Extend the lifetime of a temporary directory
+    Extend {
+        /// Name of the temporary folder to extend
+        #[clap(short, long, value_parser)]
+        name: String,
+
+        /// Duration to add to the lifetime.
+        /// Examples: 1d, 4w, 8m, 1h30min, P1DT2H
         #[clap(short, long, value_parser)]
         duration: String,
+
+        /// Parent directory of the temporary folder, needed when several
+        /// folders with the same name are tracked
+        #[clap(short, long, value_parser)]
+        parent: Option<PathBuf>,
+    },
+    /// Restart the lifetime of a temporary directory from now
+    Touch {
+        /// Name of the temporary folder to touch
+        #[clap(short, long, value_parser)]
+        name: String,
+
+        /// Parent directory of the temporary folder, needed when several
+        /// folders with the same name are tracked
+        #[clap(short, long, value_parser)]
+        parent: Option<PathBuf>,
+    },
+    /// Delete a temporary directory and its meta data before it expires
+    Remove {
+        /// Name of the temporary folder to remove
+        #[clap(short, long, value_parser)]
+        name: String,
+
+        /// Parent directory of the temporary folder, needed when several
+        /// folders with the same name are tracked
+        #[clap(short, long, value_parser)]
+        parent: Option<PathBuf>,
+
+        /// Don't ask for confirmation
+        #[clap(short, long)]
+        force: bool,
+    },
+    /// List all temporary directories with their remaining lifetime
+    List {
+        /// Show the disk space each directory uses
+        #[clap(long)]
+        show_size: bool,
+
+        /// Only list directories with this tag, can be given several times
+        #[clap(long = "tag", value_parser)]
+        tags: Vec<String>,
+
+        /// Order to list directories in
+        #[clap(long, value_enum, default_value_t = SortOrder::Name)]
+        sort: SortOrder,
+
+        /// Reverse the order
+        #[clap(long)]
+        reverse: bool,
+
+        /// Only list directories whose name matches this glob, e.g. "build-*"
+        #[clap(long, value_parser, conflicts_with = "regex")]
+        filter: Option<String>,
+
+        /// Only list directories whose name matches this regular expression
+        #[clap(long, value_parser)]
+        regex: Option<String>,
+
+        /// Only list expired directories
+        #[clap(long, conflicts_with = "active")]
+        expired: bool,
+
+        /// Only list directories that haven't expired yet
+        #[clap(long)]
+        active: bool,
+
+        /// Only print the names, one per line, for scripts and shell
+        /// completion
+        #[clap(long)]
+        names: bool,
+    },
+    /// Show the details of one temporary directory. Exits with 3 when it
+    /// has expired and 4 when it isn't tracked
+    Status {
+        /// Name of the temporary folder to show
+        #[clap(short, long, value_parser)]
+        name: String,
+
+        /// Parent directory of the temporary folder, needed when several
+        /// folders with the same name are tracked
+        #[clap(short, long, value_parser)]
+        parent: Option<PathBuf>,
+    },
+    /// Remove meta data of directories that no longer exist and report
+    /// invalid meta data. No directories are deleted
+    Repair,
+    /// Check the meta data store for invalid records, missing directories,
+    /// directories outside the allowed roots, duplicates and clock anomalies.
+    /// Exits with 5 when problems remain
+    Doctor {
+        /// Directory tracked directories have to lie in. Defaults to the
+        /// configured roots, or else the creation parent and base directory
+        #[clap(long = "root", value_parser)]
+        roots: Vec<PathBuf>,
+
+        /// Remove records of missing directories and duplicate records, like
+        /// `repair`. Other problems are only reported
+        #[clap(long)]
+        fix: bool,
+    },
+    /// Start tracking an existing directory, so it expires like one created
+    /// by tempdir. Nothing is created on disk
+    Adopt {
+        /// Directory to adopt
+        #[clap(short, long, value_parser)]
+        path: PathBuf,
+
+        /// Duration the directory will live, defaults to the
+        /// `default_duration` of the config file.
+        /// Examples: 1d, 4w, 8m, 1h30min, P1DT2H
+        #[clap(short, long, value_parser)]
+        duration: Option<String>,
+    },
+    /// Remove meta data of directories that no longer exist, and optionally
+    /// adopt or delete directories that have no meta data
+    Gc {
+        /// Also look for directories without meta data in the creation
+        /// parent (TEMPDIR_PARENT or the config file), or in --root
+        #[clap(long)]
+        orphans: bool,
+
+        /// Directory to look for directories without meta data in
+        #[clap(long, value_parser, requires = "orphans")]
+        root: Option<PathBuf>,
+
+        /// Adopt every directory without meta data instead of asking
+        #[clap(long, conflicts_with = "delete")]
+        adopt: bool,
+
+        /// Delete every directory without meta data instead of asking
+        #[clap(long)]
+        delete: bool,
+
+        /// Lifetime of adopted directories, defaults to the
+        /// `default_duration` of the config file
+        #[clap(short, long, value_parser)]
+        duration: Option<String>,
+    },
+    /// Exclude a temporary directory from cleaning until it is unpinned
+    Pin {
+        /// Name of the temporary folder to pin
+        #[clap(short, long, value_parser)]
+        name: String,
+
+        /// Parent directory of the temporary folder, needed when several
+        /// folders with the same name are tracked
+        #[clap(short, long, value_parser)]
+        parent: Option<PathBuf>,
+    },
+    /// Let a pinned temporary directory expire again
+    Unpin {
+        /// Name of the temporary folder to unpin
+        #[clap(short, long, value_parser)]
+        name: String,
+
+        /// Parent directory of the temporary folder, needed when several
+        /// folders with the same name are tracked
+        #[clap(short, long, value_parser)]
+        parent: Option<PathBuf>,
+    },
+    /// Keep a temporary directory, it will no longer expire
+    Keep {
+        /// Name of the temporary folder to keep
+        #[clap(short, long, value_parser)]
+        name: String,
+
+        /// Parent directory of the temporary folder, needed when several
+        /// folders with the same name are tracked
+        #[clap(short, long, value_parser)]
+        parent: Option<PathBuf>,
+    },
+    /// Show when temporary directories were created, extended and deleted,
+    /// and by whom, oldest first
+    History {
+        /// Only show the events of temporary folders with this name
+        #[clap(short, long, value_parser)]
+        name: Option<String>,
+    },
+    /// Print a completion script for a shell. Source it from the shell
+    /// config, e.g. `source <(tempdir completions bash)` in ~/.bashrc
+    Completions {
+        #[clap(value_enum)]
+        shell: Shell,
     },
-    Clean,
 }
 
-fn main() {
-    // Enable Logging
-    let env = Env::default()
-        .filter_or("MY_LOG_LEVEL", "trace")
-        .write_style_or("MY_LOG_STYLE", "always");
-    env_logger::init_from_env(env);
+const EXIT_CODES: &str = "Exit codes:
+  0  Success
+  1  The command failed, or failed for some of the directories
+  2  Invalid arguments or config file
+  3  status: the directory has expired
+  4  No temporary directory with that name is tracked
+  5  doctor: problems remain";
 
+/// Exit code of a command that failed, completely or for some directories
+const FAILURE_EXIT_CODE: u8 = 1;
+/// Exit code for invalid arguments or config, like clap uses for unknown
+/// arguments
+const USAGE_EXIT_CODE: u8 = 2;
+/// Exit code of `status` for a directory that has expired
+const EXPIRED_EXIT_CODE: u8 = 3;
+/// Exit code for a directory that isn't tracked
+const UNKNOWN_EXIT_CODE: u8 = 4;
+/// Exit code of `doctor` when problems remain
+const UNHEALTHY_EXIT_CODE: u8 = 5;
+
+fn main() -> ExitCode {
     // Parse command line arguments
     let args = Args::parse();
 
-    match args.action {
-        Actions::Create { name, duration } => {
-            let tempdir = TemporaryDirectory::new(name, duration).unwrap();
-            tempdir.create();
+    // Enable logging, to stderr so it never mixes with the output. The
+    // MY_LOG_LEVEL filter of older versions still applies when no flag is
+    // given.
+    let env = Env::default().filter_or("MY_LOG_LEVEL", "warn").write_style_or("MY_LOG_STYLE", "auto");
+    let mut logger = env_logger::Builder::from_env(env);
+    let level = match (args.log_level, args.quiet, args.verbose) {
+        (Some(level), _, _) => Some(level),
+        (None, true, _) => Some(LogLevel::Off),
+        (None, false, 0) => None,
+        (None, false, 1) => Some(LogLevel::Info),
+        (None, false, 2) => Some(LogLevel::Debug),
+        (None, false, _) => Some(LogLevel::Trace),
+    };
+    if let Some(level) = level {
+        logger.filter_level(level.filter());
+    }
+    logger.target(Target::Stderr).init();
+
+    let config = match args.config.as_deref().map_or_else(Config::load, Config::load_from) {
+        Ok(config) => config,
+        Err(err) => return fail(&err, args.quiet),
+    };
+    if let Some(store) = &args.store_path {
+        env::set_var("TEMPDIR_STORE", store);
+    }
+    apply_config_locations(&config);
+    if let Some(profile) = &args.profile {
+        match config.profile(profile) {
+            Ok(profile) => apply_profile(&profile, args.store_path.is_some()),
+            Err(err) => return fail(&err, args.quiet),
+        }
+    }
+
+    if config.auto_clean && !matches!(args.action, Actions::Clean { .. } | Actions::Completions { .. }) {
+        if let Ok(options) = config_clean_options(&config) {
+            let _ = clean_directories_with(&options);
+        }
+    }
+
+    let quiet = args.quiet;
+    match run(args.action, &config, args.format, quiet) {
+        Ok(code) => code,
+        Err(err) => fail(&err, quiet),
+    }
+}
+
+/// Prints the error together with its underlying causes, unless running
+/// quietly, and returns the exit code for it, see `EXIT_CODES`
+fn fail(err: &TempDirErrors, quiet: bool) -> ExitCode {
+    if !quiet {
+        let mut message = err.to_string();
+        let mut source = err.source();
+        while let Some(cause) = source {
+            message.push_str(&format!(": {cause}"));
+            source = cause.source();
+        }
+        eprintln!("Error: {message}");
+    }
+    ExitCode::from(exit_code(err))
+}
+
+fn exit_code(err: &TempDirErrors) -> u8 {
+    match err {
+        TempDirErrors::WrongDurationString
+        | TempDirErrors::WrongPeriodString
+        | TempDirErrors::WrongTimeAmount
+        | TempDirErrors::InvalidDeadline
+        | TempDirErrors::WrongSizeString
+        | TempDirErrors::InvalidFilter(_)
+        | TempDirErrors::InvalidConfig(_)
+        | TempDirErrors::InvalidProfile(_)
+        | TempDirErrors::InvalidName
+        | TempDirErrors::AmbiguousName => USAGE_EXIT_CODE,
+        TempDirErrors::NotFound => UNKNOWN_EXIT_CODE,
+        _ => FAILURE_EXIT_CODE,
+    }
+}
+
+/// Prints the directories a command failed for to stderr, unless quiet,
+/// and returns the partial failure exit code if there were any
+fn report_failures(failures: &[CleanError], quiet: bool) -> ExitCode {
+    if failures.is_empty() {
+        return ExitCode::SUCCESS
+    }
+    if !quiet {
+        for failure in failures {
+            eprintln!("Error: {}: {}", failure.path.display(), failure.reason);
+        }
+    }
+    ExitCode::from(FAILURE_EXIT_CODE)
+}
+
+/// Clean settings of the config file, which flags can add to
+fn config_clean_options(config: &Config) -> Result<CleanOptions, TempDirErrors> {
+    Ok(CleanOptions {
+        recursive: config.recursive_clean,
+        on_expire: config.on_expire.clone(),
+        archive_to: config.archive_to.clone(),
+        keep_to: config.keep_to.clone(),
+        active_window: config.active_window.as_deref().map(parse_duration).transpose()?,
+        max_total_size: config.max_total_size.as_deref().map(parse_size).transpose()?,
+        min_free_space: config.min_free_space.as_deref().map(parse_size).transpose()?,
+        ..CleanOptions::default()
+    })
+}
+
+/// The store, creation parent and base directory of the config file are used
+/// unless they are already set through the environment
+fn apply_config_locations(config: &Config) {
+    if let (Some(store), None) = (&config.store, env::var_os("TEMPDIR_STORE")) {
+        env::set_var("TEMPDIR_STORE", store);
+    }
+    if let (Some(parent), None) = (&config.parent, env::var_os("TEMPDIR_PARENT")) {
+        env::set_var("TEMPDIR_PARENT", parent);
+    }
+    if let (Some(base), None) = (&config.base, env::var_os("TEMPDIR_BASE")) {
+        env::set_var("TEMPDIR_BASE", base);
+    }
+    if let (Some(roots), None) = (&config.roots, env::var_os("TEMPDIR_ROOTS")) {
+        env::set_var("TEMPDIR_ROOTS", roots);
+    }
+}
+
+/// The locations of a profile replace those of the environment and the top
+/// of the config file, only `--store-path` takes precedence over its store
+fn apply_profile(profile: &Profile, store_path_given: bool) {
+    if let (Some(store), false) = (&profile.store, store_path_given) {
+        env::set_var("TEMPDIR_STORE", store);
+    }
+    if let Some(parent) = &profile.parent {
+        env::set_var("TEMPDIR_PARENT", parent);
+    }
+    if let Some(base) = &profile.base {
+        env::set_var("TEMPDIR_BASE", base);
+    }
+}
+
+/// Picks the given duration or the configured default, capped at the
+/// configured maximum lifetime
+fn resolve_duration(duration: Option<String>, config: &Config) -> Result<String, TempDirErrors> {
+    let duration = match duration.or_else(|| config.default_duration.clone()) {
+        Some(duration) => duration,
+        None => {
+            error!("No duration specified and no default duration configured");
+            return Err(TempDirErrors::WrongDurationString)
+        }
+    };
+
+    if let Some(max_lifetime) = &config.max_lifetime {
+        if parse_duration_string(&duration)? > parse_duration_string(max_lifetime)? {
+            warn!("{duration} exceeds the maximum lifetime, using {max_lifetime} instead");
+            return Ok(max_lifetime.clone())
+        }
+    }
+    Ok(duration)
+}
+
+/// Creates a directory expiring at `until`, or after the configured maximum
+/// lifetime when the deadline lies further away
+fn resolve_deadline(name: String, until: String, config: &Config) -> Result<TemporaryDirectory, TempDirErrors> {
+    let tempdir = TemporaryDirectory::new_until(name.clone(), until.clone())?;
+    if let Some(max_lifetime) = &config.max_lifetime {
+        if tempdir.remaining_seconds() > parse_duration_string(max_lifetime)? {
+            warn!("{until} exceeds the maximum lifetime, using {max_lifetime} instead");
+            return TemporaryDirectory::new(name, max_lifetime.clone())
+        }
+    }
+    Ok(tempdir)
+}
+
+/// Asks a yes/no question on the terminal, anything but yes means no
+fn confirm(question: &str) -> bool {
+    matches!(ask(&format!("{question} [y/N]")).as_str(), "y" | "yes")
+}
+
+/// Asks a question on the terminal and returns the lowercase answer, empty
+/// when nothing could be read
+fn ask(question: &str) -> String {
+    eprint!("{question} ");
+    let mut answer = String::new();
+    if io::stdin().read_line(&mut answer).is_err() {
+        return String::new()
+    }
+    answer.trim().to_lowercase()
+}
+
+fn format_timestamp(timestamp: i64) -> String {
+    match Local.timestamp_opt(timestamp, 0).single() {
+        Some(time) => time.format("%Y-%m-%d %H:%M:%S").to_string(),
+        None => timestamp.to_string(),
+    }
+}
+
+/// Runs `command` inside `path`, returning its exit code
+fn run_in(path: &Path, command: &[String]) -> Result<ExitCode, TempDirErrors> {
+    let status = Command::new(&command[0])
+        .args(&command[1..])
+        .current_dir(path)
+        .env("TMPDIR", path)
+        .status();
+    match status {
+        Ok(status) => {
+            let code = status.code().and_then(|code| u8::try_from(code).ok());
+            Ok(code.map(ExitCode::from).unwrap_or(ExitCode::FAILURE))
+        }
+        Err(err) => {
+            error!("{} couldn't be started", command[0]);
+            Err(TempDirErrors::CommandFailed(err))
+        }
+    }
+}
+
+/// Stable JSON representation of a directory, independent of the meta data
+/// format
+fn directory_json(tempdir: &TemporaryDirectory, size: Option<u64>) -> Value {
+    let mut record = json!({
+        "name": tempdir.name(),
+        "path": tempdir.path(),
+        "created_at": tempdir.created_at(),
+        "end_time": tempdir.end_time(),
+        "remaining_seconds": tempdir.remaining_seconds(),
+        "expired": tempdir.is_expired(),
+        "until": tempdir.until(),
+        "tags": tempdir.tags(),
+        "pinned": tempdir.is_pinned(),
+    });
+    if let Some(size) = size {
+        record["size"] = json!(size);
+    }
+    record
+}
+
+/// Name of a problem in the `doctor` output
+fn problem_label(problem: Problem) -> &'static str {
+    match problem {
+        Problem::Unparsable => "unparsable",
+        Problem::MissingDirectory => "missing",
+        Problem::OutsideRoots => "outside-roots",
+        Problem::DuplicateRecord => "duplicate",
+        Problem::AmbiguousName => "ambiguous-name",
+        Problem::ClockAnomaly => "clock",
+    }
+}
+
+/// Progress line on stderr while the contents of a directory are deleted,
+/// when stderr is a terminal. Clear it with `clear_progress` when done.
+fn progress_line(quiet: bool) -> Option<Progress> {
+    if quiet || !io::stderr().is_terminal() {
+        return None
+    }
+    let last_update = Mutex::new(Instant::now());
+    Some(Progress(Arc::new(move |tempdir: &TemporaryDirectory, removed: &RemovalProgress| {
+        let mut last_update = last_update.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if last_update.elapsed() < Duration::from_millis(100) {
+            return
+        }
+        *last_update = Instant::now();
+        eprint!("\r\x1b[2KDeleting {}: {} files, {} freed", tempdir.name(), removed.files, format_size(removed.bytes));
+    })))
+}
+
+fn clear_progress(progress: &Option<Progress>) {
+    if progress.is_some() {
+        eprint!("\r\x1b[2K");
+    }
+}
+
+/// Size in bytes with a binary unit, e.g. "1.5 GiB"
+fn format_size(bytes: u64) -> String {
+    let units = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < units.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        return format!("{bytes} B")
+    }
+    format!("{size:.1} {}", units[unit])
+}
+
+fn run(action: Actions, config: &Config, format: Format, quiet: bool) -> Result<ExitCode, TempDirErrors> {
+    match action {
+        Actions::Create { name, prefix, duration, until, on_expire, relative, renew_on_access, delete_contents, archive_to, trash, link, template, expand_placeholders, file_max_age, contents_only, exclude, keep_to, parent, tags, if_not_exists, refresh } => {
+            let name = name.unwrap_or_else(|| format!("{}%r", prefix.replace('%', "%%")));
+            let tempdir = match until {
+                Some(until) => resolve_deadline(name, until, config)?,
+                None => TemporaryDirectory::new(name, resolve_duration(duration, config)?)?,
+            };
+            let mut tempdir = tempdir
+                .with_on_expire(on_expire)
+                .with_relative_path(relative)
+                .with_renew_on_access(renew_on_access)
+                .with_delete_contents(delete_contents)
+                .with_archive_to(archive_to)
+                .with_trash(trash)
+                .with_link(link)
+                .with_template(template)
+                .with_template_placeholders(expand_placeholders)
+                .with_file_max_age(file_max_age)?
+                .with_contents_only(contents_only)
+                .with_exclude(exclude)
+                .with_keep_to(keep_to)
+                .with_parent(parent)
+                .with_tags(tags);
+            let path = if if_not_exists {
+                let path = tempdir.create_if_not_exists()?;
+                if refresh {
+                    tempdir.touch()?;
+                }
+                path
+            } else {
+                tempdir.create()?
+            };
+            // Only the path goes to stdout, log messages go to stderr, so
+            // scripts can capture it with `$(tempdir create ...)`
+            println!("{}", path.display());
+        }
+        Actions::Clean { recursive, follow_symlinks, dry_run, trash, ignore_ownership, keep_active, max_total_size, jobs, tags, older_than, name_glob } => {
+            let defaults = config_clean_options(config)?;
+            let options = CleanOptions {
+                recursive: recursive || defaults.recursive,
+                follow_symlinks,
+                dry_run,
+                trash,
+                ignore_ownership,
+                active_window: keep_active.as_deref().map(parse_duration).transpose()?.or(defaults.active_window),
+                max_total_size: max_total_size.as_deref().map(parse_size).transpose()?.or(defaults.max_total_size),
+                jobs,
+                tags,
+                older_than: older_than.as_deref().map(parse_duration).transpose()?,
+                name: name_glob.as_deref().map(glob_pattern).transpose()?,
+                progress: progress_line(quiet),
+                ..defaults
+            };
+            let report = clean_directories_with(&options);
+            clear_progress(&options.progress);
+            let report = report?;
+            if format == Format::Json {
+                let deleted: Vec<Value> = report.deleted.iter().map(|cleaned| directory_json(&cleaned.directory, None)).collect();
+                let skipped: Vec<Value> = report
+                    .skipped
+                    .iter()
+                    .map(|skipped| {
+                        let reason = match skipped {
+                            SkipReason::Renewed(_) => "renewed",
+                            SkipReason::HookFailed(_) => "hook failed",
+                            SkipReason::Pinned(_) => "pinned",
+                            SkipReason::NotOwned(_) => "not owned",
+                            SkipReason::Emptied(_) => "emptied",
+                            SkipReason::Changed(_) => "changed",
+                        };
+                        let mut record = directory_json(skipped.directory(), None);
+                        record["reason"] = json!(reason);
+                        record
+                    })
+                    .collect();
+                let failed: Vec<Value> = report
+                    .errors
+                    .iter()
+                    .map(|failure| json!({ "path": failure.path, "reason": failure.reason }))
+                    .collect();
+                let expired_files: Vec<Value> = report
+                    .expired_files
+                    .iter()
+                    .map(|file| json!({ "directory": file.directory, "path": file.path, "size": file.size, "modified": format_timestamp(file.modified) }))
+                    .collect();
+                println!("{}", json!({ "dry_run": dry_run, "deleted": deleted, "skipped": skipped, "expired_files": expired_files, "failed": failed }));
+            } else if dry_run {
+                for cleaned in report.deleted {
+                    let tempdir = cleaned.directory;
+                    println!(
+                        "{}\t{}\t{}\t{}",
+                        tempdir.name(),
+                        cleaned.path.display(),
+                        tempdir.disk_usage().unwrap_or(0),
+                        format_timestamp(tempdir.end_time())
+                    );
+                }
+                for file in report.expired_files {
+                    println!("{}\t{}\t{}\t{}", file.directory, file.path.display(), file.size, format_timestamp(file.modified));
+                }
+            }
+            // The JSON output lists them already
+            return Ok(report_failures(&report.errors, quiet || format == Format::Json))
+        }
+        Actions::Exec { name, duration, parent, keep_until_expiry, command } => {
+            let duration = resolve_duration(duration, config)?;
+            let tempdir = TemporaryDirectory::new(name, duration)?.with_parent(parent);
+            if keep_until_expiry {
+                let tempdir = tempdir.with_delete_contents(true).try_create()?;
+                let path = tempdir.path().ok_or(TempDirErrors::CreationFailed)?;
+                return run_in(&path, &command)
+            }
+
+            let guard = tempdir.create_scoped()?;
+            let path = guard.directory().path().ok_or(TempDirErrors::CreationFailed)?;
+            return run_in(&path, &command)
+        }
+        Actions::Daemon { interval, recursive, follow_symlinks, notify_before, trash, ignore_ownership, keep_active, min_free_space, jobs } => {
+            let interval = parse_duration(&interval)?;
+            if interval.is_zero() {
+                error!("Daemon interval must be longer than zero");
+                return Err(TempDirErrors::WrongDurationString)
+            }
+            let defaults = config_clean_options(config)?;
+            let options = CleanOptions {
+                recursive: recursive || defaults.recursive,
+                follow_symlinks,
+                trash,
+                ignore_ownership,
+                active_window: keep_active.as_deref().map(parse_duration).transpose()?.or(defaults.active_window),
+                notify_before: notify_before.as_deref().map(parse_duration).transpose()?,
+                min_free_space: min_free_space.as_deref().map(parse_size).transpose()?.or(defaults.min_free_space),
+                jobs,
+                ..defaults
+            };
+            run_daemon_with(interval, &options);
+        }
+        Actions::Watch { interval, notify } => {
+            let interval = parse_duration(&interval)?;
+            if interval.is_zero() {
+                error!("Watch interval must be longer than zero");
+                return Err(TempDirErrors::WrongDurationString)
+            }
+            watch_removals(interval, notify);
+        }
+        Actions::Extend { name, duration, parent } => {
+            TemporaryDirectory::load(&name, parent.as_deref())?.extend(&duration)?;
+        }
+        Actions::Touch { name, parent } => {
+            TemporaryDirectory::load(&name, parent.as_deref())?.touch()?;
+        }
+        Actions::Remove { name, parent, force } => {
+            let tempdir = TemporaryDirectory::load(&name, parent.as_deref())?;
+            let path = tempdir.path().map(|path| path.display().to_string()).unwrap_or(name);
+            if force || confirm(&format!("Remove {path}?")) {
+                let progress = progress_line(quiet);
+                let deleted = tempdir.delete_now_with(progress.as_ref());
+                clear_progress(&progress);
+                deleted?;
+            }
+        }
+        Actions::List { show_size, tags, sort, reverse, filter, regex, expired, active, names } => {
+            let name = match (filter, regex) {
+                (Some(glob), _) => Some(glob_pattern(&glob)?),
+                (None, Some(regex)) => Some(Regex::new(&regex).map_err(|err| TempDirErrors::InvalidFilter(err.to_string()))?),
+                (None, None) => None,
+            };
+            let sort = match sort {
+                SortOrder::Name => SortKey::Name,
+                SortOrder::Remaining => SortKey::Remaining,
+                SortOrder::Created => SortKey::Created,
+                SortOrder::Size => SortKey::Size,
+            };
+            let options = ListOptions { sort, reverse, name, tags, expired, active, clock: None };
+            let directories = list_directories_with(&options)?.into_iter();
+            if names {
+                for tempdir in directories {
+                    println!("{}", tempdir.name());
+                }
+                return Ok(ExitCode::SUCCESS)
+            }
+            if format == Format::Json {
+                let records: Vec<Value> = directories
+                    .map(|tempdir| directory_json(&tempdir, show_size.then(|| tempdir.disk_usage().unwrap_or(0))))
+                    .collect();
+                println!("{}", Value::Array(records));
+                return Ok(ExitCode::SUCCESS)
+            }
+            for tempdir in directories {
+                let path = tempdir.path().map(|path| path.display().to_string()).unwrap_or_default();
+                // Pinned directories don't expire, whatever their end time
+                let remaining = if tempdir.is_pinned() { "pinned".to_string() } else { tempdir.format_remaining() };
+                let mut line = format!(
+                    "{}\t{}\t{}\t{}",
+                    tempdir.name(),
+                    path,
+                    format_timestamp(tempdir.created_at()),
+                    remaining
+                );
+                if show_size {
+                    line.push_str(&format!("\t{}", tempdir.disk_usage().unwrap_or(0)));
+                }
+                println!("{line}");
+            }
+        }
+        Actions::Status { name, parent } => {
+            let tempdir = match TemporaryDirectory::load(&name, parent.as_deref()) {
+                Ok(tempdir) => tempdir,
+                Err(TempDirErrors::NotFound) => return Ok(ExitCode::from(UNKNOWN_EXIT_CODE)),
+                Err(err) => return Err(err),
+            };
+            if format == Format::Json {
+                println!("{}", directory_json(&tempdir, Some(tempdir.disk_usage().unwrap_or(0))));
+                return Ok(ExitCode::from(if tempdir.is_expired() { EXPIRED_EXIT_CODE } else { 0 }))
+            }
+            let path = tempdir.path().map(|path| path.display().to_string()).unwrap_or_default();
+            println!("Path:      {path}");
+            println!("Created:   {}", format_timestamp(tempdir.created_at()));
+            match tempdir.until() {
+                Some(until) => println!("Expires:   {} ({until})", format_timestamp(tempdir.end_time())),
+                None => println!("Expires:   {}", format_timestamp(tempdir.end_time())),
+            }
+            println!("Remaining: {}", tempdir.format_remaining());
+            println!("Size:      {}", tempdir.disk_usage().unwrap_or(0));
+            if !tempdir.tags().is_empty() {
+                println!("Tags:      {}", tempdir.tags().join(", "));
+            }
+            if tempdir.is_pinned() {
+                println!("Pinned:    yes");
+            }
+            if tempdir.is_expired() {
+                return Ok(ExitCode::from(EXPIRED_EXIT_CODE))
+            }
+        }
+        Actions::Repair => {
+            let report = repair()?;
+            println!(
+                "Removed {} stale and {} duplicate records, {} invalid records",
+                report.removed,
+                report.deduplicated,
+                report.invalid.len()
+            );
+            for invalid in &report.invalid {
+                println!("{}: {}", invalid.path.display(), invalid.reason);
+            }
+            if !report.invalid.is_empty() {
+                return Ok(ExitCode::from(FAILURE_EXIT_CODE))
+            }
+        }
+        Actions::Doctor { mut roots, fix } => {
+            if roots.is_empty() {
+                roots = allowed_roots();
+            }
+            if roots.is_empty() {
+                roots = ["TEMPDIR_PARENT", "TEMPDIR_BASE"].iter().filter_map(env::var_os).map(PathBuf::from).collect();
+            }
+            let mut findings = diagnose(&roots)?;
+            if fix && findings.iter().any(|finding| finding.problem.is_fixable()) {
+                let report = repair()?;
+                info!("Removed {} stale and {} duplicate records", report.removed, report.deduplicated);
+                findings.retain(|finding| !finding.problem.is_fixable());
+            }
+
+            if format == Format::Json {
+                let records: Vec<Value> = findings
+                    .iter()
+                    .map(|finding| json!({ "problem": problem_label(finding.problem), "path": finding.path, "detail": finding.detail }))
+                    .collect();
+                println!("{}", Value::Array(records));
+            } else if findings.is_empty() {
+                println!("No problems found");
+            } else {
+                for finding in &findings {
+                    let fixable = if finding.problem.is_fixable() { " (fix with --fix)" } else { "" };
+                    println!("{}\t{}\t{}{fixable}", problem_label(finding.problem), finding.path.display(), finding.detail);
+                }
+            }
+            if !findings.is_empty() {
+                return Ok(ExitCode::from(UNHEALTHY_EXIT_CODE))
+            }
+        }
+        Actions::Adopt { path, duration } => {
+            let tempdir = TemporaryDirectory::adopt(&path, resolve_duration(duration, config)?)?;
+            if let Some(path) = tempdir.path() {
+                println!("{}", path.display());
+            }
+        }
+        Actions::Gc { orphans, root, adopt, delete, duration } => {
+            let report = repair()?;
+            println!("Removed {} records of directories that no longer exist", report.removed);
+            if !orphans {
+                return Ok(ExitCode::SUCCESS)
+            }
+
+            let root = match root.or_else(|| env::var_os("TEMPDIR_PARENT").map(PathBuf::from)) {
+                Some(root) => root,
+                None => {
+                    error!("No directory to look for untracked directories in, use --root");
+                    return Err(TempDirErrors::NotFound)
+                }
+            };
+            for path in untracked_directories(&root)? {
+                let answer = if adopt {
+                    "a".to_string()
+                } else if delete {
+                    "d".to_string()
+                } else {
+                    ask(&format!("{} has no meta data. Adopt, delete or skip? [a/d/S]", path.display()))
+                };
+                match answer.as_str() {
+                    "a" | "adopt" => {
+                        TemporaryDirectory::adopt(&path, resolve_duration(duration.clone(), config)?)?;
+                        println!("Adopted {}", path.display());
+                    }
+                    "d" | "delete" => {
+                        delete_untracked(&path)?;
+                        println!("Deleted {}", path.display());
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Actions::Pin { name, parent } => {
+            TemporaryDirectory::load(&name, parent.as_deref())?.set_pinned(true)?;
+        }
+        Actions::Unpin { name, parent } => {
+            TemporaryDirectory::load(&name, parent.as_deref())?.set_pinned(false)?;
+        }
+        Actions::Keep { name, parent } => {
+            cancel_expiry(&name, parent.as_deref())?;
+        }
+        Actions::History { name } => {
+            let events = history::history(name.as_deref())?;
+            if format == Format::Json {
+                let events: Vec<Value> = events.iter().filter_map(|event| serde_json::to_value(event).ok()).collect();
+                println!("{}", Value::Array(events));
+                return Ok(ExitCode::SUCCESS)
+            }
+            for event in events {
+                println!(
+                    "{}\t{}\t{}\t{}\t{}\t{}",
+                    format_timestamp(event.at),
+                    event.action.name(),
+                    event.name,
+                    event.path.map(|path| path.display().to_string()).unwrap_or_default(),
+                    event.size_freed.map(format_size).unwrap_or_default(),
+                    event.user.unwrap_or_default()
+                );
+            }
         }
-        Actions::Clean => {
-            clean_directories();
+        Actions::Completions { shell } => {
+            print!("{}", completions::generate(shell, Args::command()));
         }
     }
+    Ok(ExitCode::SUCCESS)
 }
@@ -0,0 +1,176 @@
+//! A handle on one meta data store together with the policies for cleaning
+//! it, for library users who keep their directories somewhere else than the
+//! store `TEMPDIR_STORE` or the data directory point at.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+use crate::logging::info;
+use crate::{clean_store, CleanOptions, CleanReport, JsonStore, MetadataStore, TempDirErrors, TemporaryDirectory};
+
+/// A backend given to `TempDirManager::with_backend`, shared with the
+/// directories the manager hands out
+pub(crate) type SharedBackend = Arc<Mutex<Box<dyn MetadataStore>>>;
+
+/// Where the records of a manager are kept
+#[derive(Clone)]
+pub(crate) enum StoreLocation {
+    /// The JSON index of the store directory at this path
+    Path(PathBuf),
+    Backend(SharedBackend),
+}
+impl fmt::Debug for StoreLocation {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StoreLocation::Path(path) => formatter.debug_tuple("Path").field(path).finish(),
+            StoreLocation::Backend(_) => formatter.write_str("Backend"),
+        }
+    }
+}
+
+thread_local! {
+    /// Store used instead of the default one while a manager is working on
+    /// this thread, see `with_store`
+    static STORE_OVERRIDE: RefCell<Option<StoreLocation>> = const { RefCell::new(None) };
+}
+
+/// Manages the temporary directories tracked in one store. The free
+/// functions, like `list_directories`, work on a manager of the default
+/// store.
+///
+/// Directories returned by a manager remember its store, so their methods,
+/// like `extend` or `delete_now`, update that store as well.
+#[derive(Clone, Debug, Default)]
+pub struct TempDirManager {
+    /// `None` for the default store
+    store: Option<StoreLocation>,
+    options: CleanOptions,
+}
+impl TempDirManager {
+    /// A manager of the store at `store_path`, which is created if it doesn't
+    /// exist yet, cleaning with `options`. Relative paths of directories are
+    /// resolved against the folder containing the store, unless
+    /// `TEMPDIR_BASE` is set.
+    pub fn new(store_path: impl Into<PathBuf>, options: CleanOptions) -> TempDirManager {
+        TempDirManager { store: Some(StoreLocation::Path(store_path.into())), options }
+    }
+
+    /// A manager of the default store, cleaning with `options`
+    pub fn with_default_store(options: CleanOptions) -> TempDirManager {
+        TempDirManager { store: None, options }
+    }
+
+    /// A manager keeping its records in `backend` instead of a store
+    /// directory, like an `InMemoryStore` in tests. Without a store directory
+    /// no history is recorded, and relative paths are only resolved when
+    /// `TEMPDIR_BASE` is set.
+    pub fn with_backend(backend: impl MetadataStore + 'static, options: CleanOptions) -> TempDirManager {
+        let backend: Box<dyn MetadataStore> = Box::new(backend);
+        TempDirManager { store: Some(StoreLocation::Backend(Arc::new(Mutex::new(backend)))), options }
+    }
+
+    /// Directory of the store, `None` for the default one or a backend
+    pub fn store_path(&self) -> Option<&Path> {
+        match &self.store {
+            Some(StoreLocation::Path(path)) => Some(path),
+            _ => None,
+        }
+    }
+
+    pub fn options(&self) -> &CleanOptions {
+        &self.options
+    }
+
+    /// Creates the directory on disk and tracks it in this store, like
+    /// `TemporaryDirectory::try_create`
+    pub fn create(&self, tempdir: TemporaryDirectory) -> Result<TemporaryDirectory, TempDirErrors> {
+        self.manage(tempdir).try_create()
+    }
+
+    /// All directories tracked in this store, sorted by name
+    pub fn list(&self) -> Result<Vec<TemporaryDirectory>, TempDirErrors> {
+        let directories = with_store(self.store.as_ref(), || with_backend(|backend| backend.list()))?;
+        Ok(directories.into_iter().map(|tempdir| self.manage(tempdir)).collect())
+    }
+
+    /// Iterates over the directories tracked in this store, sorted by name.
+    /// The store is read once, up front, and isn't locked while iterating.
+    pub fn iter(&self) -> Result<std::vec::IntoIter<TemporaryDirectory>, TempDirErrors> {
+        Ok(self.list()?.into_iter())
+    }
+
+    /// The directory called `name`. When directories with that name exist in
+    /// several parents, `parent` has to tell them apart.
+    pub fn get(&self, name: &str, parent: Option<&Path>) -> Result<TemporaryDirectory, TempDirErrors> {
+        let tempdir = with_store(self.store.as_ref(), || with_backend(|backend| backend.load(name, parent)))?;
+        Ok(self.manage(tempdir))
+    }
+
+    /// Adds `duration` to the lifetime of the directory called `name`, and
+    /// returns it with its new end time
+    pub fn extend(&self, name: &str, parent: Option<&Path>, duration: &str) -> Result<TemporaryDirectory, TempDirErrors> {
+        let mut tempdir = self.get(name, parent)?;
+        tempdir.extend(duration)?;
+        Ok(tempdir)
+    }
+
+    /// Deletes the expired directories of this store, like
+    /// `clean_directories_with` with the options of the manager
+    pub fn clean(&self) -> Result<CleanReport, TempDirErrors> {
+        if let Some(store) = self.store_path() {
+            info!("Cleaning the store at {store:?}");
+        }
+        with_store(self.store.as_ref(), || clean_store(&self.options))
+    }
+
+    /// Points `tempdir` at this store and the clock of the options
+    fn manage(&self, mut tempdir: TemporaryDirectory) -> TemporaryDirectory {
+        tempdir.store = self.store.clone();
+        match &self.options.clock {
+            Some(clock) => tempdir.with_clock(clock.clone()),
+            None => tempdir,
+        }
+    }
+}
+
+/// Runs `f` with `store` instead of the default store on this thread. `None`
+/// keeps the store currently in use.
+pub(crate) fn with_store<T>(store: Option<&StoreLocation>, f: impl FnOnce() -> T) -> T {
+    let store = match store {
+        Some(store) => store,
+        None => return f(),
+    };
+
+    // Restores the previous store when `f` returns or panics
+    struct Restore(Option<StoreLocation>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            STORE_OVERRIDE.with(|current| *current.borrow_mut() = self.0.take());
+        }
+    }
+
+    let previous = STORE_OVERRIDE.with(|current| current.borrow_mut().replace(store.clone()));
+    let _restore = Restore(previous);
+    f()
+}
+
+/// Store set by `with_store` on this thread, if any
+pub(crate) fn store_override() -> Option<StoreLocation> {
+    STORE_OVERRIDE.with(|current| current.borrow().clone())
+}
+
+/// Runs `f` on the backend of the store in use on this thread, the JSON
+/// index unless a manager with a backend of its own set one
+pub(crate) fn with_backend<T>(f: impl FnOnce(&mut dyn MetadataStore) -> T) -> T {
+    match store_override() {
+        Some(StoreLocation::Backend(backend)) => f(backend.lock().unwrap_or_else(PoisonError::into_inner).as_mut()),
+        _ => f(&mut JsonStore),
+    }
+}
+
+/// Whether the store in use on this thread is a backend without a store
+/// directory
+pub(crate) fn backend_in_use() -> bool {
+    matches!(store_override(), Some(StoreLocation::Backend(_)))
+}
@@ -0,0 +1,60 @@
+//! Versioning of the meta data format. Every record carries the version of
+//! the format it was written in. Records of older versions are upgraded when
+//! they are read and written in the current format the next time the store
+//! is saved.
+
+use std::io;
+use serde_json::{Map, Value};
+use crate::{timestamp, TemporaryDirectory};
+
+/// Version of the records written by this version of tempdir
+pub(crate) const SCHEMA_VERSION: u64 = 2;
+
+/// Upgrades from each version to the next, starting at version 1, the
+/// records written before versioning was introduced
+const MIGRATIONS: [fn(&mut Map<String, Value>); (SCHEMA_VERSION - 1) as usize] = [rfc_3339_timestamps];
+
+pub(crate) fn schema_version() -> u64 {
+    SCHEMA_VERSION
+}
+
+/// Deserializes a record, upgrading it to the current version first. Records
+/// of a newer version fail with `ErrorKind::Unsupported`.
+pub(crate) fn read_record(record: Value) -> io::Result<TemporaryDirectory> {
+    let mut record = match record {
+        Value::Object(record) => record,
+        _ => return Err(invalid_data("meta data record is not an object".to_string())),
+    };
+
+    let version = match record.get("version") {
+        None => 1,
+        Some(version) => match version.as_u64() {
+            Some(version) if version >= 1 => version,
+            _ => return Err(invalid_data(format!("invalid meta data version {version}"))),
+        },
+    };
+    if version > SCHEMA_VERSION {
+        let message = format!("meta data version {version} was written by a newer version of tempdir");
+        return Err(io::Error::new(io::ErrorKind::Unsupported, message))
+    }
+
+    for migration in MIGRATIONS.iter().skip((version - 1) as usize) {
+        migration(&mut record);
+    }
+    record.insert("version".to_string(), Value::from(SCHEMA_VERSION));
+    Ok(serde_json::from_value(Value::Object(record))?)
+}
+
+/// Version 2 stores timestamps as RFC 3339 strings instead of seconds
+fn rfc_3339_timestamps(record: &mut Map<String, Value>) {
+    for field in ["created_at", "end_time"] {
+        let formatted = record.get(field).and_then(Value::as_i64).and_then(timestamp::format);
+        if let Some(formatted) = formatted {
+            record.insert(field.to_string(), Value::String(formatted));
+        }
+    }
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
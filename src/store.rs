@@ -0,0 +1,416 @@
+//! The meta data store: a single `index.json` holding the records of all
+//! temporary directories, guarded by a lock file so concurrent invocations
+//! don't overwrite each other's changes. A `TempDirManager` can keep its
+//! records in another `MetadataStore` instead.
+
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io;
+use std::path::{Path, PathBuf};
+use serde::Serialize;
+use crate::logging::{error, info, warn};
+use std::sync::PoisonError;
+use crate::manager::{self, SharedBackend, StoreLocation};
+use crate::{check_store_folder, migration, find_meta_data, info_store_path, is_meta_data_file, read_directories, read_meta_data, CleanError, TempDirErrors, TemporaryDirectory};
+
+/// Persistence of temporary directory records. Implement this to keep the
+/// meta data somewhere other than the JSON index, and hand it to
+/// `TempDirManager::with_backend`. Only the JSON index ships with tempdir, a
+/// database backend like SQLite would make every user depend on its
+/// bindings.
+pub trait MetadataStore: Send {
+    /// Adds or replaces the record of a directory
+    fn save(&mut self, tempdir: &TemporaryDirectory) -> Result<(), TempDirErrors>;
+    /// Finds the directory called `name`. When directories with that name
+    /// exist in several parents, `parent` has to tell them apart.
+    fn load(&self, name: &str, parent: Option<&Path>) -> Result<TemporaryDirectory, TempDirErrors>;
+    /// All tracked directories, sorted by name
+    fn list(&self) -> Result<Vec<TemporaryDirectory>, TempDirErrors>;
+    /// Stops tracking a directory. The directory itself is left untouched.
+    fn remove(&mut self, tempdir: &TemporaryDirectory) -> Result<(), TempDirErrors>;
+}
+
+/// The default backend, the `index.json` of the store directory. Every
+/// operation opens and locks the `Store` for as long as it takes.
+#[derive(Debug, Default)]
+pub struct JsonStore;
+impl MetadataStore for JsonStore {
+    fn save(&mut self, tempdir: &TemporaryDirectory) -> Result<(), TempDirErrors> {
+        let mut store = Store::open()?;
+        store.insert(tempdir.clone());
+        store.save()
+    }
+
+    fn load(&self, name: &str, parent: Option<&Path>) -> Result<TemporaryDirectory, TempDirErrors> {
+        find_meta_data(name, parent)
+    }
+
+    fn list(&self) -> Result<Vec<TemporaryDirectory>, TempDirErrors> {
+        read_directories()
+    }
+
+    fn remove(&mut self, tempdir: &TemporaryDirectory) -> Result<(), TempDirErrors> {
+        let mut store = Store::open()?;
+        store.remove(tempdir);
+        store.save()
+    }
+}
+
+/// A backend keeping the records in memory only, for unit tests of code
+/// managing temporary directories. Nothing is written to disk, and the
+/// records are gone once the store is dropped. Hand it to
+/// `TempDirManager::with_backend` to run the lifecycle against it.
+#[cfg(feature = "test-util")]
+#[derive(Debug, Default, Clone)]
+pub struct InMemoryStore {
+    records: BTreeMap<String, TemporaryDirectory>,
+}
+#[cfg(feature = "test-util")]
+impl InMemoryStore {
+    pub fn new() -> InMemoryStore {
+        InMemoryStore::default()
+    }
+
+    /// Number of tracked directories
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+}
+#[cfg(feature = "test-util")]
+impl MetadataStore for InMemoryStore {
+    fn save(&mut self, tempdir: &TemporaryDirectory) -> Result<(), TempDirErrors> {
+        self.records.insert(tempdir.store_key(), tempdir.clone());
+        Ok(())
+    }
+
+    fn load(&self, name: &str, parent: Option<&Path>) -> Result<TemporaryDirectory, TempDirErrors> {
+        // Paths are compared as they are, without touching the file system
+        let mut matches: Vec<&TemporaryDirectory> = self
+            .records
+            .values()
+            .filter(|tempdir| tempdir.name() == name)
+            .filter(|tempdir| parent.is_none_or(|parent| tempdir.path().is_some_and(|path| path.parent() == Some(parent))))
+            .collect();
+
+        match matches.len() {
+            0 => Err(TempDirErrors::NotFound),
+            1 => Ok(matches.remove(0).clone()),
+            _ => Err(TempDirErrors::AmbiguousName),
+        }
+    }
+
+    fn list(&self) -> Result<Vec<TemporaryDirectory>, TempDirErrors> {
+        let mut directories: Vec<TemporaryDirectory> = self.records.values().cloned().collect();
+        directories.sort_by(|a, b| a.name().cmp(b.name()));
+        Ok(directories)
+    }
+
+    fn remove(&mut self, tempdir: &TemporaryDirectory) -> Result<(), TempDirErrors> {
+        self.records.remove(&tempdir.store_key());
+        Ok(())
+    }
+}
+
+const INDEX_FILE: &str = "index.json";
+const LOCK_FILE: &str = "tempdir.lock";
+
+/// In-memory copy of the meta data store. The store stays locked until the
+/// `Store` is dropped, changes are only written by `save`. Backends of a
+/// `TempDirManager` are read by `open` and written by `save`, but not locked
+/// in between.
+pub struct Store {
+    path: PathBuf,
+    records: BTreeMap<String, TemporaryDirectory>,
+    /// Records of the index that couldn't be parsed. They are written back
+    /// as they are, so fixing them by hand stays possible.
+    unparsable: BTreeMap<String, serde_json::Value>,
+    /// Meta data files and records that couldn't be read
+    invalid: Vec<CleanError>,
+    /// Backend the records were read from, with the records it held, if the
+    /// store isn't the JSON index
+    backend: Option<(SharedBackend, Vec<TemporaryDirectory>)>,
+    _lock: Option<File>,
+}
+impl Store {
+    /// Opens and locks the store, creating it if it doesn't exist yet. Meta
+    /// data files written by older versions, one per directory, are imported
+    /// into the index.
+    pub fn open() -> Result<Store, TempDirErrors> {
+        if let Some(StoreLocation::Backend(backend)) = manager::store_override() {
+            return Store::open_backend(backend)
+        }
+        let path = match info_store_path() {
+            Ok(path) => path,
+            Err(err) => {
+                error!("Meta data directory couldn't be found");
+                return Err(err)
+            }
+        };
+
+        check_store_folder(&path)?;
+        if !path.exists() {
+            info!("Meta data directory not found. Creating it now");
+            if let Err(err) = fs::create_dir_all(&path) {
+                error!("Meta data directory couldn't be created");
+                return Err(TempDirErrors::StoreFolderError(format!("{path:?}: {err}")))
+            }
+        }
+
+        let lock = match lock_store(&path) {
+            Ok(lock) => lock,
+            Err(err) => {
+                error!("Meta data directory couldn't be locked");
+                return Err(TempDirErrors::StoreFolderError(format!("{path:?}: {err}")))
+            }
+        };
+
+        let mut invalid = Vec::new();
+        let (records, unparsable) = read_index(&path, &mut invalid)?;
+        let mut store = Store { records, unparsable, path, invalid, backend: None, _lock: Some(lock) };
+        store.import_meta_data_files()?;
+        Ok(store)
+    }
+
+    fn open_backend(backend: SharedBackend) -> Result<Store, TempDirErrors> {
+        let read = backend.lock().unwrap_or_else(PoisonError::into_inner).list()?;
+        let records = read
+            .iter()
+            .map(|tempdir| {
+                let key = tempdir.store_key();
+                let mut tempdir = tempdir.clone();
+                tempdir.key = Some(key.clone());
+                (key, tempdir)
+            })
+            .collect();
+        Ok(Store {
+            path: PathBuf::new(),
+            records,
+            unparsable: BTreeMap::new(),
+            invalid: Vec::new(),
+            backend: Some((backend, read)),
+            _lock: None,
+        })
+    }
+
+    /// Directory the store lives in
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// All tracked temporary directories
+    pub fn directories(&self) -> impl Iterator<Item = &TemporaryDirectory> {
+        self.records.values()
+    }
+
+    /// Adds the record of a directory, replacing an older record of it
+    pub fn insert(&mut self, tempdir: TemporaryDirectory) {
+        self.records.insert(tempdir.store_key(), tempdir);
+    }
+
+    /// Removes the record of a directory, returning it if it was tracked
+    pub fn remove(&mut self, tempdir: &TemporaryDirectory) -> Option<TemporaryDirectory> {
+        self.records.remove(&tempdir.store_key())
+    }
+
+    /// Writes the index. It is written and flushed to a temporary file first
+    /// and then moved into place, so a crash leaves either the old or the new
+    /// index, never a half written one.
+    pub fn save(&self) -> Result<(), TempDirErrors> {
+        if let Some((backend, read)) = &self.backend {
+            let mut backend = backend.lock().unwrap_or_else(PoisonError::into_inner);
+            for removed in read.iter().filter(|tempdir| !self.records.contains_key(&tempdir.store_key())) {
+                backend.remove(removed)?;
+            }
+            return self.records.values().try_for_each(|tempdir| backend.save(&tempdir.detached()))
+        }
+
+        let path = self.index_path();
+        let temporary_path = path.with_extension("json.tmp");
+
+        let file = match File::create(&temporary_path) {
+            Ok(file) => file,
+            Err(err) => {
+                error!("{temporary_path:?} couldn't be created");
+                return Err(TempDirErrors::MetaDataWriteFailed(err))
+            }
+        };
+
+        let mut index: BTreeMap<&String, Entry> = self.unparsable.iter().map(|(key, record)| (key, Entry::Unparsable(record))).collect();
+        index.extend(self.records.iter().map(|(key, tempdir)| (key, Entry::Record(tempdir))));
+        if let Err(err) = serde_json::to_writer(&file, &index) {
+            error!("Failed to write meta data index");
+            let _ = fs::remove_file(&temporary_path);
+            return Err(TempDirErrors::MetaDataWriteFailed(err.into()))
+        }
+        if let Err(err) = file.sync_all() {
+            error!("Failed to flush meta data index to disk");
+            let _ = fs::remove_file(&temporary_path);
+            return Err(TempDirErrors::MetaDataWriteFailed(err))
+        }
+
+        match fs::rename(&temporary_path, &path) {
+            Ok(()) => {
+                sync_directory(&self.path);
+                Ok(())
+            }
+            Err(err) => {
+                error!("Failed to move meta data index into place");
+                let _ = fs::remove_file(&temporary_path);
+                Err(TempDirErrors::MetaDataWriteFailed(err))
+            }
+        }
+    }
+
+    pub(crate) fn index_path(&self) -> PathBuf {
+        self.path.join(INDEX_FILE)
+    }
+
+    pub(crate) fn get(&self, key: &str) -> Option<&TemporaryDirectory> {
+        self.records.get(key)
+    }
+
+    /// Splits the store into its records and the meta data files that
+    /// couldn't be imported, releasing the lock
+    pub(crate) fn into_parts(self) -> (Vec<(String, TemporaryDirectory)>, Vec<CleanError>) {
+        (self.records.into_iter().collect(), self.invalid)
+    }
+
+    pub(crate) fn take_invalid(&mut self) -> Vec<CleanError> {
+        std::mem::take(&mut self.invalid)
+    }
+
+    pub(crate) fn remove_key(&mut self, key: &str) -> Option<TemporaryDirectory> {
+        self.records.remove(key)
+    }
+
+    /// Moves the records of per directory meta data files into the index.
+    /// Files that can't be read are left in place and reported.
+    fn import_meta_data_files(&mut self) -> Result<(), TempDirErrors> {
+        let entries = match fs::read_dir(&self.path) {
+            Ok(entries) => entries,
+            Err(err) => {
+                error!("Meta data directory couldn't be opened");
+                return Err(TempDirErrors::StoreFolderError(format!("{:?}: {err}", self.path)))
+            }
+        };
+
+        let mut imported: Vec<PathBuf> = Vec::new();
+        for entry in entries {
+            let path = match entry {
+                Ok(entry) => entry.path(),
+                Err(err) => {
+                    error!("Meta data directory entry couldn't be read: {err}. Continuing");
+                    self.invalid.push(CleanError { path: self.path.clone(), reason: err.to_string() });
+                    continue;
+                }
+            };
+            if !is_meta_data_file(&path) {
+                continue;
+            }
+
+            match read_meta_data(&path) {
+                Ok(mut tempdir) => {
+                    let key = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or_default().to_string();
+                    tempdir.key = Some(key.clone());
+                    self.records.entry(key).or_insert(tempdir);
+                    imported.push(path);
+                }
+                Err(err) => self.invalid.push(CleanError { path, reason: err.to_string() }),
+            }
+        }
+
+        if imported.is_empty() {
+            return Ok(())
+        }
+        info!("Importing {} meta data files into the index", imported.len());
+        self.save()?;
+        for path in imported {
+            if fs::remove_file(&path).is_err() {
+                warn!("{path:?} was imported but couldn't be deleted");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A record of the index as it is written
+#[derive(Serialize)]
+#[serde(untagged)]
+enum Entry<'a> {
+    Record(&'a TemporaryDirectory),
+    Unparsable(&'a serde_json::Value),
+}
+
+/// Takes an exclusive lock on the store, waiting for other processes to
+/// release theirs. The lock is released when the file is closed.
+fn lock_store(path: &Path) -> io::Result<File> {
+    let lock = OpenOptions::new().create(true).truncate(false).write(true).open(path.join(LOCK_FILE))?;
+    lock.lock()?;
+    Ok(lock)
+}
+
+/// Flushes the directory entry of a renamed file to disk. Directories can't
+/// be opened for this on Windows, where renames are durable anyway.
+fn sync_directory(path: &Path) {
+    if cfg!(windows) {
+        return
+    }
+    if File::open(path).and_then(|directory| directory.sync_all()).is_err() {
+        warn!("{path:?} couldn't be flushed to disk");
+    }
+}
+
+/// Parsed records of the index, and those that couldn't be parsed
+type Index = (BTreeMap<String, TemporaryDirectory>, BTreeMap<String, serde_json::Value>);
+
+/// Reads the records of the index. Records that can't be parsed are kept
+/// apart and reported in `invalid`, so one broken record doesn't make the
+/// whole store unusable. Records of a newer version fail the whole index.
+fn read_index(path: &Path, invalid: &mut Vec<CleanError>) -> Result<Index, TempDirErrors> {
+    let path = path.join(INDEX_FILE);
+    if !path.exists() {
+        return Ok((BTreeMap::new(), BTreeMap::new()))
+    }
+
+    let file = match File::open(&path) {
+        Ok(file) => file,
+        Err(err) => {
+            error!("{path:?} meta data index couldn't be read");
+            return Err(TempDirErrors::MetaDataReadFailed(err))
+        }
+    };
+
+    let index = match serde_json::from_reader::<_, BTreeMap<String, serde_json::Value>>(file) {
+        Ok(index) => index,
+        Err(err) => {
+            error!("{path:?} meta data index couldn't be parsed");
+            return Err(TempDirErrors::MetaDataReadFailed(err.into()))
+        }
+    };
+
+    let mut records = BTreeMap::new();
+    let mut unparsable = BTreeMap::new();
+    for (key, record) in index {
+        match migration::read_record(record.clone()) {
+            Ok(mut tempdir) => {
+                tempdir.key = Some(key.clone());
+                records.insert(key, tempdir);
+            }
+            // A newer tempdir wrote the index, which this one mustn't touch
+            Err(err) if err.kind() == io::ErrorKind::Unsupported => {
+                error!("{path:?} record {key}: {err}");
+                return Err(TempDirErrors::MetaDataReadFailed(err))
+            }
+            Err(err) => {
+                error!("{path:?} record {key} couldn't be parsed. Skipping");
+                invalid.push(CleanError { path: path.clone(), reason: format!("{key}: {err}") });
+                unparsable.insert(key, record);
+            }
+        }
+    }
+    Ok((records, unparsable))
+}
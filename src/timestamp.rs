@@ -0,0 +1,36 @@
+//! Serialization of timestamps in the meta data as RFC 3339 UTC strings,
+//! e.g. "2024-12-31T17:00:00Z". Meta data written by older versions stores
+//! them as seconds since the epoch, which is still accepted.
+
+use chrono::{DateTime, SecondsFormat, TimeZone, Utc};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serializer};
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Timestamp {
+    Seconds(i64),
+    Rfc3339(String),
+}
+
+pub fn serialize<S: Serializer>(timestamp: &i64, serializer: S) -> Result<S::Ok, S::Error> {
+    match format(*timestamp) {
+        Some(timestamp) => serializer.serialize_str(&timestamp),
+        None => serializer.serialize_i64(*timestamp),
+    }
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i64, D::Error> {
+    match Timestamp::deserialize(deserializer)? {
+        Timestamp::Seconds(seconds) => Ok(seconds),
+        Timestamp::Rfc3339(timestamp) => match DateTime::parse_from_rfc3339(&timestamp) {
+            Ok(timestamp) => Ok(timestamp.timestamp()),
+            Err(err) => Err(D::Error::custom(format!("invalid timestamp {timestamp:?}: {err}"))),
+        },
+    }
+}
+
+/// `timestamp` in the stored format, if it is within the supported range
+pub fn format(timestamp: i64) -> Option<String> {
+    Utc.timestamp_opt(timestamp, 0).single().map(|timestamp| timestamp.to_rfc3339_opts(SecondsFormat::Secs, true))
+}
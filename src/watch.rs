@@ -0,0 +1,173 @@
+//! Drops the meta data of directories that were removed by hand, instead of
+//! leaving it to the next `clean` or `repair`. Either the tracked paths are
+//! checked every interval, or, on Linux, inotify reports their removal as
+//! it happens. Without inotify the watch logs a warning and polls instead.
+
+use std::thread;
+use std::time::Duration;
+use crate::logging::{error, info, warn};
+use crate::repair;
+
+/// Stays resident and removes the records of tracked directories that no
+/// longer exist, like `repair` does. With `notify` the removals are waited
+/// for through the file system notifications of the platform, otherwise the
+/// store is checked every `interval`. Never returns.
+pub fn watch_removals(interval: Duration, notify: bool) -> ! {
+    if notify {
+        #[cfg(target_os = "linux")]
+        {
+            let err = inotify::Watcher::new().map_or_else(|err| err, inotify::Watcher::run);
+            warn!("File system notifications are unavailable ({err}), checking every {}s instead", interval.as_secs());
+        }
+        #[cfg(not(target_os = "linux"))]
+        warn!("File system notifications aren't supported on this platform, checking every {}s instead", interval.as_secs());
+    }
+
+    info!("Checking for removed directories every {}s", interval.as_secs());
+    loop {
+        forget_removed();
+        thread::sleep(interval);
+    }
+}
+
+/// Removes the records of directories that no longer exist
+fn forget_removed() {
+    match repair() {
+        Ok(report) if report.removed > 0 => info!("Removed the meta data of {} deleted directories", report.removed),
+        Ok(_) => {}
+        Err(err) => error!("Meta data of removed directories couldn't be dropped: {err}"),
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod inotify {
+    use std::collections::{HashMap, HashSet};
+    use std::ffi::CString;
+    use std::io;
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::{Path, PathBuf};
+    use crate::logging::{info, warn};
+    use crate::{info_store_path, list_directories};
+    use super::forget_removed;
+
+    /// Events of a parent directory meaning one of its entries left, or of a
+    /// directory meaning it left itself
+    const REMOVED: u32 = libc::IN_DELETE | libc::IN_MOVED_FROM | libc::IN_DELETE_SELF | libc::IN_MOVE_SELF;
+    /// Events of the store directory meaning the index was written
+    const STORE_CHANGED: u32 = libc::IN_CLOSE_WRITE | libc::IN_MOVED_TO;
+
+    /// Size of the fixed part of an event, followed by `len` bytes of name
+    const EVENT_SIZE: usize = std::mem::size_of::<libc::inotify_event>();
+
+    /// An inotify instance watching the parents of the tracked directories,
+    /// for their removal, and the store, to follow newly tracked ones
+    pub(super) struct Watcher {
+        fd: libc::c_int,
+        /// Watch descriptors of the watched parents
+        parents: HashMap<PathBuf, libc::c_int>,
+        store: Option<libc::c_int>,
+    }
+    impl Watcher {
+        pub(super) fn new() -> io::Result<Watcher> {
+            // SAFETY: no pointers are passed
+            let fd = unsafe { libc::inotify_init1(libc::IN_CLOEXEC) };
+            if fd < 0 {
+                return Err(io::Error::last_os_error())
+            }
+            Ok(Watcher { fd, parents: HashMap::new(), store: None })
+        }
+
+        /// Waits for removals until the notifications fail, returns why
+        pub(super) fn run(mut self) -> io::Error {
+            // Removals from before the watch started aren't reported
+            forget_removed();
+            self.store = info_store_path().ok().and_then(|store| self.add_watch(&store, STORE_CHANGED));
+            loop {
+                if self.follow_tracked() {
+                    info!("Watching {} directories for removals of tracked directories", self.parents.len());
+                }
+                let removed = match self.wait() {
+                    Ok(removed) => removed,
+                    Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(err) => return err,
+                };
+                if removed {
+                    forget_removed();
+                }
+            }
+        }
+
+        /// Watches the parents of the tracked directories, and only those.
+        /// Returns whether the watched directories changed.
+        fn follow_tracked(&mut self) -> bool {
+            let parents: HashSet<PathBuf> = list_directories()
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|tempdir| tempdir.path()?.parent().map(Path::to_path_buf))
+                .collect();
+            let stale: Vec<PathBuf> = self.parents.keys().filter(|parent| !parents.contains(*parent)).cloned().collect();
+            let mut changed = !stale.is_empty();
+            for parent in stale {
+                if let Some(descriptor) = self.parents.remove(&parent) {
+                    // SAFETY: no pointers are passed
+                    unsafe { libc::inotify_rm_watch(self.fd, descriptor) };
+                }
+            }
+            for parent in parents {
+                if !self.parents.contains_key(&parent) {
+                    if let Some(descriptor) = self.add_watch(&parent, REMOVED) {
+                        self.parents.insert(parent, descriptor);
+                        changed = true;
+                    }
+                }
+            }
+            changed
+        }
+
+        fn add_watch(&self, path: &Path, mask: u32) -> Option<libc::c_int> {
+            let c_path = CString::new(path.as_os_str().as_bytes()).ok()?;
+            // SAFETY: `c_path` is nul terminated
+            let descriptor = unsafe { libc::inotify_add_watch(self.fd, c_path.as_ptr(), mask) };
+            if descriptor < 0 {
+                warn!("{path:?} can't be watched: {}", io::Error::last_os_error());
+                return None
+            }
+            Some(descriptor)
+        }
+
+        /// Blocks until the next batch of events, returns whether any of them
+        /// was the removal of an entry of a watched parent
+        fn wait(&mut self) -> io::Result<bool> {
+            let mut buffer = [0u8; 4096];
+            // SAFETY: the length passed is that of `buffer`
+            let read = unsafe { libc::read(self.fd, buffer.as_mut_ptr().cast(), buffer.len()) };
+            if read < 0 {
+                return Err(io::Error::last_os_error())
+            }
+
+            let mut removed = false;
+            let mut offset = 0;
+            while offset + EVENT_SIZE <= read as usize {
+                // SAFETY: the kernel only writes whole events, so a header
+                // lies at `offset`
+                let event: libc::inotify_event = unsafe { std::ptr::read_unaligned(buffer[offset..].as_ptr().cast()) };
+                offset += EVENT_SIZE + event.len as usize;
+                if Some(event.wd) == self.store {
+                    continue;
+                }
+                if event.mask & libc::IN_IGNORED != 0 {
+                    // The parent itself is gone, it's watched again when still needed
+                    self.parents.retain(|_, descriptor| *descriptor != event.wd);
+                }
+                removed |= event.mask & REMOVED != 0;
+            }
+            Ok(removed)
+        }
+    }
+    impl Drop for Watcher {
+        fn drop(&mut self) {
+            // SAFETY: the descriptor is owned by the watcher
+            unsafe { libc::close(self.fd) };
+        }
+    }
+}
@@ -0,0 +1,44 @@
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+use tempdir::{create_temp_dir, ManualClock, TemporaryDirectory};
+
+#[test]
+fn accessors_and_summary_of_a_new_directory() {
+    let tempdir = TemporaryDirectory::new("summary".to_string(), "1h".to_string()).unwrap();
+    let clock = Arc::new(ManualClock::at(tempdir.created_at() + 30 * 60));
+    let tempdir = tempdir.with_clock(clock.clone());
+
+    assert_eq!(tempdir.name(), "summary");
+    assert_eq!(tempdir.duration(), "1h");
+    assert_eq!(tempdir.expires_at().timestamp(), tempdir.end_time());
+    assert_eq!(tempdir.remaining(), Duration::from_secs(30 * 60));
+    assert_eq!(tempdir.to_string(), "summary, 30min left");
+
+    clock.advance(Duration::from_secs(60 * 60));
+    assert_eq!(tempdir.remaining(), Duration::ZERO);
+    assert_eq!(tempdir.to_string(), "summary, expired");
+}
+
+#[test]
+fn new_with_duration_stores_the_duration_in_seconds() {
+    let tempdir = TemporaryDirectory::new_with_duration("seconds".to_string(), Duration::from_secs(90));
+
+    assert_eq!(tempdir.name(), "seconds");
+    assert_eq!(tempdir.duration(), "90s");
+    assert_eq!(tempdir.end_time(), tempdir.created_at() + 90);
+    let created_at = tempdir.created_at();
+    assert_eq!(tempdir.with_clock(Arc::new(ManualClock::at(created_at))).remaining(), Duration::from_secs(90));
+}
+
+#[test]
+fn summary_of_a_created_directory_shows_its_path() {
+    common::sandboxed_store("accessors");
+    let tempdir = create_temp_dir("created", "2d").unwrap();
+    let path = tempdir.path().unwrap();
+
+    let summary = tempdir.to_string();
+    assert!(summary.starts_with(&format!("created at {}, ", path.display())));
+    assert!(summary.ends_with(" left"));
+}
@@ -0,0 +1,22 @@
+mod common;
+
+use std::fs;
+use std::time::Duration;
+use tempdir::{clean_directories_with, create_temp_dir, CleanOptions, TemporaryDirectory};
+
+#[test]
+fn recently_modified_directory_is_extended() {
+    common::sandboxed_store("active-window");
+
+    let tempdir = create_temp_dir("active", "1s").unwrap();
+    let path = tempdir.path().unwrap();
+    fs::write(path.join("progress"), "still working").unwrap();
+
+    let options = CleanOptions { active_window: Some(Duration::from_secs(3600)), ..common::clean_options_after(2) };
+    let report = clean_directories_with(&options).unwrap();
+
+    assert!(path.is_dir());
+    assert_eq!(report.skipped.len(), 1);
+    let reloaded = TemporaryDirectory::load("active", None).unwrap();
+    assert!((3590..=3600).contains(&reloaded.remaining_seconds()));
+}
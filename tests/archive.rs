@@ -0,0 +1,31 @@
+#![cfg(unix)]
+
+mod common;
+
+use std::fs;
+use tempdir::{clean_directories_with, TemporaryDirectory};
+
+#[test]
+fn expired_directory_is_archived_before_deletion() {
+    let sandbox = common::sandboxed_store("archive");
+    let archive_to = sandbox.join("archive");
+
+    let tempdir = TemporaryDirectory::new("archived".to_string(), "1s".to_string())
+        .unwrap()
+        .with_delete_contents(true)
+        .with_archive_to(Some(archive_to.clone()))
+        .try_create()
+        .unwrap();
+    let path = tempdir.path().unwrap();
+    fs::write(path.join("result"), "valuable").unwrap();
+
+    clean_directories_with(&common::clean_options_after(2)).unwrap();
+
+    assert!(!path.exists());
+    let archives: Vec<String> = fs::read_dir(&archive_to)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    assert_eq!(archives.len(), 1);
+    assert!(archives[0].starts_with("archived-") && archives[0].ends_with(".tar.gz"));
+}
@@ -0,0 +1,49 @@
+#![cfg(feature = "threaded-async")]
+
+mod common;
+
+use std::future::Future;
+use std::pin::pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake};
+use std::thread::{self, Thread};
+use tempdir::{asynchronous, TemporaryDirectory};
+
+struct Unpark(Thread);
+impl Wake for Unpark {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Minimal executor, polling `future` on the current thread
+fn block_on<F: Future>(future: F) -> F::Output {
+    let waker = Arc::new(Unpark(thread::current())).into();
+    let mut context = Context::from_waker(&waker);
+    let mut future = pin!(future);
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+#[test]
+fn directories_are_created_and_cleaned_asynchronously() {
+    common::sandboxed_store("async-api");
+
+    let tempdir = TemporaryDirectory::new("async-expiring".to_string(), "1s".to_string()).unwrap();
+    let expiring = block_on(asynchronous::create(tempdir)).unwrap().path().unwrap();
+    let tempdir = TemporaryDirectory::new("async-deleted".to_string(), "1h".to_string()).unwrap();
+    let deleted = block_on(asynchronous::create(tempdir)).unwrap();
+    let deleted_path = deleted.path().unwrap();
+    assert!(expiring.is_dir() && deleted_path.is_dir());
+
+    block_on(asynchronous::delete(deleted)).unwrap();
+    assert!(!deleted_path.exists());
+
+    let report = block_on(asynchronous::clean(common::clean_options_after(2))).unwrap();
+    assert_eq!(report.deleted.len(), 1);
+    assert!(!expiring.exists());
+}
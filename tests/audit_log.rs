@@ -0,0 +1,28 @@
+mod common;
+
+use std::env;
+use std::fs;
+use tempdir::create_temp_dir;
+
+#[test]
+fn audit_log_keeps_its_original_format() {
+    let sandbox = common::sandboxed_store("audit-log");
+    let audit_log = sandbox.join("audit.jsonl");
+    env::set_var("TEMPDIR_AUDIT_LOG", &audit_log);
+
+    let tempdir = create_temp_dir("audited", "1h").unwrap();
+    tempdir.delete_now().unwrap();
+
+    let records: Vec<serde_json::Value> = fs::read_to_string(&audit_log)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let events: Vec<&str> = records.iter().map(|record| record["event"].as_str().unwrap()).collect();
+    assert_eq!(events, ["create", "delete"]);
+    for record in &records {
+        assert_eq!(record["name"], "audited");
+        assert!(record["at"].is_i64());
+        assert_eq!(record.as_object().unwrap().len(), 3);
+    }
+}
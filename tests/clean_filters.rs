@@ -0,0 +1,32 @@
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+use tempdir::{clean_directories_with, create_temp_dir, glob_pattern, CleanOptions, ManualClock};
+
+const DAY: u64 = 24 * 60 * 60;
+
+#[test]
+fn clean_is_scoped_by_name_and_expiry_age() {
+    common::sandboxed_store("clean-filters");
+    let first = create_temp_dir("ci-1", "1h").unwrap().path().unwrap();
+    let second = create_temp_dir("ci-2", "1h").unwrap().path().unwrap();
+    let other = create_temp_dir("dev-1", "1h").unwrap().path().unwrap();
+
+    let clock = Arc::new(ManualClock::default());
+    clock.advance(Duration::from_secs(2 * 60 * 60));
+    let by_name = CleanOptions { clock: Some(clock.clone()), name: Some(glob_pattern("ci-*").unwrap()), ..CleanOptions::default() };
+    let report = clean_directories_with(&by_name).unwrap();
+    assert_eq!(report.deleted.len(), 2);
+    assert!(!first.exists() && !second.exists());
+    assert!(other.is_dir());
+
+    let by_age = CleanOptions { clock: Some(clock.clone()), older_than: Some(Duration::from_secs(30 * DAY)), ..CleanOptions::default() };
+    assert!(clean_directories_with(&by_age).unwrap().deleted.is_empty());
+    assert!(other.is_dir());
+
+    clock.advance(Duration::from_secs(30 * DAY));
+    let report = clean_directories_with(&by_age).unwrap();
+    assert_eq!(report.deleted.len(), 1);
+    assert!(!other.exists());
+}
@@ -0,0 +1,49 @@
+#![cfg(all(unix, feature = "cli"))]
+
+mod common;
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tempdir::{clean_directories_with, CleanOptions, ManualClock, SkipReason, TemporaryDirectory};
+
+/// An expiry hook running `commands`, which see the tempdir binary as `$TEMPDIR`
+fn hook(sandbox: &Path, name: &str, commands: &str) -> String {
+    let path = sandbox.join(format!("{name}.sh"));
+    fs::write(&path, format!("#!/bin/sh\nTEMPDIR={}\n{commands}\n", env!("CARGO_BIN_EXE_tempdir"))).unwrap();
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    path.display().to_string()
+}
+
+fn expire(name: &str, hook: String) -> PathBuf {
+    let tempdir = TemporaryDirectory::new(name.to_string(), "1s".to_string()).unwrap().with_on_expire(Some(hook)).try_create().unwrap();
+    tempdir.path().unwrap()
+}
+
+fn clean_later() -> Vec<String> {
+    let clock = Arc::new(ManualClock::default());
+    clock.advance(Duration::from_secs(60));
+    let report = clean_directories_with(&CleanOptions { clock: Some(clock), ..CleanOptions::default() }).unwrap();
+    report.skipped.iter().filter(|skipped| matches!(skipped, SkipReason::Changed(_))).map(|skipped| skipped.directory().name().to_string()).collect()
+}
+
+/// Both directories are cleaned by the same pass, which runs their hooks
+#[test]
+fn directories_changed_during_clean_are_kept() {
+    let sandbox = common::sandboxed_store("clean-race");
+    let extended = expire("extended", hook(&sandbox, "extend", "$TEMPDIR extend -n extended -d 1h"));
+    let recreated = expire("recreated", hook(&sandbox, "recreate", "$TEMPDIR remove -n recreated -f && $TEMPDIR create -n recreated -d 1h"));
+
+    let mut changed = clean_later();
+    changed.sort();
+    assert_eq!(changed, ["extended", "recreated"]);
+
+    assert!(extended.is_dir());
+    assert!(TemporaryDirectory::load("extended", None).unwrap().remaining() > Duration::from_secs(30 * 60));
+    assert!(recreated.is_dir());
+    let record = TemporaryDirectory::load("recreated", None).unwrap();
+    assert_eq!(record.path(), Some(recreated));
+    assert_eq!(record.duration(), "1h");
+}
@@ -0,0 +1,47 @@
+#![cfg(feature = "cli")]
+
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+/// Compares the completion script of `shell` with the snapshot in
+/// tests/snapshots, rewriting the snapshot when UPDATE_SNAPSHOTS is set
+fn assert_snapshot(shell: &str) {
+    let sandbox = common::sandbox(&format!("cli-completions-{shell}"));
+    let output = common::tempdir(&sandbox).args(["completions", shell]).output().unwrap();
+    assert!(output.status.success());
+    fs::remove_dir_all(&sandbox).unwrap();
+    let script = String::from_utf8(output.stdout).unwrap();
+    let snapshot = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("snapshots").join(format!("completions.{shell}"));
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::write(&snapshot, &script).unwrap();
+    }
+    assert_eq!(script, fs::read_to_string(&snapshot).unwrap(), "{shell} completions differ from {}", snapshot.display());
+}
+
+#[test]
+fn bash_completions() {
+    assert_snapshot("bash");
+}
+
+#[test]
+fn zsh_completions() {
+    assert_snapshot("zsh");
+}
+
+#[test]
+fn fish_completions() {
+    assert_snapshot("fish");
+}
+
+#[test]
+fn names_are_completed_from_list_names() {
+    let sandbox = common::sandbox("cli-completions-names");
+    for name in ["alpha", "beta"] {
+        assert!(common::tempdir(&sandbox).args(["create", "-n", name, "-d", "1h"]).status().unwrap().success());
+    }
+    let output = common::tempdir(&sandbox).args(["list", "--names"]).output().unwrap();
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "alpha\nbeta\n");
+    fs::remove_dir_all(&sandbox).unwrap();
+}
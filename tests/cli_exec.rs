@@ -0,0 +1,23 @@
+#![cfg(all(unix, feature = "cli"))]
+
+mod common;
+
+use std::fs;
+use common::tempdir;
+
+#[test]
+fn exec_passes_exit_status_and_removes_directory() {
+    let sandbox = common::sandbox("cli-exec");
+    let status = tempdir(&sandbox)
+        .args(["exec", "-n", "job", "-d", "1h", "--", "sh", "-c", "pwd > ../cwd; exit 3"])
+        .status()
+        .unwrap();
+    assert_eq!(status.code(), Some(3));
+
+    let cwd = fs::read_to_string(sandbox.join("cwd")).unwrap();
+    assert!(cwd.trim_end().ends_with("/job"));
+    assert!(!sandbox.join("job").exists());
+    let output = tempdir(&sandbox).args(["--format", "json", "list"]).output().unwrap();
+    assert_eq!(serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap(), serde_json::json!([]));
+    fs::remove_dir_all(&sandbox).unwrap();
+}
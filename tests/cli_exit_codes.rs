@@ -0,0 +1,54 @@
+#![cfg(feature = "cli")]
+
+mod common;
+
+use std::fs;
+use common::{exit_code, tempdir};
+
+/// Moves the end time of the directory called `name` into the past
+fn expire(store: &std::path::Path, name: &str) {
+    let index_path = store.join("index.json");
+    let mut index: serde_json::Map<String, serde_json::Value> = serde_json::from_str(&fs::read_to_string(&index_path).unwrap()).unwrap();
+    for record in index.values_mut().filter(|record| record["name"] == name) {
+        record["created_at"] = "2000-01-01T00:00:00Z".into();
+        record["end_time"] = "2000-01-01T01:00:00Z".into();
+    }
+    fs::write(&index_path, serde_json::to_string(&index).unwrap()).unwrap();
+}
+
+#[test]
+fn success_and_usage_errors() {
+    let sandbox = common::sandbox("cli-exit-usage");
+    assert_eq!(exit_code(&mut tempdir(&sandbox), &["create", "-n", "fresh", "-d", "1h"]), Some(0));
+    assert_eq!(exit_code(&mut tempdir(&sandbox), &["status", "-n", "fresh"]), Some(0));
+    assert_eq!(exit_code(&mut tempdir(&sandbox), &["create", "-n", "invalid", "-d", "5x"]), Some(2));
+    assert_eq!(exit_code(&mut tempdir(&sandbox), &["list", "--no-such-flag"]), Some(2));
+    fs::remove_dir_all(&sandbox).unwrap();
+}
+
+#[test]
+fn failure_and_doctor_problems() {
+    let sandbox = common::sandbox("cli-exit-failure");
+    assert_eq!(exit_code(&mut tempdir(&sandbox), &["create", "-n", "vanished", "-d", "1h"]), Some(0));
+    fs::remove_dir(sandbox.join("vanished")).unwrap();
+    assert_eq!(exit_code(&mut tempdir(&sandbox), &["doctor", "--root", sandbox.to_str().unwrap()]), Some(5));
+    assert_eq!(exit_code(&mut tempdir(&sandbox), &["doctor", "--root", sandbox.to_str().unwrap(), "--fix"]), Some(0));
+
+    fs::write(sandbox.join("store").join("broken.json"), "{").unwrap();
+    assert_eq!(exit_code(&mut tempdir(&sandbox), &["repair"]), Some(1));
+    fs::remove_dir_all(&sandbox).unwrap();
+}
+
+#[test]
+fn expired_and_unknown_directories() {
+    let sandbox = common::sandbox("cli-exit-status");
+    assert_eq!(exit_code(&mut tempdir(&sandbox), &["create", "-n", "old", "-d", "1h"]), Some(0));
+    expire(&sandbox.join("store"), "old");
+    assert_eq!(exit_code(&mut tempdir(&sandbox), &["status", "-n", "old"]), Some(3));
+
+    let output = tempdir(&sandbox).args(["status", "-n", "missing"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(4));
+    assert!(output.stdout.is_empty());
+    assert_eq!(exit_code(&mut tempdir(&sandbox), &["extend", "-n", "missing", "-d", "1h"]), Some(4));
+    fs::remove_dir_all(&sandbox).unwrap();
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "cli")]
+
+mod common;
+
+use std::fs;
+use serde_json::Value;
+use common::tempdir;
+
+/// Parses the stdout of running the binary with `args` as JSON
+fn json_output(sandbox: &std::path::Path, args: &[&str]) -> Value {
+    let output = tempdir(sandbox).args(["--format", "json"]).args(args).output().unwrap();
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn list_and_status_print_records() {
+    let sandbox = common::sandbox("cli-json");
+    let created = tempdir(&sandbox).args(["create", "-n", "data", "-d", "1h", "--tag", "build"]).status().unwrap();
+    assert!(created.success());
+    fs::write(sandbox.join("data").join("file"), [0u8; 10]).unwrap();
+
+    let list = json_output(&sandbox, &["list", "--show-size"]);
+    let records = list.as_array().unwrap();
+    assert_eq!(records.len(), 1);
+    let record = &records[0];
+    assert_eq!(record["name"], "data");
+    assert_eq!(record["path"], sandbox.join("data").to_str().unwrap());
+    assert_eq!(record["end_time"].as_i64().unwrap() - record["created_at"].as_i64().unwrap(), 3600);
+    assert!((3590..=3600).contains(&record["remaining_seconds"].as_i64().unwrap()));
+    assert_eq!(record["expired"], false);
+    assert_eq!(record["pinned"], false);
+    assert_eq!(record["until"], Value::Null);
+    assert_eq!(record["tags"], serde_json::json!(["build"]));
+    assert_eq!(record["size"], 10);
+
+    let list = json_output(&sandbox, &["list"]);
+    assert!(list[0].get("size").is_none());
+
+    let status = json_output(&sandbox, &["status", "-n", "data"]);
+    assert_eq!(status["name"], "data");
+    assert_eq!(status["created_at"], record["created_at"]);
+    assert_eq!(status["end_time"], record["end_time"]);
+    assert_eq!(status["size"], 10);
+    fs::remove_dir_all(&sandbox).unwrap();
+}
@@ -0,0 +1,65 @@
+#![cfg(all(target_os = "linux", feature = "cli"))]
+
+mod common;
+
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::process::{Child, Stdio};
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+use common::tempdir;
+
+/// Starts `tempdir watch` with `args` and waits for the log line containing
+/// `ready`
+fn watch(sandbox: &Path, args: &[&str], ready: &str) -> Child {
+    let mut child = tempdir(sandbox).args(["-v", "watch"]).args(args).stderr(Stdio::piped()).spawn().unwrap();
+    let mut lines = BufReader::new(child.stderr.take().unwrap()).lines();
+    assert!(lines.any(|line| line.unwrap().contains(ready)));
+    child
+}
+
+/// Whether the record of `name` is dropped within a few seconds
+fn forgotten(sandbox: &Path, name: &str) -> bool {
+    let started = Instant::now();
+    while started.elapsed() < Duration::from_secs(5) {
+        if common::meta_data_records(&sandbox.join("store"), name).is_empty() {
+            return true
+        }
+        sleep(Duration::from_millis(20));
+    }
+    false
+}
+
+#[test]
+fn notify_drops_the_record_of_a_removed_directory() {
+    let sandbox = common::sandbox("cli-watch-notify");
+    for name in ["removed", "kept"] {
+        assert!(tempdir(&sandbox).args(["create", "-n", name, "-d", "1h"]).status().unwrap().success());
+    }
+
+    let mut child = watch(&sandbox, &["--notify", "--interval", "1h"], "Watching 1 directories");
+    fs::remove_dir(sandbox.join("removed")).unwrap();
+    let dropped = forgotten(&sandbox, "removed");
+    child.kill().unwrap();
+    child.wait().unwrap();
+
+    assert!(dropped);
+    assert_eq!(common::meta_data_records(&sandbox.join("store"), "kept").len(), 1);
+    fs::remove_dir_all(&sandbox).unwrap();
+}
+
+#[test]
+fn polling_drops_the_record_of_a_removed_directory() {
+    let sandbox = common::sandbox("cli-watch-poll");
+    assert!(tempdir(&sandbox).args(["create", "-n", "removed", "-d", "1h"]).status().unwrap().success());
+
+    let mut child = watch(&sandbox, &["--interval", "1s"], "Checking for removed directories");
+    fs::remove_dir(sandbox.join("removed")).unwrap();
+    let dropped = forgotten(&sandbox, "removed");
+    child.kill().unwrap();
+    child.wait().unwrap();
+
+    assert!(dropped);
+    fs::remove_dir_all(&sandbox).unwrap();
+}
@@ -0,0 +1,33 @@
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+use tempdir::{clean_directories_with, create_temp_dir, CleanOptions, ManualClock, TemporaryDirectory};
+
+#[test]
+fn clean_decides_expiry_by_the_given_clock() {
+    common::sandboxed_store("clock");
+    let path = create_temp_dir("simulated", "1h").unwrap().path().unwrap();
+
+    let clock = Arc::new(ManualClock::default());
+    let options = CleanOptions { clock: Some(clock.clone()), ..CleanOptions::default() };
+    assert!(clean_directories_with(&options).unwrap().deleted.is_empty());
+    assert!(path.is_dir());
+
+    clock.advance(Duration::from_secs(2 * 60 * 60));
+    let report = clean_directories_with(&options).unwrap();
+    assert_eq!(report.deleted.len(), 1);
+    assert!(!path.exists());
+}
+
+#[test]
+fn expiry_at_a_given_time() {
+    let tempdir = TemporaryDirectory::new("at".to_string(), "1h".to_string()).unwrap();
+    assert!(!tempdir.is_expired_at(tempdir.end_time()));
+    assert!(tempdir.is_expired_at(tempdir.end_time() + 1));
+
+    let clock = Arc::new(ManualClock::at(tempdir.end_time() + 60));
+    let tempdir = tempdir.with_clock(clock);
+    assert!(tempdir.is_expired());
+    assert_eq!(tempdir.remaining_seconds(), -60);
+}
@@ -0,0 +1,71 @@
+#![allow(dead_code)]
+
+use std::env;
+use std::path::PathBuf;
+
+/// Creates an empty directory under the system temp dir, unique to this test
+/// process
+pub fn sandbox(name: &str) -> PathBuf {
+    let path = env::temp_dir().join(format!("tempdir-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&path);
+    std::fs::create_dir_all(&path).unwrap();
+    path
+}
+
+/// Points the meta data store and creation parent of this test process into
+/// a sandbox, once, and returns the sandbox
+pub fn sandboxed_store(name: &str) -> PathBuf {
+    static SANDBOX: std::sync::OnceLock<PathBuf> = std::sync::OnceLock::new();
+    SANDBOX
+        .get_or_init(|| {
+            let sandbox = sandbox(name);
+            env::set_var("TEMPDIR_STORE", sandbox.join("store"));
+            env::set_var("TEMPDIR_PARENT", &sandbox);
+            sandbox
+        })
+        .clone()
+}
+
+/// Records in the index of `store` that track a directory called `name`
+pub fn meta_data_records(store: &std::path::Path, name: &str) -> Vec<serde_json::Value> {
+    let index = std::fs::read_to_string(store.join("index.json"))
+        .ok()
+        .and_then(|contents| serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(&contents).ok())
+        .unwrap_or_default();
+    index.into_iter().map(|(_, record)| record).filter(|record| record["name"] == name).collect()
+}
+
+/// The tempdir binary, keeping its store and directories inside `sandbox`
+/// and ignoring the config file and locations of the user
+#[cfg(feature = "cli")]
+pub fn tempdir(sandbox: &std::path::Path) -> std::process::Command {
+    let mut command = std::process::Command::new(env!("CARGO_BIN_EXE_tempdir"));
+    command
+        .env("TEMPDIR_STORE", sandbox.join("store"))
+        .env("TEMPDIR_PARENT", sandbox)
+        .env("XDG_CONFIG_HOME", sandbox.join("config"))
+        .env_remove("TEMPDIR_BASE")
+        .env_remove("TEMPDIR_ROOTS")
+        .env_remove("TEMPDIR_AUDIT_LOG");
+    command
+}
+
+/// Exit code of running `command` with `args`
+#[cfg(feature = "cli")]
+pub fn exit_code(command: &mut std::process::Command, args: &[&str]) -> Option<i32> {
+    command.args(args).output().unwrap().status.code()
+}
+
+/// A clock `seconds` ahead of the system clock, to act as if that much time
+/// had passed instead of sleeping until it has
+pub fn clock_after(seconds: u64) -> std::sync::Arc<tempdir::ManualClock> {
+    let clock = tempdir::ManualClock::default();
+    clock.advance(std::time::Duration::from_secs(seconds));
+    std::sync::Arc::new(clock)
+}
+
+/// Clean options with a clock `seconds` ahead of the system clock, see
+/// `clock_after`
+pub fn clean_options_after(seconds: u64) -> tempdir::CleanOptions {
+    tempdir::CleanOptions { clock: Some(clock_after(seconds)), ..tempdir::CleanOptions::default() }
+}
@@ -0,0 +1,21 @@
+mod common;
+
+use std::thread;
+use tempdir::create_temp_dir;
+
+#[test]
+fn concurrent_creations_are_all_recorded() {
+    let sandbox = common::sandboxed_store("index-concurrent");
+
+    let creators: Vec<_> = (0..8)
+        .map(|index| thread::spawn(move || create_temp_dir(&format!("concurrent-{index}"), "1h").unwrap()))
+        .collect();
+    for creator in creators {
+        creator.join().unwrap();
+    }
+
+    for index in 0..8 {
+        let name = format!("concurrent-{index}");
+        assert_eq!(common::meta_data_records(&sandbox.join("store"), &name).len(), 1);
+    }
+}
@@ -0,0 +1,110 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use tempdir::Config;
+
+#[test]
+fn parses_every_setting() {
+    let config = Config::parse(
+        r#"
+        # Shared scratch space
+        store = "/var/lib/tempdir"
+        parent = "/tmp"
+        base = "/srv/scratch"
+        roots = "/tmp:/srv/scratch"
+        default_duration = "1d"
+        max_lifetime = "4w"
+        auto_clean = true
+        recursive_clean = true
+        on_expire = "sync-results --quiet"
+        keep_to = "/srv/results"
+        archive_to = "/srv/archive"
+        active_window = "2h"
+        max_total_size = "10G"
+        min_free_space = "5G"
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(config.store, Some(PathBuf::from("/var/lib/tempdir")));
+    assert_eq!(config.parent, Some(PathBuf::from("/tmp")));
+    assert_eq!(config.base, Some(PathBuf::from("/srv/scratch")));
+    assert_eq!(config.roots.as_deref(), Some("/tmp:/srv/scratch"));
+    assert_eq!(config.default_duration.as_deref(), Some("1d"));
+    assert_eq!(config.max_lifetime.as_deref(), Some("4w"));
+    assert!(config.auto_clean);
+    assert!(config.recursive_clean);
+    assert_eq!(config.on_expire.as_deref(), Some("sync-results --quiet"));
+    assert_eq!(config.archive_to, Some(PathBuf::from("/srv/archive")));
+    assert_eq!(config.keep_to, Some(PathBuf::from("/srv/results")));
+    assert_eq!(config.active_window.as_deref(), Some("2h"));
+    assert_eq!(config.max_total_size.as_deref(), Some("10G"));
+    assert_eq!(config.min_free_space.as_deref(), Some("5G"));
+}
+
+#[test]
+fn rejects_unknown_settings() {
+    assert!(Config::parse("colour = \"blue\"").is_err());
+    assert!(Config::parse("store").is_err());
+}
+
+#[test]
+fn loads_an_explicit_config_file() {
+    let path = env::temp_dir().join(format!("tempdir-config-{}.toml", std::process::id()));
+    fs::write(&path, "default_duration = \"2h\"\n").unwrap();
+
+    let config = Config::load_from(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(config.default_duration.as_deref(), Some("2h"));
+    assert!(Config::load_from(&path).is_err());
+}
+
+#[test]
+fn parses_profile_sections() {
+    let config = Config::parse(
+        r#"
+        parent = "/tmp"
+
+        [profiles.ci] # Directories of the build jobs
+        store = "/var/lib/tempdir-ci"
+        parent = "/srv/ci"
+
+        [profiles.personal]
+        base = "/home/scratch"
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(config.parent, Some(PathBuf::from("/tmp")));
+    assert_eq!(config.profiles.len(), 2);
+    assert_eq!(config.profiles["ci"].store, Some(PathBuf::from("/var/lib/tempdir-ci")));
+    assert_eq!(config.profiles["ci"].parent, Some(PathBuf::from("/srv/ci")));
+    assert_eq!(config.profiles["personal"].base, Some(PathBuf::from("/home/scratch")));
+    assert_eq!(config.profile("ci").unwrap(), config.profiles["ci"]);
+}
+
+#[test]
+fn rejects_invalid_sections() {
+    assert!(Config::parse("[colours]").is_err());
+    assert!(Config::parse("[profiles.a/b]").is_err());
+    assert!(Config::parse("[profiles.ci]\ncolour = \"blue\"").is_err());
+}
+
+#[test]
+fn rejects_unsupported_toml_syntax() {
+    for (config, reason) in [
+        ("parent = \"C:\\scratch\"", "escape sequences"),
+        ("parent = \"/tmp\\\"\"", "escape sequences"),
+        ("parent = '/tmp'", "single-quoted strings"),
+        ("parent = \"\"\"/tmp\"\"\"", "multi-line strings"),
+        ("roots = [\"/tmp\", \"/srv\"]", "arrays"),
+        ("profiles = { ci = \"/srv/ci\" }", "inline tables"),
+        ("profiles.ci.parent = \"/srv/ci\"", "dotted and quoted keys"),
+        ("max_lifetime = 1.5", "only strings, integers and booleans"),
+    ] {
+        let contents = format!("# Scratch space\n{config}\n");
+        let message = Config::parse(&contents).unwrap_err().to_string();
+        assert!(message.contains(&format!("line 2: {reason}")), "{config}: {message}");
+    }
+}
@@ -0,0 +1,30 @@
+mod common;
+
+use std::fs;
+use tempdir::{clean_directories_with, SkipReason, TemporaryDirectory};
+
+#[test]
+fn expired_directory_is_emptied_and_its_lifetime_restarted() {
+    common::sandboxed_store("contents-only");
+
+    let tempdir = TemporaryDirectory::new("rolling".to_string(), "2s".to_string())
+        .unwrap()
+        .with_contents_only(true)
+        .try_create()
+        .unwrap();
+    let path = tempdir.path().unwrap();
+    fs::create_dir(path.join("nested")).unwrap();
+    fs::write(path.join("nested").join("download.zip"), "junk").unwrap();
+    fs::write(path.join("notes.txt"), "junk").unwrap();
+    let first_end_time = tempdir.end_time();
+
+    let report = clean_directories_with(&common::clean_options_after(3)).unwrap();
+    assert!(report.deleted.is_empty());
+    assert!(matches!(report.skipped.as_slice(), [SkipReason::Emptied(tempdir)] if tempdir.name() == "rolling"));
+    assert!(path.is_dir());
+    assert_eq!(fs::read_dir(&path).unwrap().count(), 0);
+
+    let reloaded = TemporaryDirectory::load("rolling", None).unwrap();
+    assert!(reloaded.end_time() > first_end_time);
+    assert!(!reloaded.is_expired());
+}
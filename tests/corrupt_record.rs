@@ -0,0 +1,30 @@
+mod common;
+
+use std::fs;
+use tempdir::{create_temp_dir, diagnose, list_directories, repair, Problem};
+
+#[test]
+fn corrupt_record_is_reported_and_kept() {
+    let sandbox = common::sandboxed_store("corrupt-record");
+    let store = sandbox.join("store");
+    create_temp_dir("healthy", "1h").unwrap();
+
+    let index_path = store.join("index.json");
+    let mut index: serde_json::Map<String, serde_json::Value> = serde_json::from_str(&fs::read_to_string(&index_path).unwrap()).unwrap();
+    index.insert("broken-1".to_string(), serde_json::json!({ "name": 5 }));
+    fs::write(&index_path, serde_json::to_string(&index).unwrap()).unwrap();
+
+    let names: Vec<String> = list_directories().unwrap().iter().map(|tempdir| tempdir.name().to_string()).collect();
+    assert_eq!(names, ["healthy"]);
+    create_temp_dir("created", "1h").unwrap();
+
+    let findings = diagnose(&[]).unwrap();
+    assert!(findings.iter().any(|finding| finding.problem == Problem::Unparsable && finding.detail.starts_with("broken-1")));
+    let report = repair().unwrap();
+    assert_eq!(report.invalid.len(), 1);
+
+    // Saving the store writes the broken record back untouched
+    let index: serde_json::Map<String, serde_json::Value> = serde_json::from_str(&fs::read_to_string(&index_path).unwrap()).unwrap();
+    assert_eq!(index["broken-1"], serde_json::json!({ "name": 5 }));
+    assert_eq!(index.len(), 3);
+}
@@ -0,0 +1,31 @@
+mod common;
+
+use std::env;
+use std::fs;
+use tempdir::TemporaryDirectory;
+
+#[test]
+fn delete_resolves_path_after_cwd_change() {
+    let sandbox = common::sandbox("create-path");
+    let store = sandbox.join("store");
+    let elsewhere = sandbox.join("elsewhere");
+    fs::create_dir_all(&elsewhere).unwrap();
+    env::set_var("TEMPDIR_STORE", &store);
+    env::set_current_dir(&sandbox).unwrap();
+
+    let name = format!("cwd-change-{}", std::process::id());
+    TemporaryDirectory::new(name.clone(), "1h".to_string())
+        .unwrap()
+        .create()
+        .unwrap();
+    assert!(sandbox.join(&name).is_dir());
+
+    let record = common::meta_data_records(&store, &name).remove(0);
+    let tempdir: TemporaryDirectory = serde_json::from_value(record).unwrap();
+
+    env::set_current_dir(&elsewhere).unwrap();
+    tempdir.delete().unwrap();
+
+    assert!(!sandbox.join(&name).exists());
+    fs::remove_dir_all(&sandbox).unwrap();
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tempdir::{CleanOptions, MetadataStore, TempDirErrors, TempDirManager, TemporaryDirectory};
+
+/// Keeps its records in a list shared with the test, and logs the calls
+#[derive(Default)]
+struct ListStore {
+    records: Arc<Mutex<Vec<TemporaryDirectory>>>,
+    calls: Arc<Mutex<Vec<&'static str>>>,
+}
+impl ListStore {
+    fn position(records: &[TemporaryDirectory], tempdir: &TemporaryDirectory) -> Option<usize> {
+        records.iter().position(|record| record.name() == tempdir.name() && record.path() == tempdir.path())
+    }
+}
+impl MetadataStore for ListStore {
+    fn save(&mut self, tempdir: &TemporaryDirectory) -> Result<(), TempDirErrors> {
+        self.calls.lock().unwrap().push("save");
+        let mut records = self.records.lock().unwrap();
+        match ListStore::position(&records, tempdir) {
+            Some(index) => records[index] = tempdir.clone(),
+            None => records.push(tempdir.clone()),
+        }
+        Ok(())
+    }
+
+    fn load(&self, name: &str, _parent: Option<&Path>) -> Result<TemporaryDirectory, TempDirErrors> {
+        self.calls.lock().unwrap().push("load");
+        self.records.lock().unwrap().iter().find(|record| record.name() == name).cloned().ok_or(TempDirErrors::NotFound)
+    }
+
+    fn list(&self) -> Result<Vec<TemporaryDirectory>, TempDirErrors> {
+        self.calls.lock().unwrap().push("list");
+        Ok(self.records.lock().unwrap().clone())
+    }
+
+    fn remove(&mut self, tempdir: &TemporaryDirectory) -> Result<(), TempDirErrors> {
+        self.calls.lock().unwrap().push("remove");
+        let mut records = self.records.lock().unwrap();
+        if let Some(index) = ListStore::position(&records, tempdir) {
+            records.remove(index);
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn manager_keeps_its_records_in_the_given_backend() {
+    let sandbox = common::sandboxed_store("custom-backend");
+    let backend = ListStore::default();
+    let (records, calls) = (backend.records.clone(), backend.calls.clone());
+    let manager = TempDirManager::with_backend(backend, CleanOptions::default());
+
+    let scratch = TemporaryDirectory::new("scratch".to_string(), "1h".to_string()).unwrap().with_parent(Some(sandbox.clone()));
+    let created = manager.create(scratch).unwrap();
+    assert!(created.path().unwrap().is_dir());
+    assert_eq!(records.lock().unwrap().len(), 1);
+    assert!(calls.lock().unwrap().contains(&"save"));
+    assert!(common::meta_data_records(&sandbox.join("store"), "scratch").is_empty());
+
+    let extended = manager.extend("scratch", None, "1h").unwrap();
+    assert_eq!(records.lock().unwrap()[0].end_time(), extended.end_time());
+    assert_eq!(manager.list().unwrap().len(), 1);
+
+    manager.get("scratch", None).unwrap().delete_now().unwrap();
+    assert!(!created.path().unwrap().exists());
+    assert!(records.lock().unwrap().is_empty());
+    let calls = calls.lock().unwrap();
+    for call in ["load", "list", "remove"] {
+        assert!(calls.contains(&call), "{call} wasn't called: {calls:?}");
+    }
+}
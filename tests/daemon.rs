@@ -0,0 +1,26 @@
+mod common;
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tempdir::{create_temp_dir, daemon_pass, CleanOptions, ManualClock};
+
+#[test]
+fn daemon_pass_removes_expired_directories() {
+    let sandbox = common::sandboxed_store("daemon");
+    let expiring = create_temp_dir("daemon-expiring", "1h").unwrap();
+    let alive = create_temp_dir("daemon-alive", "4h").unwrap();
+    let clock = Arc::new(ManualClock::at(expiring.created_at()));
+    let options = CleanOptions { clock: Some(clock.clone()), ..CleanOptions::default() };
+    let mut notified = HashSet::new();
+
+    daemon_pass(&options, &mut notified);
+    assert!(expiring.path().unwrap().is_dir());
+
+    clock.advance(Duration::from_secs(2 * 60 * 60));
+    daemon_pass(&options, &mut notified);
+    assert!(!expiring.path().unwrap().exists());
+    assert!(common::meta_data_records(&sandbox.join("store"), "daemon-expiring").is_empty());
+    assert!(alive.path().unwrap().is_dir());
+    assert_eq!(common::meta_data_records(&sandbox.join("store"), "daemon-alive").len(), 1);
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use std::env;
+use std::fs;
+use tempdir::{clean_directories_with, create_temp_dir, delete_untracked, TempDirErrors, TemporaryDirectory};
+
+/// Points the record of `name` in the index at `path`, like a corrupted or
+/// edited meta data store would
+fn redirect(store: &std::path::Path, name: &str, path: &std::path::Path) {
+    let index_path = store.join("index.json");
+    let mut index: serde_json::Map<String, serde_json::Value> = serde_json::from_str(&fs::read_to_string(&index_path).unwrap()).unwrap();
+    for record in index.values_mut().filter(|record| record["name"] == name) {
+        record["path"] = serde_json::json!(path);
+        record["relative"] = serde_json::json!(false);
+    }
+    fs::write(&index_path, serde_json::to_string(&index).unwrap()).unwrap();
+}
+
+#[test]
+fn deletion_outside_roots_and_of_home_is_refused() {
+    let sandbox = common::sandboxed_store("deletion-guard");
+    let root = sandbox.join("root");
+    let outside = common::sandbox("deletion-guard-outside");
+    let home = common::sandbox("deletion-guard-home");
+    fs::create_dir_all(&root).unwrap();
+    env::set_var("TEMPDIR_PARENT", &root);
+    env::set_var("TEMPDIR_ROOTS", &root);
+    env::set_var("HOME", &home);
+
+    let inside = create_temp_dir("inside", "1h").unwrap().path().unwrap();
+    create_temp_dir("moved-out", "1h").unwrap();
+    redirect(&sandbox.join("store"), "moved-out", &outside);
+    create_temp_dir("moved-home", "1h").unwrap();
+    redirect(&sandbox.join("store"), "moved-home", &home);
+
+    let refused = TemporaryDirectory::load("moved-out", None).unwrap().delete_now();
+    assert!(matches!(refused, Err(TempDirErrors::UnsafeDeletion(..))));
+    assert!(outside.is_dir());
+    assert!(TemporaryDirectory::load("moved-out", None).is_ok());
+    assert!(matches!(delete_untracked(&outside), Err(TempDirErrors::UnsafeDeletion(..))));
+    assert!(outside.is_dir());
+
+    let refused = TemporaryDirectory::load("moved-home", None).unwrap().delete();
+    assert!(matches!(refused, Err(TempDirErrors::UnsafeDeletion(..))));
+    assert!(home.is_dir());
+
+    TemporaryDirectory::load("inside", None).unwrap().delete_now().unwrap();
+    assert!(!inside.exists());
+
+    fs::remove_dir_all(&outside).unwrap();
+    fs::remove_dir_all(&home).unwrap();
+}
+
+#[test]
+fn clean_reports_refused_deletion() {
+    let sandbox = common::sandboxed_store("deletion-guard");
+    let root = sandbox.join("root");
+    fs::create_dir_all(&root).unwrap();
+    env::set_var("TEMPDIR_PARENT", &root);
+    env::set_var("TEMPDIR_ROOTS", &root);
+
+    create_temp_dir("moved-root", "1s").unwrap();
+    redirect(&sandbox.join("store"), "moved-root", std::path::Path::new("/"));
+
+    let report = clean_directories_with(&common::clean_options_after(2)).unwrap();
+    assert!(report.deleted.iter().all(|cleaned| cleaned.directory.name() != "moved-root"));
+    assert!(report.errors.iter().any(|error| error.path == std::path::Path::new("/")));
+    assert!(TemporaryDirectory::load("moved-root", None).is_ok());
+}
+
+#[test]
+fn dropped_guard_outside_roots_keeps_its_record() {
+    let sandbox = common::sandboxed_store("deletion-guard");
+    let root = sandbox.join("root");
+    let outside = common::sandbox("deletion-guard-scoped");
+    fs::create_dir_all(&root).unwrap();
+    env::set_var("TEMPDIR_PARENT", &root);
+    env::set_var("TEMPDIR_ROOTS", &root);
+
+    let guard = TemporaryDirectory::new("scoped-outside".to_string(), "1h".to_string())
+        .unwrap()
+        .with_parent(Some(outside.clone()))
+        .create_scoped()
+        .unwrap();
+    let path = guard.directory().path().unwrap();
+    drop(guard);
+
+    assert!(path.is_dir());
+    assert_eq!(common::meta_data_records(&sandbox.join("store"), "scoped-outside").len(), 1);
+    fs::remove_dir_all(&outside).unwrap();
+}
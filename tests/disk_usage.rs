@@ -0,0 +1,55 @@
+mod common;
+
+use std::fs;
+use tempdir::TemporaryDirectory;
+
+/// Collects the warnings the library logs
+#[cfg(feature = "log")]
+mod capture {
+    use std::sync::Mutex;
+
+    pub static WARNINGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    struct Capture;
+    impl log::Log for Capture {
+        fn enabled(&self, metadata: &log::Metadata) -> bool {
+            metadata.level() <= log::Level::Warn
+        }
+
+        fn log(&self, record: &log::Record) {
+            if record.level() == log::Level::Warn {
+                WARNINGS.lock().unwrap().push(record.args().to_string());
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    pub fn install() {
+        log::set_logger(&Capture).unwrap();
+        log::set_max_level(log::LevelFilter::Warn);
+    }
+}
+
+#[test]
+fn disk_usage_sums_nested_files_and_is_zero_once_removed() {
+    #[cfg(feature = "log")]
+    capture::install();
+    common::sandboxed_store("disk-usage");
+
+    let mut tempdir = TemporaryDirectory::new("usage".to_string(), "1h".to_string()).unwrap();
+    let path = tempdir.create().unwrap();
+    fs::create_dir_all(path.join("a").join("b")).unwrap();
+    fs::write(path.join("top"), [0u8; 100]).unwrap();
+    fs::write(path.join("a").join("middle"), [0u8; 20]).unwrap();
+    fs::write(path.join("a").join("b").join("bottom"), [0u8; 3]).unwrap();
+    assert_eq!(tempdir.disk_usage().unwrap(), 123);
+    #[cfg(feature = "log")]
+    assert!(capture::WARNINGS.lock().unwrap().is_empty());
+
+    fs::remove_dir_all(&path).unwrap();
+    assert_eq!(tempdir.disk_usage().unwrap(), 0);
+    #[cfg(feature = "log")]
+    assert_eq!(*capture::WARNINGS.lock().unwrap(), vec!["Directory of usage doesn't exist. Assuming it uses no space".to_string()]);
+    tempdir.delete_now().unwrap();
+}
@@ -0,0 +1,31 @@
+mod common;
+
+use std::fs;
+use tempdir::{create_temp_dir, diagnose, repair, Problem, TemporaryDirectory};
+
+#[test]
+fn doctor_reports_store_problems() {
+    let sandbox = common::sandboxed_store("doctor");
+    let outside = common::sandbox("doctor-outside");
+
+    create_temp_dir("healthy", "1h").unwrap();
+    let vanished = create_temp_dir("vanished", "1h").unwrap();
+    fs::remove_dir(vanished.path().unwrap()).unwrap();
+    TemporaryDirectory::adopt(&outside, "1h".to_string()).unwrap();
+    fs::write(sandbox.join("store").join("broken.json"), "{").unwrap();
+
+    let roots = vec![sandbox.clone()];
+    let findings = diagnose(&roots).unwrap();
+    let problems: Vec<Problem> = findings.iter().map(|finding| finding.problem).collect();
+    assert_eq!(problems.len(), 3, "{findings:?}");
+    assert!(problems.contains(&Problem::Unparsable));
+    assert!(problems.contains(&Problem::MissingDirectory));
+    assert!(problems.contains(&Problem::OutsideRoots));
+
+    repair().unwrap();
+
+    let problems: Vec<Problem> = diagnose(&roots).unwrap().iter().map(|finding| finding.problem).collect();
+    assert_eq!(problems.len(), 2);
+    assert!(!problems.contains(&Problem::MissingDirectory));
+    fs::remove_dir_all(&outside).unwrap();
+}
@@ -0,0 +1,17 @@
+mod common;
+
+use tempdir::{clean_directories_with, create_temp_dir, CleanOptions};
+
+#[test]
+fn dry_run_reports_without_deleting() {
+    let sandbox = common::sandboxed_store("dry-run");
+
+    let tempdir = create_temp_dir("dry-run", "1s").unwrap();
+    let path = tempdir.path().unwrap();
+
+    let report = clean_directories_with(&CleanOptions { dry_run: true, ..common::clean_options_after(2) }).unwrap();
+
+    assert!(report.deleted.iter().any(|cleaned| cleaned.directory.name() == "dry-run"));
+    assert!(path.is_dir());
+    assert_eq!(common::meta_data_records(&sandbox.join("store"), "dry-run").len(), 1);
+}
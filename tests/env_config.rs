@@ -0,0 +1,22 @@
+mod common;
+
+use std::env;
+use std::fs;
+use tempdir::create_temp_dir;
+
+#[test]
+fn store_and_parent_are_taken_from_the_environment() {
+    let sandbox = env::temp_dir().join(format!("tempdir-env-config-{}", std::process::id()));
+    let store = sandbox.join("store");
+    let parent = sandbox.join("parent");
+    fs::create_dir_all(&parent).unwrap();
+    env::set_var("TEMPDIR_STORE", &store);
+    env::set_var("TEMPDIR_PARENT", &parent);
+
+    let tempdir = create_temp_dir("configured", "1h").unwrap();
+
+    assert_eq!(tempdir.path(), Some(parent.canonicalize().unwrap().join("configured")));
+    assert!(parent.join("configured").is_dir());
+    assert_eq!(common::meta_data_records(&store, "configured").len(), 1);
+    fs::remove_dir_all(&sandbox).unwrap();
+}
@@ -0,0 +1,67 @@
+mod common;
+
+use std::fs;
+use tempdir::{clean_directories_with, exclude_pattern, TemporaryDirectory};
+
+#[test]
+fn exclude_globs_match_relative_paths() {
+    let logs = exclude_pattern("*.log").unwrap();
+    assert!(logs.is_match("run.log"));
+    assert!(logs.is_match("nested/deeper/run.log"));
+    assert!(!logs.is_match("run.log.gz"));
+
+    let results = exclude_pattern("results/**").unwrap();
+    assert!(results.is_match("results/a/b.csv"));
+    assert!(!results.is_match("other/results/a.csv"));
+
+    let nested = exclude_pattern("**/keep/*.txt").unwrap();
+    assert!(nested.is_match("keep/a.txt"));
+    assert!(nested.is_match("a/b/keep/a.txt"));
+    assert!(!nested.is_match("keep/a/b.txt"));
+}
+
+fn fill(path: &std::path::Path) {
+    fs::create_dir_all(path.join("results").join("run")).unwrap();
+    fs::create_dir_all(path.join("build")).unwrap();
+    fs::write(path.join("results").join("run").join("out.csv"), "1,2").unwrap();
+    fs::write(path.join("build").join("debug.log"), "log").unwrap();
+    fs::write(path.join("build").join("object.o"), "junk").unwrap();
+    fs::write(path.join("scratch.bin"), "junk").unwrap();
+}
+
+#[test]
+fn expired_directory_keeps_excluded_entries() {
+    let sandbox = common::sandboxed_store("exclude");
+
+    let sparse = TemporaryDirectory::new("sparse".to_string(), "1s".to_string())
+        .unwrap()
+        .with_exclude(vec!["*.log".to_string(), "results/**".to_string()])
+        .try_create()
+        .unwrap()
+        .path()
+        .unwrap();
+    fill(&sparse);
+
+    let keep_to = sandbox.join("kept");
+    let moved = TemporaryDirectory::new("moved".to_string(), "1s".to_string())
+        .unwrap()
+        .with_exclude(vec!["results/**".to_string()])
+        .with_keep_to(Some(keep_to.clone()))
+        .try_create()
+        .unwrap()
+        .path()
+        .unwrap();
+    fill(&moved);
+
+    let report = clean_directories_with(&common::clean_options_after(2)).unwrap();
+    assert_eq!(report.deleted.len(), 2, "{report:?}");
+
+    assert!(sparse.join("results").join("run").join("out.csv").is_file());
+    assert!(sparse.join("build").join("debug.log").is_file());
+    assert!(!sparse.join("build").join("object.o").exists());
+    assert!(!sparse.join("scratch.bin").exists());
+    assert!(TemporaryDirectory::load("sparse", None).is_err());
+
+    assert!(!moved.exists());
+    assert_eq!(fs::read_to_string(keep_to.join("moved").join("results").join("run").join("out.csv")).unwrap(), "1,2");
+}
@@ -0,0 +1,55 @@
+#![cfg(unix)]
+
+mod common;
+
+use tempdir::{clean_directories_with, create_temp_dir, CleanOptions, SkipReason, TemporaryDirectory};
+
+#[test]
+fn failing_default_hook_keeps_the_directory() {
+    common::sandboxed_store("expiry-hooks");
+
+    let tempdir = create_temp_dir("hooked", "1s").unwrap();
+    let path = tempdir.path().unwrap();
+
+    let failing = CleanOptions { on_expire: Some("false".to_string()), ..common::clean_options_after(2) };
+    let report = clean_directories_with(&failing).unwrap();
+    assert!(path.is_dir());
+    assert!(report.skipped.iter().any(|skipped| matches!(skipped, SkipReason::HookFailed(tempdir) if tempdir.name() == "hooked")));
+
+    let succeeding = CleanOptions { on_expire: Some("true".to_string()), ..common::clean_options_after(2) };
+    let report = clean_directories_with(&succeeding).unwrap();
+    assert!(!path.exists());
+    assert!(report.deleted.iter().any(|deleted| deleted.directory.name() == "hooked"));
+}
+
+#[test]
+fn failing_hook_of_a_directory_aborts_its_deletion() {
+    common::sandboxed_store("expiry-hooks");
+
+    let tempdir = TemporaryDirectory::new("own-hook".to_string(), "1s".to_string())
+        .unwrap()
+        .with_on_expire(Some("false".to_string()))
+        .try_create()
+        .unwrap();
+    let path = tempdir.path().unwrap();
+
+    clean_directories_with(&common::clean_options_after(2)).unwrap();
+    assert!(path.is_dir());
+}
+
+#[test]
+fn hook_runs_through_the_shell_with_the_path_as_first_argument() {
+    let sandbox = common::sandboxed_store("expiry-hooks");
+    let seen = sandbox.join("seen by hook");
+
+    let tempdir = TemporaryDirectory::new("shell-hook".to_string(), "1s".to_string())
+        .unwrap()
+        .with_on_expire(Some(format!("test -d \"$1\" && printf '%s' \"$1\" > '{}'", seen.display())))
+        .try_create()
+        .unwrap();
+    let path = tempdir.path().unwrap();
+
+    clean_directories_with(&common::clean_options_after(2)).unwrap();
+    assert_eq!(std::fs::read_to_string(&seen).unwrap(), path.display().to_string());
+    assert!(!path.exists());
+}
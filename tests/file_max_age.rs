@@ -0,0 +1,58 @@
+mod common;
+
+use std::fs::{self, File};
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+use tempdir::{clean_directories, clean_directories_with, CleanOptions, TempDirErrors, TemporaryDirectory};
+
+fn write_aged(path: &Path, age: Duration) {
+    fs::write(path, "data").unwrap();
+    File::options().write(true).open(path).unwrap().set_modified(SystemTime::now() - age).unwrap();
+}
+
+#[test]
+fn clean_removes_files_older_than_the_file_max_age() {
+    common::sandboxed_store("file-max-age");
+    let week = Duration::from_secs(7 * 24 * 60 * 60);
+
+    let tempdir = TemporaryDirectory::new("downloads".to_string(), "4w".to_string())
+        .unwrap()
+        .with_file_max_age(Some("7d".to_string()))
+        .unwrap()
+        .try_create()
+        .unwrap();
+    let path = tempdir.path().unwrap();
+    fs::create_dir_all(path.join("old")).unwrap();
+    fs::create_dir_all(path.join("mixed")).unwrap();
+    write_aged(&path.join("stale.iso"), week * 2);
+    write_aged(&path.join("old").join("stale.zip"), week * 2);
+    write_aged(&path.join("mixed").join("stale.txt"), week * 2);
+    write_aged(&path.join("mixed").join("fresh.txt"), Duration::from_secs(60));
+    write_aged(&path.join("fresh.iso"), Duration::ZERO);
+
+    let report = clean_directories_with(&CleanOptions { dry_run: true, ..CleanOptions::default() }).unwrap();
+    assert_eq!(report.expired_files.len(), 3);
+    assert!(path.join("stale.iso").exists());
+
+    let report = clean_directories().unwrap();
+    assert!(report.deleted.is_empty());
+    assert_eq!(report.expired_files.len(), 3);
+    assert!(report.expired_files.iter().all(|file| file.directory == "downloads" && file.size == 4));
+    assert!(!path.join("stale.iso").exists());
+    assert!(!path.join("old").exists());
+    assert!(!path.join("mixed").join("stale.txt").exists());
+    assert!(path.join("mixed").join("fresh.txt").is_file());
+    assert!(path.join("fresh.iso").is_file());
+    assert!(TemporaryDirectory::load("downloads", None).is_ok());
+}
+
+#[test]
+fn file_max_age_has_to_be_longer_than_zero() {
+    for max_age in ["-1d", "0s", "1x"] {
+        let tempdir = TemporaryDirectory::new("ageless".to_string(), "1h".to_string()).unwrap();
+        let rejected = tempdir.with_file_max_age(Some(max_age.to_string()));
+        assert!(matches!(rejected, Err(TempDirErrors::InvalidDuration(_) | TempDirErrors::WrongDurationString)), "{max_age} should be rejected");
+    }
+    let tempdir = TemporaryDirectory::new("ageless".to_string(), "1h".to_string()).unwrap();
+    assert!(matches!(tempdir.with_file_max_age(Some("-1d".to_string())), Err(TempDirErrors::InvalidDuration(_))));
+}
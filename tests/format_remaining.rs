@@ -0,0 +1,30 @@
+use tempdir::format_remaining;
+
+#[test]
+fn formats_exactly_one_week() {
+    assert_eq!(format_remaining(604800), "1w");
+}
+
+#[test]
+fn formats_ninety_seconds() {
+    assert_eq!(format_remaining(90), "1min 30s");
+}
+
+#[test]
+fn formats_negative_as_expired() {
+    assert_eq!(format_remaining(-5), "expired");
+}
+
+#[test]
+fn formats_multi_unit_value_with_two_largest_units() {
+    // 1 day, 2 hours, 3 minutes and 4 seconds
+    assert_eq!(format_remaining(93784), "1d 2h");
+}
+
+#[test]
+fn formats_boundaries() {
+    assert_eq!(format_remaining(0), "0s");
+    assert_eq!(format_remaining(59), "59s");
+    assert_eq!(format_remaining(60), "1min");
+    assert_eq!(format_remaining(604799), "6d 23h");
+}
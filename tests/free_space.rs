@@ -0,0 +1,24 @@
+mod common;
+
+use std::fs;
+use tempdir::{clean_directories_with, create_temp_dir, CleanOptions};
+
+#[test]
+fn low_free_space_evicts_soonest_expiring_directories() {
+    common::sandboxed_store("free-space");
+
+    let later = create_temp_dir("later", "2h").unwrap().path().unwrap();
+    let soon = create_temp_dir("soon", "1h").unwrap().path().unwrap();
+    let empty = create_temp_dir("empty", "1h").unwrap().path().unwrap();
+    fs::write(soon.join("data"), "data").unwrap();
+    fs::write(later.join("data"), "data").unwrap();
+
+    // No file system has that much space, so everything that frees some is
+    // evicted
+    let options = CleanOptions { min_free_space: Some(u64::MAX), dry_run: true, ..CleanOptions::default() };
+    let report = clean_directories_with(&options).unwrap();
+
+    let evicted: Vec<&str> = report.deleted.iter().map(|cleaned| cleaned.directory.name()).collect();
+    assert_eq!(evicted, vec!["soon", "later"]);
+    assert!(soon.is_dir() && later.is_dir() && empty.is_dir());
+}
@@ -0,0 +1,34 @@
+mod common;
+
+use std::env;
+use std::fs;
+use tempdir::{clean_directories, TemporaryDirectory};
+
+#[test]
+fn directory_created_in_the_future_is_not_cleaned() {
+    let sandbox = env::temp_dir().join(format!("tempdir-future-{}", std::process::id()));
+    let store = sandbox.join("store");
+    let directory = sandbox.join("future");
+    fs::create_dir_all(&store).unwrap();
+    fs::create_dir_all(&directory).unwrap();
+    env::set_var("TEMPDIR_STORE", &store);
+
+    let now = chrono::offset::Local::now().timestamp();
+    let record = serde_json::json!({
+        "name": "future",
+        "duration": "1h",
+        "created_at": now + 7200,
+        "end_time": now - 10,
+        "path": directory,
+    });
+    fs::write(store.join("future.json"), record.to_string()).unwrap();
+
+    let tempdir: TemporaryDirectory = serde_json::from_value(record).unwrap();
+    assert!(!tempdir.is_expired());
+
+    clean_directories().unwrap();
+
+    assert!(directory.is_dir());
+    assert_eq!(common::meta_data_records(&store, "future").len(), 1);
+    fs::remove_dir_all(&sandbox).unwrap();
+}
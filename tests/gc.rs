@@ -0,0 +1,25 @@
+mod common;
+
+use std::fs;
+use tempdir::{create_temp_dir, delete_untracked, untracked_directories, TemporaryDirectory};
+
+#[test]
+fn untracked_directories_can_be_adopted_or_deleted() {
+    let sandbox = common::sandboxed_store("gc");
+    let sandbox = sandbox.canonicalize().unwrap();
+
+    create_temp_dir("tracked", "1h").unwrap();
+    fs::create_dir(sandbox.join("stray")).unwrap();
+    fs::create_dir(sandbox.join("abandoned")).unwrap();
+    fs::write(sandbox.join("abandoned").join("file"), "contents").unwrap();
+
+    let untracked = untracked_directories(&sandbox).unwrap();
+    assert_eq!(untracked, vec![sandbox.join("abandoned"), sandbox.join("stray")]);
+
+    TemporaryDirectory::adopt(&sandbox.join("stray"), "1h".to_string()).unwrap();
+    delete_untracked(&sandbox.join("abandoned")).unwrap();
+
+    assert!(untracked_directories(&sandbox).unwrap().is_empty());
+    assert!(!sandbox.join("abandoned").exists());
+    assert_eq!(common::meta_data_records(&sandbox.join("store"), "stray").len(), 1);
+}
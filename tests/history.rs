@@ -0,0 +1,31 @@
+mod common;
+
+use std::fs;
+use tempdir::history::{history, Action};
+use tempdir::{create_temp_dir, TemporaryDirectory};
+
+#[test]
+fn events_are_recorded_in_the_store() {
+    let sandbox = common::sandboxed_store("history");
+
+    let mut tempdir = TemporaryDirectory::new("recorded".to_string(), "1h".to_string())
+        .unwrap()
+        .with_delete_contents(true)
+        .try_create()
+        .unwrap();
+    let path = tempdir.path().unwrap();
+    tempdir.extend("1h").unwrap();
+    fs::write(path.join("file"), "12345").unwrap();
+    tempdir.delete_now().unwrap();
+    create_temp_dir("unrelated", "1h").unwrap();
+
+    let events = history(Some("recorded")).unwrap();
+    let actions: Vec<Action> = events.iter().map(|event| event.action).collect();
+    assert_eq!(actions, [Action::Create, Action::Extend, Action::Delete]);
+    assert!(events.iter().all(|event| event.path.as_deref() == Some(path.as_path())));
+    assert_eq!(events[2].size_freed, Some(5));
+    assert!(events[2].user.is_some());
+
+    assert_eq!(history(None).unwrap().len(), 4);
+    assert!(sandbox.join("store").join("events.jsonl").is_file());
+}
@@ -0,0 +1,44 @@
+mod common;
+
+use std::fs;
+use tempdir::TemporaryDirectory;
+
+#[test]
+fn existing_directory_is_reused() {
+    let sandbox = common::sandboxed_store("if-not-exists");
+
+    let mut first = TemporaryDirectory::new("cache".to_string(), "7d".to_string()).unwrap();
+    let path = first.create().unwrap();
+    fs::write(path.join("kept"), "contents").unwrap();
+
+    let mut again = TemporaryDirectory::new("cache".to_string(), "1h".to_string()).unwrap();
+    assert_eq!(again.create_if_not_exists().unwrap(), path);
+    assert!(path.join("kept").is_file());
+    // The handle took over the existing record
+    assert_eq!(again.end_time(), first.end_time());
+    assert_eq!(common::meta_data_records(&sandbox.join("store"), "cache").len(), 1);
+}
+
+#[test]
+fn untracked_directory_is_tracked() {
+    let sandbox = common::sandboxed_store("if-not-exists");
+    fs::create_dir_all(sandbox.join("untracked")).unwrap();
+
+    let mut tempdir = TemporaryDirectory::new("untracked".to_string(), "1h".to_string()).unwrap().with_tags(vec!["ci".to_string()]);
+    let path = tempdir.create_if_not_exists().unwrap();
+
+    assert_eq!(path, sandbox.join("untracked").canonicalize().unwrap());
+    let record = common::meta_data_records(&sandbox.join("store"), "untracked").remove(0);
+    assert_eq!(record["tags"], serde_json::json!(["ci"]));
+}
+
+#[test]
+fn missing_directory_is_created() {
+    let sandbox = common::sandboxed_store("if-not-exists");
+
+    let mut tempdir = TemporaryDirectory::new("fresh".to_string(), "1h".to_string()).unwrap();
+    let path = tempdir.create_if_not_exists().unwrap();
+
+    assert!(path.is_dir());
+    assert_eq!(common::meta_data_records(&sandbox.join("store"), "fresh").len(), 1);
+}
@@ -0,0 +1,49 @@
+#![cfg(feature = "test-util")]
+
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+use tempdir::{CleanOptions, InMemoryStore, ManualClock, MetadataStore, TempDirErrors, TempDirManager, TemporaryDirectory};
+
+#[test]
+fn in_memory_store_saves_loads_and_removes_records() {
+    let mut store = InMemoryStore::new();
+    let scratch = TemporaryDirectory::new("scratch".to_string(), "1h".to_string()).unwrap();
+    let build = TemporaryDirectory::new("build".to_string(), "2h".to_string()).unwrap();
+    store.save(&scratch).unwrap();
+    store.save(&build).unwrap();
+    // Saving again replaces the record
+    store.save(&scratch).unwrap();
+
+    assert_eq!(store.len(), 2);
+    let names: Vec<String> = store.list().unwrap().iter().map(|tempdir| tempdir.name().to_string()).collect();
+    assert_eq!(names, ["build", "scratch"]);
+    assert_eq!(store.load("build", None).unwrap().end_time(), build.end_time());
+    assert!(matches!(store.load("missing", None), Err(TempDirErrors::NotFound)));
+
+    store.remove(&scratch).unwrap();
+    assert!(store.load("scratch", None).is_err());
+    assert_eq!(store.len(), 1);
+}
+
+#[test]
+fn directories_are_created_and_cleaned_against_an_in_memory_store() {
+    let sandbox = common::sandboxed_store("in-memory-store");
+    let clock = Arc::new(ManualClock::default());
+    let manager = TempDirManager::with_backend(InMemoryStore::new(), CleanOptions { clock: Some(clock.clone()), ..CleanOptions::default() });
+
+    let tempdir = TemporaryDirectory::new("scratch".to_string(), "1h".to_string()).unwrap().with_parent(Some(sandbox.clone()));
+    let path = manager.create(tempdir).unwrap().path().unwrap();
+    assert!(path.is_dir());
+    assert_eq!(manager.list().unwrap().len(), 1);
+    assert!(manager.clean().unwrap().deleted.is_empty());
+
+    clock.advance(Duration::from_secs(2 * 60 * 60));
+    let report = manager.clean().unwrap();
+    assert_eq!(report.deleted.len(), 1);
+    assert!(!path.exists());
+    assert!(manager.list().unwrap().is_empty());
+    // The records never reached the store on disk
+    assert!(!sandbox.join("store").exists());
+}
@@ -0,0 +1,37 @@
+mod common;
+
+use std::env;
+use std::fs;
+use tempdir::{clean_directories, Store};
+
+#[test]
+fn meta_data_files_are_imported_into_the_index() {
+    let sandbox = common::sandbox("index-import");
+    let store = sandbox.join("store");
+    fs::create_dir_all(&store).unwrap();
+    env::set_var("TEMPDIR_STORE", &store);
+
+    let now = chrono::offset::Local::now().timestamp();
+    for name in ["first", "second"] {
+        let record = serde_json::json!({
+            "name": name,
+            "duration": "1h",
+            "created_at": now,
+            "end_time": now + 3600,
+            "path": sandbox.join(name),
+        });
+        fs::write(store.join(format!("{name}.json")), record.to_string()).unwrap();
+    }
+    fs::write(store.join("broken.json"), "{").unwrap();
+
+    let report = clean_directories().unwrap();
+
+    assert_eq!(report.errors.len(), 1);
+    assert!(store.join("broken.json").is_file());
+    assert!(!store.join("first.json").exists());
+    assert_eq!(common::meta_data_records(&store, "first").len(), 1);
+    assert_eq!(common::meta_data_records(&store, "second").len(), 1);
+    assert_eq!(Store::open().unwrap().directories().count(), 2);
+
+    fs::remove_dir_all(&sandbox).unwrap();
+}
@@ -0,0 +1,41 @@
+#![cfg(unix)]
+
+use std::env;
+use std::fs;
+use tempdir::list_directories;
+
+#[test]
+fn meta_data_next_to_the_executable_is_migrated() {
+    let data = env::temp_dir().join(format!("tempdir-legacy-store-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&data);
+    env::remove_var("TEMPDIR_STORE");
+    env::set_var("XDG_DATA_HOME", &data);
+    env::set_var("HOME", &data);
+
+    let legacy_store = env::current_exe().unwrap().parent().unwrap().join("temporary_directories");
+    let _ = fs::remove_dir_all(&legacy_store);
+    fs::create_dir_all(&legacy_store).unwrap();
+    let now = chrono::offset::Local::now().timestamp();
+    let record = serde_json::json!({
+        "name": "legacy",
+        "duration": "1h",
+        "created_at": now,
+        "end_time": now + 3600,
+        "path": data.join("legacy"),
+    });
+    fs::write(legacy_store.join("legacy.json"), record.to_string()).unwrap();
+
+    let directories = list_directories().unwrap();
+
+    assert_eq!(directories.len(), 1);
+    assert_eq!(directories[0].name(), "legacy");
+    let store = if cfg!(target_os = "macos") {
+        data.join("Library").join("Application Support").join("tempdir")
+    } else {
+        data.join("tempdir")
+    };
+    assert!(store.join("index.json").is_file());
+    assert!(!store.join("legacy.json").exists());
+    assert!(!legacy_store.exists());
+    fs::remove_dir_all(&data).unwrap();
+}
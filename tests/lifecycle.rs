@@ -0,0 +1,68 @@
+mod common;
+
+use tempdir::{clean_directories, clean_directories_with, create_temp_dir, TemporaryDirectory};
+
+#[test]
+fn expired_directory_and_meta_data_are_cleaned() {
+    let sandbox = common::sandboxed_store("lifecycle");
+
+    let tempdir = create_temp_dir("expiring", "1s").unwrap();
+    let path = tempdir.path().unwrap();
+    assert!(path.is_dir());
+    assert_eq!(common::meta_data_records(&sandbox.join("store"), "expiring").len(), 1);
+
+    let report = clean_directories_with(&common::clean_options_after(2)).unwrap();
+
+    assert!(!path.exists());
+    assert!(report.deleted.iter().any(|cleaned| cleaned.directory.name() == "expiring" && cleaned.path == path));
+    assert!(common::meta_data_records(&sandbox.join("store"), "expiring").is_empty());
+}
+
+#[test]
+fn unexpired_directory_survives_clean() {
+    let sandbox = common::sandboxed_store("lifecycle");
+
+    let tempdir = create_temp_dir("lasting", "1h").unwrap();
+    let path = tempdir.path().unwrap();
+
+    clean_directories().unwrap();
+
+    assert!(path.is_dir());
+    assert_eq!(common::meta_data_records(&sandbox.join("store"), "lasting").len(), 1);
+}
+
+#[test]
+fn guard_registers_and_removes_directory() {
+    let sandbox = common::sandboxed_store("lifecycle");
+
+    let guard = TemporaryDirectory::new("guarded".to_string(), "1h".to_string())
+        .unwrap()
+        .create_scoped()
+        .unwrap();
+    let path = guard.directory().path().unwrap();
+    std::fs::write(path.join("file"), "contents").unwrap();
+    assert_eq!(common::meta_data_records(&sandbox.join("store"), "guarded").len(), 1);
+
+    drop(guard);
+
+    assert!(!path.exists());
+    assert!(common::meta_data_records(&sandbox.join("store"), "guarded").is_empty());
+}
+
+#[test]
+fn handle_stays_usable_after_create_and_delete() {
+    let sandbox = common::sandboxed_store("lifecycle");
+
+    let mut tempdir = TemporaryDirectory::new("handle".to_string(), "1h".to_string()).unwrap();
+    let path = tempdir.create().unwrap();
+    assert_eq!(tempdir.path(), Some(path.clone()));
+    assert!(path.is_dir());
+    // Creating it again would lose track of the first directory
+    assert!(tempdir.create().is_err());
+    assert!(path.is_dir());
+
+    tempdir.delete_now().unwrap();
+    assert!(!path.exists());
+    assert!(common::meta_data_records(&sandbox.join("store"), "handle").is_empty());
+    assert_eq!(tempdir.name(), "handle");
+}
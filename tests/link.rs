@@ -0,0 +1,44 @@
+#![cfg(unix)]
+
+mod common;
+
+use std::fs;
+use tempdir::{TempDirErrors, TemporaryDirectory};
+
+fn create_linked(name: &str, link: &std::path::Path) -> Result<TemporaryDirectory, TempDirErrors> {
+    TemporaryDirectory::new(name.to_string(), "1h".to_string())
+        .unwrap()
+        .with_link(Some(link.to_path_buf()))
+        .try_create()
+}
+
+#[test]
+fn link_follows_the_newest_directory_and_goes_with_it() {
+    let sandbox = common::sandboxed_store("link");
+    let link = sandbox.join("current");
+
+    let first = create_linked("first", &link).unwrap();
+    assert_eq!(fs::read_link(&link).unwrap(), first.path().unwrap());
+
+    let second = create_linked("second", &link).unwrap();
+    assert_eq!(fs::read_link(&link).unwrap(), second.path().unwrap());
+
+    // The link belongs to the second directory now
+    TemporaryDirectory::load("first", None).unwrap().delete_now().unwrap();
+    assert_eq!(fs::read_link(&link).unwrap(), second.path().unwrap());
+
+    TemporaryDirectory::load("second", None).unwrap().delete_now().unwrap();
+    assert!(fs::symlink_metadata(&link).is_err());
+}
+
+#[test]
+fn link_never_replaces_other_files() {
+    let sandbox = common::sandboxed_store("link");
+    let occupied = sandbox.join("occupied");
+    fs::write(&occupied, "keep me").unwrap();
+
+    let created = create_linked("blocked", &occupied);
+    assert!(matches!(created, Err(TempDirErrors::LinkFailed(_))));
+    assert_eq!(fs::read_to_string(&occupied).unwrap(), "keep me");
+    assert!(!sandbox.join("blocked").exists());
+}
@@ -0,0 +1,37 @@
+mod common;
+
+use tempdir::{create_temp_dir, glob_pattern, list_directories_with, ListOptions, SortKey};
+
+/// Names of the directories `options` selects, two seconds from now
+fn names(options: ListOptions) -> Vec<String> {
+    let options = ListOptions { clock: Some(common::clock_after(2)), ..options };
+    list_directories_with(&options).unwrap().iter().map(|tempdir| tempdir.name().to_string()).collect()
+}
+
+#[test]
+fn filters_and_sorts_directories() {
+    common::sandboxed_store("list-options");
+
+    create_temp_dir("build-long", "2h").unwrap();
+    create_temp_dir("build-short", "1h").unwrap();
+    create_temp_dir("scratch", "1s").unwrap();
+
+    assert_eq!(names(ListOptions::default()), ["build-long", "build-short", "scratch"]);
+    assert_eq!(names(ListOptions { sort: SortKey::Remaining, ..ListOptions::default() }), ["scratch", "build-short", "build-long"]);
+    assert_eq!(
+        names(ListOptions { sort: SortKey::Remaining, reverse: true, ..ListOptions::default() }),
+        ["build-long", "build-short", "scratch"]
+    );
+    assert_eq!(names(ListOptions { name: Some(glob_pattern("build-*").unwrap()), ..ListOptions::default() }), ["build-long", "build-short"]);
+    assert_eq!(names(ListOptions { expired: true, ..ListOptions::default() }), ["scratch"]);
+    assert_eq!(names(ListOptions { active: true, ..ListOptions::default() }), ["build-long", "build-short"]);
+}
+
+#[test]
+fn globs_match_whole_names() {
+    let pattern = glob_pattern("tmp-?.d").unwrap();
+
+    assert!(pattern.is_match("tmp-1.d"));
+    assert!(!pattern.is_match("tmp-1xd"));
+    assert!(!pattern.is_match("my-tmp-1.d"));
+}
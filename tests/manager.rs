@@ -0,0 +1,57 @@
+mod common;
+
+use std::fs;
+use std::sync::Arc;
+use std::time::Duration;
+use tempdir::{CleanOptions, ManualClock, TempDirManager, TemporaryDirectory};
+
+#[test]
+fn managers_keep_their_directories_in_their_own_store() {
+    let sandbox = common::sandbox("manager");
+    let first = TempDirManager::new(sandbox.join("first"), CleanOptions::default());
+    let clock = Arc::new(ManualClock::default());
+    let second = TempDirManager::new(sandbox.join("second"), CleanOptions { jobs: 2, clock: Some(clock.clone()), ..CleanOptions::default() });
+
+    let scratch = TemporaryDirectory::new("scratch".to_string(), "1h".to_string()).unwrap().with_parent(Some(sandbox.clone()));
+    let created = first.create(scratch).unwrap();
+    assert!(created.path().unwrap().is_dir());
+    assert_eq!(common::meta_data_records(&sandbox.join("first"), "scratch").len(), 1);
+    assert!(second.list().unwrap().is_empty());
+
+    let extended = first.extend("scratch", None, "1h").unwrap();
+    assert!(extended.end_time() > created.end_time());
+    assert_eq!(first.get("scratch", None).unwrap().end_time(), extended.end_time());
+
+    for name in ["expired-1", "expired-2"] {
+        let expired = TemporaryDirectory::new(name.to_string(), "1s".to_string()).unwrap().with_parent(Some(sandbox.clone()));
+        second.create(expired).unwrap();
+    }
+    clock.advance(Duration::from_secs(2));
+    let report = second.clean().unwrap();
+    assert_eq!(report.deleted.len(), 2);
+    assert!(second.list().unwrap().is_empty());
+    assert_eq!(first.list().unwrap().len(), 1);
+
+    // Directories loaded through a manager update its store
+    first.get("scratch", None).unwrap().delete_now().unwrap();
+    assert!(first.list().unwrap().is_empty());
+    fs::remove_dir_all(&sandbox).unwrap();
+}
+
+#[test]
+fn iterating_a_manager_yields_its_directories() {
+    let sandbox = common::sandbox("manager-iter");
+    let manager = TempDirManager::new(sandbox.join("store"), CleanOptions::default());
+    for (name, duration) in [("b-long", "2h"), ("a-short", "1h")] {
+        let tempdir = TemporaryDirectory::new(name.to_string(), duration.to_string()).unwrap().with_parent(Some(sandbox.clone()));
+        manager.create(tempdir).unwrap();
+    }
+
+    let summary: Vec<(String, bool, bool)> = manager
+        .iter()
+        .unwrap()
+        .map(|tempdir| (tempdir.name().to_string(), tempdir.path().unwrap().is_dir(), tempdir.remaining_seconds() > 60 * 60))
+        .collect();
+    assert_eq!(summary, [("a-short".to_string(), true, false), ("b-long".to_string(), true, true)]);
+    fs::remove_dir_all(&sandbox).unwrap();
+}
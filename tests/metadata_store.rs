@@ -0,0 +1,20 @@
+mod common;
+
+use tempdir::{create_temp_dir, JsonStore, MetadataStore};
+
+#[test]
+fn json_store_loads_lists_and_removes_records() {
+    let sandbox = common::sandboxed_store("metadata-store");
+    let mut store = JsonStore;
+
+    let tempdir = create_temp_dir("backend", "1h").unwrap();
+    let loaded = store.load("backend", None).unwrap();
+    assert_eq!(loaded.path(), tempdir.path());
+    assert_eq!(store.list().unwrap().iter().filter(|tempdir| tempdir.name() == "backend").count(), 1);
+
+    store.remove(&loaded).unwrap();
+
+    assert!(store.load("backend", None).is_err());
+    assert!(common::meta_data_records(&sandbox.join("store"), "backend").is_empty());
+    assert!(tempdir.path().unwrap().is_dir());
+}
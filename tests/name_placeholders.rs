@@ -0,0 +1,43 @@
+mod common;
+
+use tempdir::{create_temp_dir, TempDirErrors};
+
+#[test]
+fn timestamp_placeholder_expands_to_numeric_suffix() {
+    let sandbox = common::sandboxed_store("placeholders");
+
+    let tempdir = create_temp_dir("run-%t", "1h").unwrap();
+    let path = tempdir.path().unwrap();
+    let name = path.file_name().unwrap().to_str().unwrap().to_string();
+
+    let suffix = name.strip_prefix("run-").unwrap();
+    assert!(!suffix.is_empty() && suffix.chars().all(|c| c.is_ascii_digit()));
+    assert_eq!(common::meta_data_records(&sandbox.join("store"), &name).len(), 1);
+    assert!(common::meta_data_records(&sandbox.join("store"), "run-%t").is_empty());
+}
+
+#[test]
+fn unknown_placeholder_is_rejected() {
+    common::sandboxed_store("placeholders");
+
+    assert!(matches!(create_temp_dir("run-%x", "1h"), Err(TempDirErrors::InvalidName)));
+}
+
+#[test]
+fn random_placeholder_generates_distinct_names() {
+    common::sandboxed_store("placeholders");
+
+    let names: Vec<String> = (0..5)
+        .map(|_| create_temp_dir("build-%r", "1h").unwrap().name().to_string())
+        .collect();
+
+    for name in &names {
+        let suffix = name.strip_prefix("build-").unwrap();
+        assert_eq!(suffix.len(), 6);
+        assert!(suffix.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit()));
+    }
+    let mut unique = names.clone();
+    unique.sort();
+    unique.dedup();
+    assert_eq!(unique.len(), names.len());
+}
@@ -0,0 +1,35 @@
+#![cfg(unix)]
+
+mod common;
+
+use std::fs;
+use std::os::unix::fs::chown;
+use tempdir::{clean_directories_with, create_temp_dir, CleanOptions, SkipReason};
+
+#[test]
+fn directories_of_other_users_are_skipped_unless_ownership_is_ignored() {
+    let sandbox = common::sandboxed_store("ownership");
+
+    let own = create_temp_dir("own", "1s").unwrap().path().unwrap();
+    let foreign = create_temp_dir("foreign", "1s").unwrap().path().unwrap();
+    // Handing a directory to another user takes root
+    if chown(&foreign, Some(4242), Some(4242)).is_err() {
+        return
+    }
+    let index_path = sandbox.join("store").join("index.json");
+    let mut index: serde_json::Map<String, serde_json::Value> = serde_json::from_str(&fs::read_to_string(&index_path).unwrap()).unwrap();
+    for record in index.values_mut().filter(|record| record["name"] == "foreign") {
+        record["uid"] = serde_json::json!(4242);
+    }
+    fs::write(&index_path, serde_json::to_string(&index).unwrap()).unwrap();
+
+    let report = clean_directories_with(&common::clean_options_after(2)).unwrap();
+    assert!(!own.exists());
+    assert!(foreign.is_dir());
+    assert!(matches!(report.skipped.as_slice(), [SkipReason::NotOwned(tempdir)] if tempdir.name() == "foreign"));
+
+    let options = CleanOptions { ignore_ownership: true, ..common::clean_options_after(2) };
+    let report = clean_directories_with(&options).unwrap();
+    assert!(!foreign.exists());
+    assert_eq!(report.deleted.len(), 1);
+}
@@ -0,0 +1,17 @@
+mod common;
+
+use tempdir::{clean_directories_with, create_temp_dir, list_directories, CleanOptions};
+
+#[test]
+fn parallel_clean_deletes_every_expired_directory() {
+    common::sandboxed_store("parallel-clean");
+
+    let paths: Vec<_> = (0..8).map(|index| create_temp_dir(&format!("parallel-{index}"), "1s").unwrap().path().unwrap()).collect();
+
+    let report = clean_directories_with(&CleanOptions { jobs: 4, ..common::clean_options_after(2) }).unwrap();
+
+    assert_eq!(report.deleted.len(), 8);
+    assert!(report.errors.is_empty());
+    assert!(paths.iter().all(|path| !path.exists()));
+    assert!(list_directories().unwrap().is_empty());
+}
@@ -0,0 +1,35 @@
+mod common;
+
+use std::fs;
+use std::sync::Arc;
+use std::time::Duration;
+use tempdir::{clean_directories_with, list_directories, CleanOptions, ManualClock, Progress, RemovalProgress, TemporaryDirectory};
+
+#[test]
+fn panic_while_cleaning_one_directory_keeps_the_others_recorded() {
+    common::sandboxed_store("parallel-clean-panic");
+    let paths: Vec<_> = ["calm-1", "calm-2", "calm-3", "panicky"]
+        .iter()
+        .map(|name| {
+            let tempdir = TemporaryDirectory::new(name.to_string(), "1h".to_string()).unwrap().with_delete_contents(true).try_create().unwrap();
+            let path = tempdir.path().unwrap();
+            fs::write(path.join("file"), "contents").unwrap();
+            path
+        })
+        .collect();
+
+    let clock = Arc::new(ManualClock::default());
+    clock.advance(Duration::from_secs(2 * 60 * 60));
+    let progress = Progress(Arc::new(|tempdir: &TemporaryDirectory, _: &RemovalProgress| {
+        assert_ne!(tempdir.name(), "panicky", "removal of panicky panics");
+    }));
+    let options = CleanOptions { jobs: 2, clock: Some(clock), progress: Some(progress), ..CleanOptions::default() };
+    let report = clean_directories_with(&options).unwrap();
+
+    assert_eq!(report.deleted.len(), 3);
+    assert_eq!(report.errors.len(), 1);
+    assert_eq!(report.errors[0].path, paths[3]);
+    assert!(paths[..3].iter().all(|path| !path.exists()));
+    let names: Vec<String> = list_directories().unwrap().iter().map(|tempdir| tempdir.name().to_string()).collect();
+    assert_eq!(names, ["panicky"]);
+}
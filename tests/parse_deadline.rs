@@ -0,0 +1,35 @@
+use chrono::{Local, NaiveDate, TimeZone};
+use tempdir::{parse_deadline, TemporaryDirectory};
+
+#[test]
+fn parses_deadlines_with_offset() {
+    assert_eq!(parse_deadline("2024-12-31T18:00:00+01:00").ok(), Some(1735664400));
+    assert_eq!(parse_deadline("2024-12-31T17:00:00Z").ok(), Some(1735664400));
+}
+
+#[test]
+fn parses_deadlines_without_offset_as_local_time() {
+    let local = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap().and_hms_opt(18, 0, 0).unwrap();
+    let expected = Local.from_local_datetime(&local).earliest().unwrap().timestamp();
+    assert_eq!(parse_deadline("2024-12-31T18:00:00").ok(), Some(expected));
+}
+
+#[test]
+fn rejects_invalid_deadlines() {
+    for until in ["tomorrow", "2024-12-31", "2024-13-01T00:00:00", ""] {
+        assert!(parse_deadline(until).is_err(), "{until} should be rejected");
+    }
+}
+
+#[test]
+fn deadline_sets_the_end_time() {
+    let until = Local::now() + chrono::Duration::hours(2);
+    let until = until.format("%Y-%m-%dT%H:%M:%S").to_string();
+
+    let tempdir = TemporaryDirectory::new_until("deadline".to_string(), until.clone()).unwrap();
+
+    assert_eq!(tempdir.end_time(), parse_deadline(&until).unwrap());
+    assert_eq!(tempdir.duration(), format!("{}s", tempdir.end_time() - tempdir.created_at()));
+    assert_eq!(tempdir.until(), Some(until.as_str()));
+    assert!(TemporaryDirectory::new_until("deadline".to_string(), "2000-01-01T00:00:00Z".to_string()).is_err());
+}
@@ -0,0 +1,200 @@
+use std::time::Duration;
+use chrono::{Local, TimeZone};
+use tempdir::{duration, parse_duration, parse_duration_string, TempDirErrors, TemporaryDirectory};
+
+#[test]
+fn parses_every_unit_spelling_and_casing() {
+    let cases: &[(&str, i64)] = &[
+        ("5s", 5),
+        ("5S", 5),
+        ("5min", 300),
+        ("5Min", 300),
+        ("5MIN", 300),
+        ("5mIn", 300),
+        ("5h", 18000),
+        ("5H", 18000),
+        ("5d", 432000),
+        ("5D", 432000),
+        ("5w", 3024000),
+        ("5W", 3024000),
+        ("5m", 13392000),
+        ("5M", 13392000),
+    ];
+
+    for (duration, seconds) in cases {
+        assert_eq!(
+            parse_duration_string(duration).ok(),
+            Some(*seconds),
+            "{duration} should be {seconds} seconds"
+        );
+    }
+}
+
+#[test]
+fn distinguishes_month_from_minute() {
+    assert_eq!(parse_duration_string("1m").ok(), Some(2678400));
+    assert_eq!(parse_duration_string("1min").ok(), Some(60));
+}
+
+#[test]
+fn rejects_unknown_units() {
+    for duration in ["5", "5mi", "5mins", "5x", "min", ""] {
+        assert!(parse_duration_string(duration).is_err(), "{duration} should be rejected");
+    }
+}
+
+#[test]
+fn parses_into_typed_duration() {
+    assert_eq!(parse_duration("90s").ok(), Some(Duration::from_secs(90)));
+    assert_eq!(parse_duration("2h").ok(), Some(Duration::from_secs(7200)));
+    assert!(parse_duration("-5s").is_err());
+}
+
+#[test]
+fn sums_compound_durations() {
+    assert_eq!(parse_duration_string("1d12h30min").ok(), Some(131400));
+    assert_eq!(parse_duration_string("2w3d").ok(), Some(1468800));
+    assert_eq!(parse_duration_string("1h30min").ok(), Some(5400));
+    assert_eq!(parse_duration_string("1H30Min").ok(), Some(5400));
+}
+
+#[test]
+fn compound_durations_accept_any_order() {
+    assert_eq!(parse_duration_string("30min1h").ok(), Some(5400));
+    assert_eq!(parse_duration_string("3d2w").ok(), parse_duration_string("2w3d").ok());
+}
+
+#[test]
+fn compound_durations_allow_whitespace_between_pairs() {
+    assert_eq!(parse_duration_string("1h 30min").ok(), Some(5400));
+    assert_eq!(parse_duration_string("  1d\t12h ").ok(), Some(129600));
+}
+
+#[test]
+fn rejects_invalid_compound_durations() {
+    for duration in ["1h30", "h30min", "1h1h", "1h 30x", "1.5h", "1h-30min", "1 h30min", "1h,30min"] {
+        assert!(parse_duration_string(duration).is_err(), "{duration} should be rejected");
+    }
+}
+
+#[test]
+fn parses_iso_8601_durations() {
+    let cases: &[(&str, i64)] = &[
+        ("P1DT2H30M", 95400),
+        ("PT90S", 90),
+        ("PT1M", 60),
+        ("P1M", 2678400),
+        ("P2W", 1209600),
+        ("P1Y", 31536000),
+        ("P1DT1S", 86401),
+    ];
+
+    for (duration, seconds) in cases {
+        assert_eq!(
+            parse_duration_string(duration).ok(),
+            Some(*seconds),
+            "{duration} should be {seconds} seconds"
+        );
+    }
+}
+
+#[test]
+fn rejects_invalid_iso_8601_durations() {
+    for duration in ["P", "PT", "P1DT", "P1H", "PT1D", "P1.5D", "P1D2H", "PT30M1H"] {
+        assert!(parse_duration_string(duration).is_err(), "{duration} should be rejected");
+    }
+}
+
+fn local_timestamp(year: i32, month: u32, day: u32) -> i64 {
+    Local.ymd(year, month, day).and_hms(12, 0, 0).timestamp()
+}
+
+#[test]
+fn months_land_on_the_same_day_of_the_month() {
+    let cases = [
+        ((2023, 2, 1), "1m", (2023, 3, 1)),
+        ((2023, 1, 15), "2m", (2023, 3, 15)),
+        ((2023, 11, 10), "P3M", (2024, 2, 10)),
+    ];
+
+    for ((year, month, day), duration, (end_year, end_month, end_day)) in cases {
+        assert_eq!(
+            duration::end_time(local_timestamp(year, month, day), duration).unwrap(),
+            local_timestamp(end_year, end_month, end_day),
+            "{duration} after {year}-{month}-{day}"
+        );
+    }
+}
+
+#[test]
+fn months_clamp_to_the_end_of_shorter_months() {
+    let start = local_timestamp(2023, 1, 31);
+    assert_eq!(duration::end_time(start, "1m").unwrap(), local_timestamp(2023, 2, 28));
+    assert_eq!(duration::end_time(start, "1m1d").unwrap(), local_timestamp(2023, 2, 28) + 86400);
+}
+
+#[test]
+fn fixed_units_are_added_as_seconds() {
+    let start = local_timestamp(2023, 1, 31);
+    assert_eq!(duration::end_time(start, "1w2h").unwrap(), start + 612000);
+    assert!(duration::end_time(start, "-1m").is_err());
+}
+
+#[test]
+fn rejects_durations_out_of_range() {
+    for duration in ["99999999999999w", "9223372036854775807s1min", "99999999999999m", "P99999999999999Y", "P1DT9223372036854775807S"] {
+        assert!(
+            matches!(parse_duration_string(duration), Err(TempDirErrors::WrongDurationString)),
+            "{duration} should be rejected"
+        );
+    }
+    assert!(duration::end_time(i64::MAX, "1s").is_err());
+}
+
+#[test]
+fn invalid_duration_is_reported_as_such() {
+    let created = TemporaryDirectory::new("invalid".to_string(), "1zz".to_string());
+    assert!(matches!(created, Err(TempDirErrors::WrongDurationString)));
+}
+
+#[test]
+fn parse_table() {
+    let cases: &[(&str, Option<i64>)] = &[
+        ("0s", Some(0)),
+        ("90s", Some(90)),
+        ("1h30min", Some(5400)),
+        (" 2d ", Some(172800)),
+        ("P1DT2H", Some(93600)),
+        ("-5m", Some(-13392000)),
+        ("-1h30min", Some(-5400)),
+        ("-PT1H", Some(-3600)),
+        ("--5m", None),
+        ("5m-", None),
+        ("-", None),
+        ("", None),
+    ];
+
+    for (input, seconds) in cases {
+        assert_eq!(duration::parse(input).ok(), *seconds, "{input:?}");
+    }
+}
+
+#[test]
+fn humanize_table() {
+    let cases: &[(i64, &str)] = &[
+        (-1, "expired"),
+        (0, "0s"),
+        (59, "59s"),
+        (60, "1min"),
+        (90, "1min 30s"),
+        (3600, "1h"),
+        (3661, "1h 1min"),
+        (86400 + 3 * 3600, "1d 3h"),
+        (604800 + 86400 + 60, "1w 1d"),
+        (2678400 + 3600, "1m"),
+    ];
+
+    for (seconds, text) in cases {
+        assert_eq!(duration::humanize(*seconds), *text, "{seconds}");
+    }
+}
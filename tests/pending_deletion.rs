@@ -0,0 +1,25 @@
+mod common;
+
+use std::fs;
+use tempdir::{clean_directories, create_temp_dir, TemporaryDirectory};
+
+#[test]
+fn directory_marked_for_deletion_is_deleted_by_next_clean() {
+    let sandbox = common::sandboxed_store("pending-deletion");
+
+    let marked = create_temp_dir("marked", "1h").unwrap().path().unwrap();
+    let kept = create_temp_dir("kept", "1h").unwrap().path().unwrap();
+    // Left behind by a removal that failed because files were in use
+    let index_path = sandbox.join("store").join("index.json");
+    let mut index: serde_json::Map<String, serde_json::Value> = serde_json::from_str(&fs::read_to_string(&index_path).unwrap()).unwrap();
+    for record in index.values_mut().filter(|record| record["name"] == "marked") {
+        record["pending_deletion"] = serde_json::json!(true);
+    }
+    fs::write(&index_path, serde_json::to_string(&index).unwrap()).unwrap();
+
+    let report = clean_directories().unwrap();
+    assert_eq!(report.deleted.len(), 1);
+    assert!(!marked.exists());
+    assert!(kept.is_dir());
+    assert!(TemporaryDirectory::load("marked", None).is_err());
+}
@@ -0,0 +1,23 @@
+mod common;
+
+use tempdir::{clean_directories_with, create_temp_dir, SkipReason, TemporaryDirectory};
+
+#[test]
+fn pinned_directory_survives_clean_until_unpinned() {
+    common::sandboxed_store("pin");
+
+    let mut tempdir = create_temp_dir("pinned", "1s").unwrap();
+    let path = tempdir.path().unwrap();
+    tempdir.set_pinned(true).unwrap();
+
+    let report = clean_directories_with(&common::clean_options_after(2)).unwrap();
+    assert!(path.is_dir());
+    assert!(matches!(report.skipped.as_slice(), [SkipReason::Pinned(tempdir)] if tempdir.name() == "pinned"));
+
+    let mut reloaded = TemporaryDirectory::load("pinned", None).unwrap();
+    assert!(reloaded.is_pinned());
+    reloaded.set_pinned(false).unwrap();
+
+    clean_directories_with(&common::clean_options_after(2)).unwrap();
+    assert!(!path.exists());
+}
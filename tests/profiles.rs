@@ -0,0 +1,20 @@
+mod common;
+
+use std::path::PathBuf;
+use tempdir::{Config, TempDirErrors};
+
+#[test]
+fn profiles_without_a_store_live_inside_the_default_store() {
+    let sandbox = common::sandboxed_store("profiles");
+    let config = Config::parse("[profiles.work]\nparent = \"/srv/work\"").unwrap();
+
+    let work = config.profile("work").unwrap();
+    assert_eq!(work.store, Some(sandbox.join("store").join("profiles").join("work")));
+    assert_eq!(work.parent, Some(PathBuf::from("/srv/work")));
+
+    let undefined = config.profile("personal").unwrap();
+    assert_eq!(undefined.store, Some(sandbox.join("store").join("profiles").join("personal")));
+    assert_eq!(undefined.parent, None);
+
+    assert!(matches!(config.profile("../elsewhere"), Err(TempDirErrors::InvalidProfile(_))));
+}
@@ -0,0 +1,41 @@
+mod common;
+
+use std::fs;
+use tempdir::{clean_directories_with, create_temp_dir, parse_size, CleanOptions};
+
+#[test]
+fn parses_sizes() {
+    let cases: &[(&str, u64)] = &[
+        ("512", 512),
+        ("1K", 1024),
+        ("10G", 10 * 1024 * 1024 * 1024),
+        ("10GB", 10 * 1024 * 1024 * 1024),
+        ("1.5MiB", 1536 * 1024),
+        ("2t", 2 * 1024u64.pow(4)),
+    ];
+
+    for (size, bytes) in cases {
+        assert_eq!(parse_size(size).ok(), Some(*bytes), "{size} should be {bytes} bytes");
+    }
+    for size in ["", "G", "10X", "1.2.3M"] {
+        assert!(parse_size(size).is_err(), "{size} should be rejected");
+    }
+}
+
+#[test]
+fn evicts_soonest_expiring_directories_over_quota() {
+    common::sandboxed_store("quota");
+
+    let soon = create_temp_dir("soon", "1h").unwrap().path().unwrap();
+    let later = create_temp_dir("later", "2h").unwrap().path().unwrap();
+    fs::write(soon.join("data"), vec![0u8; 1000]).unwrap();
+    fs::write(later.join("data"), vec![0u8; 1000]).unwrap();
+
+    let options = CleanOptions { max_total_size: Some(1500), ..CleanOptions::default() };
+    let report = clean_directories_with(&options).unwrap();
+
+    assert_eq!(report.deleted.len(), 1);
+    assert_eq!(report.deleted[0].directory.name(), "soon");
+    assert!(!soon.exists());
+    assert!(later.join("data").is_file());
+}
@@ -0,0 +1,28 @@
+mod common;
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use tempdir::TemporaryDirectory;
+
+#[test]
+fn relative_paths_resolve_against_the_base_directory() {
+    let sandbox = common::sandboxed_store("relative-base");
+    let base = sandbox.join("base");
+    fs::create_dir_all(base.join("scratch")).unwrap();
+    env::set_var("TEMPDIR_BASE", &base);
+    env::set_var("TEMPDIR_PARENT", base.join("scratch"));
+
+    let mut tempdir = TemporaryDirectory::new("relative".to_string(), "1h".to_string()).unwrap().with_relative_path(true);
+    let path = tempdir.create().unwrap();
+    assert_eq!(path, base.join("scratch").join("relative").canonicalize().unwrap());
+    let record = common::meta_data_records(&sandbox.join("store"), "relative").remove(0);
+    assert_eq!(record["path"], serde_json::json!(Path::new("scratch").join("relative")));
+
+    // Moving the base moves the directory along with it
+    let moved = sandbox.join("moved");
+    fs::create_dir_all(moved.join("scratch").join("relative")).unwrap();
+    env::set_var("TEMPDIR_BASE", &moved);
+    let loaded = TemporaryDirectory::load("relative", None).unwrap();
+    assert_eq!(loaded.path(), Some(moved.join("scratch").join("relative")));
+}
@@ -0,0 +1,19 @@
+mod common;
+
+use std::env;
+use tempdir::TemporaryDirectory;
+
+#[test]
+fn relative_paths_resolve_against_the_store_parent_without_a_base() {
+    env::remove_var("TEMPDIR_BASE");
+    let sandbox = common::sandboxed_store("relative-store-parent");
+
+    let mut tempdir = TemporaryDirectory::new("relative".to_string(), "1h".to_string()).unwrap().with_relative_path(true);
+    let path = tempdir.create().unwrap();
+    assert_eq!(path, sandbox.join("relative").canonicalize().unwrap());
+    let record = common::meta_data_records(&sandbox.join("store"), "relative").remove(0);
+    assert_eq!(record["path"], serde_json::json!("relative"));
+
+    let loaded = TemporaryDirectory::load("relative", None).unwrap();
+    assert_eq!(loaded.path(), Some(sandbox.join("relative")));
+}
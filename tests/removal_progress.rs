@@ -0,0 +1,33 @@
+mod common;
+
+use std::fs;
+use std::sync::{Arc, Mutex};
+use tempdir::{create_temp_dir, Progress, RemovalProgress, TemporaryDirectory};
+
+#[test]
+fn progress_counts_removed_files_and_bytes() {
+    common::sandboxed_store("removal-progress");
+
+    let tempdir = create_temp_dir("progress", "1h").unwrap();
+    let path = tempdir.path().unwrap();
+    fs::create_dir(path.join("nested")).unwrap();
+    fs::write(path.join("a"), [0; 100]).unwrap();
+    fs::write(path.join("b"), [0; 20]).unwrap();
+    fs::write(path.join("nested").join("c"), [0; 3]).unwrap();
+
+    let updates: Arc<Mutex<Vec<RemovalProgress>>> = Arc::default();
+    let recorded = Arc::clone(&updates);
+    let progress = Progress(Arc::new(move |tempdir: &TemporaryDirectory, removed: &RemovalProgress| {
+        assert_eq!(tempdir.name(), "progress");
+        recorded.lock().unwrap().push(*removed);
+    }));
+
+    let tempdir = TemporaryDirectory::load("progress", None).unwrap().with_delete_contents(true);
+    tempdir.delete_now_with(Some(&progress)).unwrap();
+
+    let updates = updates.lock().unwrap();
+    assert_eq!(updates.len(), 3);
+    let last = updates.last().unwrap();
+    assert_eq!((last.files, last.bytes), (3, 123));
+    assert!(!path.exists());
+}
@@ -0,0 +1,38 @@
+mod common;
+
+use std::env;
+use std::fs;
+use std::time::{Duration, UNIX_EPOCH};
+use tempdir::{clean_directories_with, Clock, CleanOptions, SkipReason, TemporaryDirectory};
+
+#[test]
+fn touched_directory_survives_clean() {
+    let sandbox = env::temp_dir().join(format!("tempdir-renew-{}", std::process::id()));
+    let store = sandbox.join("store");
+    let parent = sandbox.join("parent");
+    fs::create_dir_all(&parent).unwrap();
+    env::set_var("TEMPDIR_STORE", &store);
+    env::set_var("TEMPDIR_PARENT", &parent);
+
+    let tempdir = TemporaryDirectory::new("renewing".to_string(), "3s".to_string())
+        .unwrap()
+        .with_renew_on_access(true)
+        .try_create()
+        .unwrap();
+    let path = tempdir.path().unwrap();
+
+    // Touched after the lifetime has passed, by the clock of the clean
+    let clock = common::clock_after(4);
+    fs::write(path.join("touched"), "still in use").unwrap();
+    let touched = fs::File::options().write(true).open(path.join("touched")).unwrap();
+    touched.set_modified(UNIX_EPOCH + Duration::from_secs(clock.now() as u64)).unwrap();
+    let report = clean_directories_with(&CleanOptions { clock: Some(clock), ..CleanOptions::default() }).unwrap();
+
+    assert!(path.join("touched").is_file());
+    assert!(report
+        .skipped
+        .iter()
+        .any(|skipped| matches!(skipped, SkipReason::Renewed(tempdir) if tempdir.name() == "renewing")));
+    assert_eq!(common::meta_data_records(&store, "renewing").len(), 1);
+    fs::remove_dir_all(&sandbox).unwrap();
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use std::fs;
+use tempdir::{create_temp_dir, repair};
+
+/// The RFC 3339 timestamp `seconds` after `timestamp`
+fn shifted(timestamp: &serde_json::Value, seconds: i64) -> serde_json::Value {
+    let timestamp = chrono::DateTime::parse_from_rfc3339(timestamp.as_str().unwrap()).unwrap();
+    serde_json::json!((timestamp + chrono::Duration::seconds(seconds)).with_timezone(&chrono::Utc).to_rfc3339())
+}
+
+fn read_index(store: &std::path::Path) -> serde_json::Map<String, serde_json::Value> {
+    serde_json::from_str(&fs::read_to_string(store.join("index.json")).unwrap()).unwrap()
+}
+
+#[test]
+fn repair_drops_dangling_and_duplicate_records_only() {
+    let sandbox = common::sandboxed_store("repair");
+    let store = sandbox.join("store");
+
+    let live = create_temp_dir("live", "1h").unwrap().path().unwrap();
+    let dangling = create_temp_dir("dangling", "1h").unwrap().path().unwrap();
+    fs::remove_dir(&dangling).unwrap();
+    let backwards = create_temp_dir("backwards", "1h").unwrap().path().unwrap();
+
+    // A second record of the live directory expiring sooner, as a crash
+    // during a rename could leave behind, and a record expiring before it
+    // was created
+    let mut index = read_index(&store);
+    let (key, record) = index.iter().find(|(_, record)| record["name"] == "live").map(|(key, record)| (key.clone(), record.clone())).unwrap();
+    let mut duplicate = record.clone();
+    duplicate["end_time"] = shifted(&record["end_time"], -60);
+    index.insert(format!("{key}-copy"), duplicate);
+    for record in index.values_mut().filter(|record| record["name"] == "backwards") {
+        record["end_time"] = shifted(&record["created_at"], -1);
+    }
+    fs::write(store.join("index.json"), serde_json::to_string(&index).unwrap()).unwrap();
+    fs::write(store.join("broken.json"), "{").unwrap();
+
+    let report = repair().unwrap();
+    assert_eq!(report.removed, 1);
+    assert_eq!(report.deduplicated, 1);
+    assert_eq!(report.invalid.len(), 2, "{:?}", report.invalid);
+
+    let index = read_index(&store);
+    assert!(index.contains_key(&key));
+    assert!(!index.contains_key(&format!("{key}-copy")));
+    assert!(common::meta_data_records(&store, "dangling").is_empty());
+    // Invalid meta data is reported, never removed
+    assert_eq!(common::meta_data_records(&store, "backwards").len(), 1);
+    assert!(store.join("broken.json").is_file());
+    assert!(live.is_dir() && backwards.is_dir());
+}
@@ -0,0 +1,52 @@
+mod common;
+
+use tempdir::{cancel_expiry, clean_directories_with, TempDirErrors, TemporaryDirectory};
+
+#[test]
+fn same_named_directories_in_different_parents_coexist() {
+    let sandbox = common::sandboxed_store("same-name");
+    let store = sandbox.join("store");
+    let first = sandbox.join("first");
+    let second = sandbox.join("second");
+    std::fs::create_dir_all(&first).unwrap();
+    std::fs::create_dir_all(&second).unwrap();
+
+    for parent in [&first, &second] {
+        TemporaryDirectory::new("scratch".to_string(), "1s".to_string())
+            .unwrap()
+            .with_parent(Some(parent.clone()))
+            .try_create()
+            .unwrap();
+    }
+    assert_eq!(common::meta_data_records(&store, "scratch").len(), 2);
+    assert!(matches!(cancel_expiry("scratch", None), Err(TempDirErrors::AmbiguousName)));
+
+    clean_directories_with(&common::clean_options_after(2)).unwrap();
+
+    assert!(!first.join("scratch").exists());
+    assert!(!second.join("scratch").exists());
+    assert!(common::meta_data_records(&store, "scratch").is_empty());
+}
+
+#[test]
+fn parent_disambiguates_same_named_directories() {
+    let sandbox = common::sandboxed_store("same-name");
+    let store = sandbox.join("store");
+    let first = sandbox.join("kept-first");
+    let second = sandbox.join("kept-second");
+    std::fs::create_dir_all(&first).unwrap();
+    std::fs::create_dir_all(&second).unwrap();
+
+    for parent in [&first, &second] {
+        TemporaryDirectory::new("kept".to_string(), "1h".to_string())
+            .unwrap()
+            .with_parent(Some(parent.clone()))
+            .try_create()
+            .unwrap();
+    }
+
+    cancel_expiry("kept", Some(&first)).unwrap();
+
+    assert!(first.join("kept").is_dir());
+    assert_eq!(common::meta_data_records(&store, "kept").len(), 1);
+}
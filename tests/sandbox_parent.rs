@@ -0,0 +1,27 @@
+mod common;
+
+use std::env;
+use std::fs;
+use tempdir::{clean_directories_with, TemporaryDirectory};
+
+#[test]
+fn create_and_clean_inside_sandbox() {
+    let sandbox = env::temp_dir().join(format!("tempdir-sandbox-{}", std::process::id()));
+    let store = sandbox.join("store");
+    fs::create_dir_all(&sandbox).unwrap();
+    env::set_var("TEMPDIR_STORE", &store);
+
+    let tempdir = TemporaryDirectory::new("sandboxed".to_string(), "1s".to_string())
+        .unwrap()
+        .with_parent(Some(sandbox.clone()))
+        .try_create()
+        .unwrap();
+    assert_eq!(tempdir.path(), Some(sandbox.canonicalize().unwrap().join("sandboxed")));
+    assert_eq!(common::meta_data_records(&store, "sandboxed").len(), 1);
+
+    clean_directories_with(&common::clean_options_after(2)).unwrap();
+
+    assert!(!sandbox.join("sandboxed").exists());
+    assert!(common::meta_data_records(&store, "sandboxed").is_empty());
+    fs::remove_dir_all(&sandbox).unwrap();
+}
@@ -0,0 +1,33 @@
+mod common;
+
+use std::fs;
+use tempdir::{Store, TempDirErrors};
+
+#[test]
+fn older_records_are_upgraded_and_newer_ones_rejected() {
+    let sandbox = common::sandboxed_store("schema-version");
+    let store = sandbox.join("store");
+    fs::create_dir_all(&store).unwrap();
+
+    let record = serde_json::json!({
+        "name": "unversioned",
+        "duration": "1h",
+        "created_at": 1700000000,
+        "end_time": 1700003600,
+        "path": sandbox.join("unversioned"),
+    });
+    fs::write(store.join("index.json"), serde_json::json!({ "unversioned": record }).to_string()).unwrap();
+
+    Store::open().unwrap().save().unwrap();
+
+    let record = common::meta_data_records(&store, "unversioned").remove(0);
+    assert_eq!(record["version"], 2);
+    assert_eq!(record["created_at"], "2023-11-14T22:13:20Z");
+    assert_eq!(record["end_time"], "2023-11-14T23:13:20Z");
+
+    let mut newer = record;
+    newer["version"] = serde_json::json!(99);
+    fs::write(store.join("index.json"), serde_json::json!({ "unversioned": newer }).to_string()).unwrap();
+
+    assert!(matches!(Store::open(), Err(TempDirErrors::MetaDataReadFailed(_))));
+}
@@ -0,0 +1,243 @@
+_tempdir() {
+    local cur="${COMP_WORDS[COMP_CWORD]}" prev="${COMP_WORDS[COMP_CWORD-1]}" subcommand="" word
+    for word in "${COMP_WORDS[@]:1:COMP_CWORD-1}"; do
+        case " create clean exec daemon watch extend touch remove list status repair doctor adopt gc pin unpin keep history completions help " in *" $word "*) subcommand="$word"; break;; esac
+    done
+    if [[ -z $subcommand ]]; then
+        COMPREPLY=($(compgen -W "-q --quiet -v --verbose --log-level --store-path --profile --format --config -h --help -V --version create clean exec daemon watch extend touch remove list status repair doctor adopt gc pin unpin keep history completions help" -- "$cur"))
+        return
+    fi
+    local options
+    case "$subcommand" in
+        create)
+            case "$prev" in
+                -n|--name) COMPREPLY=(); return;;
+                --prefix) COMPREPLY=(); return;;
+                -d|--duration) COMPREPLY=(); return;;
+                --until) COMPREPLY=(); return;;
+                --on-expire) COMPREPLY=(); return;;
+                --archive-to) COMPREPLY=(); return;;
+                --link) COMPREPLY=(); return;;
+                --template) COMPREPLY=(); return;;
+                --file-max-age) COMPREPLY=(); return;;
+                --exclude) COMPREPLY=(); return;;
+                --keep-to) COMPREPLY=(); return;;
+                -p|--parent) COMPREPLY=(); return;;
+                --tag) COMPREPLY=(); return;;
+                --log-level) COMPREPLY=($(compgen -W "off error warn info debug trace" -- "$cur")); return;;
+                --store-path) COMPREPLY=(); return;;
+                --profile) COMPREPLY=(); return;;
+                --format) COMPREPLY=($(compgen -W "text json" -- "$cur")); return;;
+                --config) COMPREPLY=(); return;;
+            esac
+            options="-n --name --prefix -d --duration --until --on-expire --relative --renew-on-access --delete-contents --archive-to --trash --link --template --expand-placeholders --file-max-age --contents-only --exclude --keep-to -p --parent --tag --if-not-exists --refresh -q --quiet -v --verbose --log-level --store-path --profile --format --config -h --help";;
+        clean)
+            case "$prev" in
+                --keep-active) COMPREPLY=(); return;;
+                --max-total-size) COMPREPLY=(); return;;
+                -j|--jobs) COMPREPLY=(); return;;
+                --tag) COMPREPLY=(); return;;
+                --older-than) COMPREPLY=(); return;;
+                --name-glob) COMPREPLY=(); return;;
+                --log-level) COMPREPLY=($(compgen -W "off error warn info debug trace" -- "$cur")); return;;
+                --store-path) COMPREPLY=(); return;;
+                --profile) COMPREPLY=(); return;;
+                --format) COMPREPLY=($(compgen -W "text json" -- "$cur")); return;;
+                --config) COMPREPLY=(); return;;
+            esac
+            options="-r --recursive --follow-symlinks --dry-run --trash --ignore-ownership --keep-active --max-total-size -j --jobs --tag --older-than --name-glob -q --quiet -v --verbose --log-level --store-path --profile --format --config -h --help";;
+        exec)
+            case "$prev" in
+                -n|--name) COMPREPLY=(); return;;
+                -d|--duration) COMPREPLY=(); return;;
+                -p|--parent) COMPREPLY=(); return;;
+                --log-level) COMPREPLY=($(compgen -W "off error warn info debug trace" -- "$cur")); return;;
+                --store-path) COMPREPLY=(); return;;
+                --profile) COMPREPLY=(); return;;
+                --format) COMPREPLY=($(compgen -W "text json" -- "$cur")); return;;
+                --config) COMPREPLY=(); return;;
+            esac
+            options="-n --name -d --duration -p --parent --keep-until-expiry -q --quiet -v --verbose --log-level --store-path --profile --format --config -h --help";;
+        daemon)
+            case "$prev" in
+                -i|--interval) COMPREPLY=(); return;;
+                --notify-before) COMPREPLY=(); return;;
+                --keep-active) COMPREPLY=(); return;;
+                --min-free-space) COMPREPLY=(); return;;
+                -j|--jobs) COMPREPLY=(); return;;
+                --log-level) COMPREPLY=($(compgen -W "off error warn info debug trace" -- "$cur")); return;;
+                --store-path) COMPREPLY=(); return;;
+                --profile) COMPREPLY=(); return;;
+                --format) COMPREPLY=($(compgen -W "text json" -- "$cur")); return;;
+                --config) COMPREPLY=(); return;;
+            esac
+            options="-i --interval -r --recursive --follow-symlinks --notify-before --trash --ignore-ownership --keep-active --min-free-space -j --jobs -q --quiet -v --verbose --log-level --store-path --profile --format --config -h --help";;
+        watch)
+            case "$prev" in
+                -i|--interval) COMPREPLY=(); return;;
+                --log-level) COMPREPLY=($(compgen -W "off error warn info debug trace" -- "$cur")); return;;
+                --store-path) COMPREPLY=(); return;;
+                --profile) COMPREPLY=(); return;;
+                --format) COMPREPLY=($(compgen -W "text json" -- "$cur")); return;;
+                --config) COMPREPLY=(); return;;
+            esac
+            options="-i --interval --notify -q --quiet -v --verbose --log-level --store-path --profile --format --config -h --help";;
+        extend)
+            case "$prev" in
+                -n|--name) COMPREPLY=($(compgen -W "$(tempdir list --names 2>/dev/null)" -- "$cur")); return;;
+                -d|--duration) COMPREPLY=(); return;;
+                -p|--parent) COMPREPLY=(); return;;
+                --log-level) COMPREPLY=($(compgen -W "off error warn info debug trace" -- "$cur")); return;;
+                --store-path) COMPREPLY=(); return;;
+                --profile) COMPREPLY=(); return;;
+                --format) COMPREPLY=($(compgen -W "text json" -- "$cur")); return;;
+                --config) COMPREPLY=(); return;;
+            esac
+            options="-n --name -d --duration -p --parent -q --quiet -v --verbose --log-level --store-path --profile --format --config -h --help";;
+        touch)
+            case "$prev" in
+                -n|--name) COMPREPLY=($(compgen -W "$(tempdir list --names 2>/dev/null)" -- "$cur")); return;;
+                -p|--parent) COMPREPLY=(); return;;
+                --log-level) COMPREPLY=($(compgen -W "off error warn info debug trace" -- "$cur")); return;;
+                --store-path) COMPREPLY=(); return;;
+                --profile) COMPREPLY=(); return;;
+                --format) COMPREPLY=($(compgen -W "text json" -- "$cur")); return;;
+                --config) COMPREPLY=(); return;;
+            esac
+            options="-n --name -p --parent -q --quiet -v --verbose --log-level --store-path --profile --format --config -h --help";;
+        remove)
+            case "$prev" in
+                -n|--name) COMPREPLY=($(compgen -W "$(tempdir list --names 2>/dev/null)" -- "$cur")); return;;
+                -p|--parent) COMPREPLY=(); return;;
+                --log-level) COMPREPLY=($(compgen -W "off error warn info debug trace" -- "$cur")); return;;
+                --store-path) COMPREPLY=(); return;;
+                --profile) COMPREPLY=(); return;;
+                --format) COMPREPLY=($(compgen -W "text json" -- "$cur")); return;;
+                --config) COMPREPLY=(); return;;
+            esac
+            options="-n --name -p --parent -f --force -q --quiet -v --verbose --log-level --store-path --profile --format --config -h --help";;
+        list)
+            case "$prev" in
+                --tag) COMPREPLY=(); return;;
+                --sort) COMPREPLY=($(compgen -W "name remaining created size" -- "$cur")); return;;
+                --filter) COMPREPLY=(); return;;
+                --regex) COMPREPLY=(); return;;
+                --log-level) COMPREPLY=($(compgen -W "off error warn info debug trace" -- "$cur")); return;;
+                --store-path) COMPREPLY=(); return;;
+                --profile) COMPREPLY=(); return;;
+                --format) COMPREPLY=($(compgen -W "text json" -- "$cur")); return;;
+                --config) COMPREPLY=(); return;;
+            esac
+            options="--show-size --tag --sort --reverse --filter --regex --expired --active --names -q --quiet -v --verbose --log-level --store-path --profile --format --config -h --help";;
+        status)
+            case "$prev" in
+                -n|--name) COMPREPLY=($(compgen -W "$(tempdir list --names 2>/dev/null)" -- "$cur")); return;;
+                -p|--parent) COMPREPLY=(); return;;
+                --log-level) COMPREPLY=($(compgen -W "off error warn info debug trace" -- "$cur")); return;;
+                --store-path) COMPREPLY=(); return;;
+                --profile) COMPREPLY=(); return;;
+                --format) COMPREPLY=($(compgen -W "text json" -- "$cur")); return;;
+                --config) COMPREPLY=(); return;;
+            esac
+            options="-n --name -p --parent -q --quiet -v --verbose --log-level --store-path --profile --format --config -h --help";;
+        repair)
+            case "$prev" in
+                --log-level) COMPREPLY=($(compgen -W "off error warn info debug trace" -- "$cur")); return;;
+                --store-path) COMPREPLY=(); return;;
+                --profile) COMPREPLY=(); return;;
+                --format) COMPREPLY=($(compgen -W "text json" -- "$cur")); return;;
+                --config) COMPREPLY=(); return;;
+            esac
+            options="-q --quiet -v --verbose --log-level --store-path --profile --format --config -h --help";;
+        doctor)
+            case "$prev" in
+                --root) COMPREPLY=(); return;;
+                --log-level) COMPREPLY=($(compgen -W "off error warn info debug trace" -- "$cur")); return;;
+                --store-path) COMPREPLY=(); return;;
+                --profile) COMPREPLY=(); return;;
+                --format) COMPREPLY=($(compgen -W "text json" -- "$cur")); return;;
+                --config) COMPREPLY=(); return;;
+            esac
+            options="--root --fix -q --quiet -v --verbose --log-level --store-path --profile --format --config -h --help";;
+        adopt)
+            case "$prev" in
+                -p|--path) COMPREPLY=(); return;;
+                -d|--duration) COMPREPLY=(); return;;
+                --log-level) COMPREPLY=($(compgen -W "off error warn info debug trace" -- "$cur")); return;;
+                --store-path) COMPREPLY=(); return;;
+                --profile) COMPREPLY=(); return;;
+                --format) COMPREPLY=($(compgen -W "text json" -- "$cur")); return;;
+                --config) COMPREPLY=(); return;;
+            esac
+            options="-p --path -d --duration -q --quiet -v --verbose --log-level --store-path --profile --format --config -h --help";;
+        gc)
+            case "$prev" in
+                --root) COMPREPLY=(); return;;
+                -d|--duration) COMPREPLY=(); return;;
+                --log-level) COMPREPLY=($(compgen -W "off error warn info debug trace" -- "$cur")); return;;
+                --store-path) COMPREPLY=(); return;;
+                --profile) COMPREPLY=(); return;;
+                --format) COMPREPLY=($(compgen -W "text json" -- "$cur")); return;;
+                --config) COMPREPLY=(); return;;
+            esac
+            options="--orphans --root --adopt --delete -d --duration -q --quiet -v --verbose --log-level --store-path --profile --format --config -h --help";;
+        pin)
+            case "$prev" in
+                -n|--name) COMPREPLY=($(compgen -W "$(tempdir list --names 2>/dev/null)" -- "$cur")); return;;
+                -p|--parent) COMPREPLY=(); return;;
+                --log-level) COMPREPLY=($(compgen -W "off error warn info debug trace" -- "$cur")); return;;
+                --store-path) COMPREPLY=(); return;;
+                --profile) COMPREPLY=(); return;;
+                --format) COMPREPLY=($(compgen -W "text json" -- "$cur")); return;;
+                --config) COMPREPLY=(); return;;
+            esac
+            options="-n --name -p --parent -q --quiet -v --verbose --log-level --store-path --profile --format --config -h --help";;
+        unpin)
+            case "$prev" in
+                -n|--name) COMPREPLY=($(compgen -W "$(tempdir list --names 2>/dev/null)" -- "$cur")); return;;
+                -p|--parent) COMPREPLY=(); return;;
+                --log-level) COMPREPLY=($(compgen -W "off error warn info debug trace" -- "$cur")); return;;
+                --store-path) COMPREPLY=(); return;;
+                --profile) COMPREPLY=(); return;;
+                --format) COMPREPLY=($(compgen -W "text json" -- "$cur")); return;;
+                --config) COMPREPLY=(); return;;
+            esac
+            options="-n --name -p --parent -q --quiet -v --verbose --log-level --store-path --profile --format --config -h --help";;
+        keep)
+            case "$prev" in
+                -n|--name) COMPREPLY=($(compgen -W "$(tempdir list --names 2>/dev/null)" -- "$cur")); return;;
+                -p|--parent) COMPREPLY=(); return;;
+                --log-level) COMPREPLY=($(compgen -W "off error warn info debug trace" -- "$cur")); return;;
+                --store-path) COMPREPLY=(); return;;
+                --profile) COMPREPLY=(); return;;
+                --format) COMPREPLY=($(compgen -W "text json" -- "$cur")); return;;
+                --config) COMPREPLY=(); return;;
+            esac
+            options="-n --name -p --parent -q --quiet -v --verbose --log-level --store-path --profile --format --config -h --help";;
+        history)
+            case "$prev" in
+                -n|--name) COMPREPLY=($(compgen -W "$(tempdir list --names 2>/dev/null)" -- "$cur")); return;;
+                --log-level) COMPREPLY=($(compgen -W "off error warn info debug trace" -- "$cur")); return;;
+                --store-path) COMPREPLY=(); return;;
+                --profile) COMPREPLY=(); return;;
+                --format) COMPREPLY=($(compgen -W "text json" -- "$cur")); return;;
+                --config) COMPREPLY=(); return;;
+            esac
+            options="-n --name -q --quiet -v --verbose --log-level --store-path --profile --format --config -h --help";;
+        completions)
+            case "$prev" in
+                --log-level) COMPREPLY=($(compgen -W "off error warn info debug trace" -- "$cur")); return;;
+                --store-path) COMPREPLY=(); return;;
+                --profile) COMPREPLY=(); return;;
+                --format) COMPREPLY=($(compgen -W "text json" -- "$cur")); return;;
+                --config) COMPREPLY=(); return;;
+            esac
+            options="-q --quiet -v --verbose --log-level --store-path --profile --format --config -h --help";;
+        help)
+            case "$prev" in
+            esac
+            options="";;
+    esac
+    COMPREPLY=($(compgen -W "$options" -- "$cur"))
+}
+complete -o default -F _tempdir tempdir
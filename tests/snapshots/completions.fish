@@ -0,0 +1,263 @@
+complete -c tempdir -n '__fish_use_subcommand' -s q -l quiet -d 'Don\'t log anything, not even errors'
+complete -c tempdir -n '__fish_use_subcommand' -s v -l verbose -d 'Log more: -v for progress, -vv for details, -vvv for everything. Only warnings and errors are logged by default'
+complete -c tempdir -n '__fish_use_subcommand' -l log-level -r -f -a 'off error warn info debug trace' -d 'Log messages of this level and above, overrides -q and -v'
+complete -c tempdir -n '__fish_use_subcommand' -l store-path -r -d 'Directory to keep the meta data in, overrides TEMPDIR_STORE and the config file'
+complete -c tempdir -n '__fish_use_subcommand' -l profile -r -d 'Use the store and directories of this profile of the config file. Profiles not in the config file keep their meta data in profiles/<name> inside the default store'
+complete -c tempdir -n '__fish_use_subcommand' -l format -r -f -a 'text json' -d 'Output format of list, status, clean and history'
+complete -c tempdir -n '__fish_use_subcommand' -l config -r -d 'Config file to use instead of ~/.config/tempdir/config.toml'
+complete -c tempdir -n '__fish_use_subcommand' -s h -l help -d 'Print help information (use `--help` for more detail)'
+complete -c tempdir -n '__fish_use_subcommand' -s V -l version -d 'Print version information'
+complete -c tempdir -n '__fish_use_subcommand' -f -a create -d ''
+complete -c tempdir -n '__fish_use_subcommand' -f -a clean -d ''
+complete -c tempdir -n '__fish_use_subcommand' -f -a exec -d 'Run a command inside a new temporary directory, which is deleted once the command exits'
+complete -c tempdir -n '__fish_use_subcommand' -f -a daemon -d 'Keep running and clean expired directories periodically'
+complete -c tempdir -n '__fish_use_subcommand' -f -a watch -d 'Keep running and drop the meta data of tracked directories as soon as they are removed by hand. No directories are deleted'
+complete -c tempdir -n '__fish_use_subcommand' -f -a extend -d 'Extend the lifetime of a temporary directory'
+complete -c tempdir -n '__fish_use_subcommand' -f -a touch -d 'Restart the lifetime of a temporary directory from now'
+complete -c tempdir -n '__fish_use_subcommand' -f -a remove -d 'Delete a temporary directory and its meta data before it expires'
+complete -c tempdir -n '__fish_use_subcommand' -f -a list -d 'List all temporary directories with their remaining lifetime'
+complete -c tempdir -n '__fish_use_subcommand' -f -a status -d 'Show the details of one temporary directory. Exits with 3 when it has expired and 4 when it isn\'t tracked'
+complete -c tempdir -n '__fish_use_subcommand' -f -a repair -d 'Remove meta data of directories that no longer exist and report invalid meta data. No directories are deleted'
+complete -c tempdir -n '__fish_use_subcommand' -f -a doctor -d 'Check the meta data store for invalid records, missing directories, directories outside the allowed roots, duplicates and clock anomalies. Exits with 5 when problems remain'
+complete -c tempdir -n '__fish_use_subcommand' -f -a adopt -d 'Start tracking an existing directory, so it expires like one created by tempdir. Nothing is created on disk'
+complete -c tempdir -n '__fish_use_subcommand' -f -a gc -d 'Remove meta data of directories that no longer exist, and optionally adopt or delete directories that have no meta data'
+complete -c tempdir -n '__fish_use_subcommand' -f -a pin -d 'Exclude a temporary directory from cleaning until it is unpinned'
+complete -c tempdir -n '__fish_use_subcommand' -f -a unpin -d 'Let a pinned temporary directory expire again'
+complete -c tempdir -n '__fish_use_subcommand' -f -a keep -d 'Keep a temporary directory, it will no longer expire'
+complete -c tempdir -n '__fish_use_subcommand' -f -a history -d 'Show when temporary directories were created, extended and deleted, and by whom, oldest first'
+complete -c tempdir -n '__fish_use_subcommand' -f -a completions -d 'Print a completion script for a shell. Source it from the shell config, e.g. `source <(tempdir completions bash)` in ~/.bashrc'
+complete -c tempdir -n '__fish_use_subcommand' -f -a help -d 'Print this message or the help of the given subcommand(s)'
+complete -c tempdir -n '__fish_seen_subcommand_from create' -s n -l name -r -d 'Name of the tempory folder to create, a random name is generated when it is left out. Placeholders: %d date, %t timestamp, %pid process id, %r random characters'
+complete -c tempdir -n '__fish_seen_subcommand_from create' -l prefix -r -d 'Start of the generated random name'
+complete -c tempdir -n '__fish_seen_subcommand_from create' -s d -l duration -r -d 'Duration the directory will live, defaults to the `default_duration` of the config file. Examples: 1d, 4w, 8m, 1h30min, P1DT2H'
+complete -c tempdir -n '__fish_seen_subcommand_from create' -l until -r -d 'Expire at this time instead of after a duration, as RFC 3339 with or without offset. Example: 2024-12-31T18:00:00'
+complete -c tempdir -n '__fish_seen_subcommand_from create' -l on-expire -r -d 'Command to run before the directory is deleted on expiry, through `sh -c` with the directory path as $1, e.g. \'cp -r "$1" /srv/backup\''
+complete -c tempdir -n '__fish_seen_subcommand_from create' -l relative -d 'Store the path relative to the base directory (TEMPDIR_BASE, or the directory containing the meta data store)'
+complete -c tempdir -n '__fish_seen_subcommand_from create' -l renew-on-access -d 'Restart the lifetime whenever the directory contents are modified'
+complete -c tempdir -n '__fish_seen_subcommand_from create' -l delete-contents -d 'Delete the directory on expiry even if it still has contents'
+complete -c tempdir -n '__fish_seen_subcommand_from create' -l archive-to -r -d 'Compress the contents into a timestamped .tar.gz in this directory before deleting it on expiry'
+complete -c tempdir -n '__fish_seen_subcommand_from create' -l trash -d 'Move the directory to the trash on expiry instead of deleting it'
+complete -c tempdir -n '__fish_seen_subcommand_from create' -l link -r -d 'Place a symlink to the new temporary folder here, replacing a symlink to a previous one. It is removed along with the folder'
+complete -c tempdir -n '__fish_seen_subcommand_from create' -l template -r -d 'Copy this directory tree into the new temporary folder'
+complete -c tempdir -n '__fish_seen_subcommand_from create' -l expand-placeholders -d 'Replace {{name}} and {{date}} in the contents of text files copied from the template'
+complete -c tempdir -n '__fish_seen_subcommand_from create' -l file-max-age -r -d 'Remove files inside the directory on every clean once they weren\'t modified for this long. Examples: 7d, 12h'
+complete -c tempdir -n '__fish_seen_subcommand_from create' -l contents-only -d 'Only delete the contents on expiry, keeping the directory and restarting its lifetime'
+complete -c tempdir -n '__fish_seen_subcommand_from create' -l exclude -r -d 'Keep entries matching this glob on expiry, deleting only the rest, can be given several times. Examples: \'*.log\', \'results/**\''
+complete -c tempdir -n '__fish_seen_subcommand_from create' -l keep-to -r -d 'Move the entries kept by --exclude into this directory on expiry and delete the temporary folder'
+complete -c tempdir -n '__fish_seen_subcommand_from create' -s p -l parent -r -d 'Directory to create the temporary folder in, created if missing. Defaults to TEMPDIR_PARENT, the config file, or the current directory'
+complete -c tempdir -n '__fish_seen_subcommand_from create' -l tag -r -d 'Label to filter `list` and `clean` by, can be given several times'
+complete -c tempdir -n '__fish_seen_subcommand_from create' -l if-not-exists -d 'Succeed with the path of the folder when it already exists, tracking it if it isn\'t tracked yet, instead of failing'
+complete -c tempdir -n '__fish_seen_subcommand_from create' -l refresh -d 'Restart the lifetime of a folder that already exists'
+complete -c tempdir -n '__fish_seen_subcommand_from create' -s q -l quiet -d 'Don\'t log anything, not even errors'
+complete -c tempdir -n '__fish_seen_subcommand_from create' -s v -l verbose -d 'Log more: -v for progress, -vv for details, -vvv for everything. Only warnings and errors are logged by default'
+complete -c tempdir -n '__fish_seen_subcommand_from create' -l log-level -r -f -a 'off error warn info debug trace' -d 'Log messages of this level and above, overrides -q and -v'
+complete -c tempdir -n '__fish_seen_subcommand_from create' -l store-path -r -d 'Directory to keep the meta data in, overrides TEMPDIR_STORE and the config file'
+complete -c tempdir -n '__fish_seen_subcommand_from create' -l profile -r -d 'Use the store and directories of this profile of the config file. Profiles not in the config file keep their meta data in profiles/<name> inside the default store'
+complete -c tempdir -n '__fish_seen_subcommand_from create' -l format -r -f -a 'text json' -d 'Output format of list, status, clean and history'
+complete -c tempdir -n '__fish_seen_subcommand_from create' -l config -r -d 'Config file to use instead of ~/.config/tempdir/config.toml'
+complete -c tempdir -n '__fish_seen_subcommand_from create' -s h -l help -d 'Print help information (use `--help` for more detail)'
+complete -c tempdir -n '__fish_seen_subcommand_from clean' -s r -l recursive -d 'Also delete expired directories that still have contents'
+complete -c tempdir -n '__fish_seen_subcommand_from clean' -l follow-symlinks -d 'Delete the contents of symlinked directories when deleting recursively, instead of only removing the symlinks'
+complete -c tempdir -n '__fish_seen_subcommand_from clean' -l dry-run -d 'Only list the directories that would be deleted, with their size and expiry time'
+complete -c tempdir -n '__fish_seen_subcommand_from clean' -l trash -d 'Move expired directories to the trash instead of deleting them'
+complete -c tempdir -n '__fish_seen_subcommand_from clean' -l ignore-ownership -d 'Also delete expired directories that belong to other users'
+complete -c tempdir -n '__fish_seen_subcommand_from clean' -l keep-active -r -d 'Extend expired directories modified within this window instead of deleting them. Examples: 30min, 2h'
+complete -c tempdir -n '__fish_seen_subcommand_from clean' -l max-total-size -r -d 'Also delete directories that haven\'t expired yet, soonest expiring first, until all of them together use at most this much. Examples: 500M, 10G'
+complete -c tempdir -n '__fish_seen_subcommand_from clean' -s j -l jobs -r -d 'Number of directories to delete at the same time'
+complete -c tempdir -n '__fish_seen_subcommand_from clean' -l tag -r -d 'Only clean directories with this tag, can be given several times'
+complete -c tempdir -n '__fish_seen_subcommand_from clean' -l older-than -r -d 'Only delete directories that expired at least this long ago. Examples: 30d, 12h'
+complete -c tempdir -n '__fish_seen_subcommand_from clean' -l name-glob -r -d 'Only clean directories whose name matches this glob, where * matches any characters and ? a single one. Example: \'ci-*\''
+complete -c tempdir -n '__fish_seen_subcommand_from clean' -s q -l quiet -d 'Don\'t log anything, not even errors'
+complete -c tempdir -n '__fish_seen_subcommand_from clean' -s v -l verbose -d 'Log more: -v for progress, -vv for details, -vvv for everything. Only warnings and errors are logged by default'
+complete -c tempdir -n '__fish_seen_subcommand_from clean' -l log-level -r -f -a 'off error warn info debug trace' -d 'Log messages of this level and above, overrides -q and -v'
+complete -c tempdir -n '__fish_seen_subcommand_from clean' -l store-path -r -d 'Directory to keep the meta data in, overrides TEMPDIR_STORE and the config file'
+complete -c tempdir -n '__fish_seen_subcommand_from clean' -l profile -r -d 'Use the store and directories of this profile of the config file. Profiles not in the config file keep their meta data in profiles/<name> inside the default store'
+complete -c tempdir -n '__fish_seen_subcommand_from clean' -l format -r -f -a 'text json' -d 'Output format of list, status, clean and history'
+complete -c tempdir -n '__fish_seen_subcommand_from clean' -l config -r -d 'Config file to use instead of ~/.config/tempdir/config.toml'
+complete -c tempdir -n '__fish_seen_subcommand_from clean' -s h -l help -d 'Print help information (use `--help` for more detail)'
+complete -c tempdir -n '__fish_seen_subcommand_from exec' -s n -l name -r -d 'Name of the temporary folder to create'
+complete -c tempdir -n '__fish_seen_subcommand_from exec' -s d -l duration -r -d 'Duration the directory will live, defaults to the `default_duration` of the config file. Examples: 1d, 4w, 8m, 1h30min, P1DT2H'
+complete -c tempdir -n '__fish_seen_subcommand_from exec' -s p -l parent -r -d 'Directory to create the temporary folder in, created if missing. Defaults to TEMPDIR_PARENT, the config file, or the current directory'
+complete -c tempdir -n '__fish_seen_subcommand_from exec' -l keep-until-expiry -d 'Leave the directory in place until it expires instead of deleting it when the command exits'
+complete -c tempdir -n '__fish_seen_subcommand_from exec' -s q -l quiet -d 'Don\'t log anything, not even errors'
+complete -c tempdir -n '__fish_seen_subcommand_from exec' -s v -l verbose -d 'Log more: -v for progress, -vv for details, -vvv for everything. Only warnings and errors are logged by default'
+complete -c tempdir -n '__fish_seen_subcommand_from exec' -l log-level -r -f -a 'off error warn info debug trace' -d 'Log messages of this level and above, overrides -q and -v'
+complete -c tempdir -n '__fish_seen_subcommand_from exec' -l store-path -r -d 'Directory to keep the meta data in, overrides TEMPDIR_STORE and the config file'
+complete -c tempdir -n '__fish_seen_subcommand_from exec' -l profile -r -d 'Use the store and directories of this profile of the config file. Profiles not in the config file keep their meta data in profiles/<name> inside the default store'
+complete -c tempdir -n '__fish_seen_subcommand_from exec' -l format -r -f -a 'text json' -d 'Output format of list, status, clean and history'
+complete -c tempdir -n '__fish_seen_subcommand_from exec' -l config -r -d 'Config file to use instead of ~/.config/tempdir/config.toml'
+complete -c tempdir -n '__fish_seen_subcommand_from exec' -s h -l help -d 'Print help information (use `--help` for more detail)'
+complete -c tempdir -n '__fish_seen_subcommand_from daemon' -s i -l interval -r -d 'Time between two clean passes. Examples: 30s, 5min, 1h'
+complete -c tempdir -n '__fish_seen_subcommand_from daemon' -s r -l recursive -d 'Also delete expired directories that still have contents'
+complete -c tempdir -n '__fish_seen_subcommand_from daemon' -l follow-symlinks -d 'Delete the contents of symlinked directories when deleting recursively, instead of only removing the symlinks'
+complete -c tempdir -n '__fish_seen_subcommand_from daemon' -l notify-before -r -d 'Notify this long before a directory expires, so it can still be extended. Examples: 10min, 1h'
+complete -c tempdir -n '__fish_seen_subcommand_from daemon' -l trash -d 'Move expired directories to the trash instead of deleting them'
+complete -c tempdir -n '__fish_seen_subcommand_from daemon' -l ignore-ownership -d 'Also delete expired directories that belong to other users'
+complete -c tempdir -n '__fish_seen_subcommand_from daemon' -l keep-active -r -d 'Extend expired directories modified within this window instead of deleting them. Examples: 30min, 2h'
+complete -c tempdir -n '__fish_seen_subcommand_from daemon' -l min-free-space -r -d 'Also delete directories that haven\'t expired yet, soonest expiring first, while their file system has less than this available. Examples: 1G, 500M'
+complete -c tempdir -n '__fish_seen_subcommand_from daemon' -s j -l jobs -r -d 'Number of directories to delete at the same time'
+complete -c tempdir -n '__fish_seen_subcommand_from daemon' -s q -l quiet -d 'Don\'t log anything, not even errors'
+complete -c tempdir -n '__fish_seen_subcommand_from daemon' -s v -l verbose -d 'Log more: -v for progress, -vv for details, -vvv for everything. Only warnings and errors are logged by default'
+complete -c tempdir -n '__fish_seen_subcommand_from daemon' -l log-level -r -f -a 'off error warn info debug trace' -d 'Log messages of this level and above, overrides -q and -v'
+complete -c tempdir -n '__fish_seen_subcommand_from daemon' -l store-path -r -d 'Directory to keep the meta data in, overrides TEMPDIR_STORE and the config file'
+complete -c tempdir -n '__fish_seen_subcommand_from daemon' -l profile -r -d 'Use the store and directories of this profile of the config file. Profiles not in the config file keep their meta data in profiles/<name> inside the default store'
+complete -c tempdir -n '__fish_seen_subcommand_from daemon' -l format -r -f -a 'text json' -d 'Output format of list, status, clean and history'
+complete -c tempdir -n '__fish_seen_subcommand_from daemon' -l config -r -d 'Config file to use instead of ~/.config/tempdir/config.toml'
+complete -c tempdir -n '__fish_seen_subcommand_from daemon' -s h -l help -d 'Print help information (use `--help` for more detail)'
+complete -c tempdir -n '__fish_seen_subcommand_from watch' -s i -l interval -r -d 'Time between two checks for removed directories. Examples: 30s, 5min, 1h'
+complete -c tempdir -n '__fish_seen_subcommand_from watch' -l notify -d 'Wait for file system notifications of removals (inotify on Linux) instead of checking every interval. Falls back to checking when they are unavailable'
+complete -c tempdir -n '__fish_seen_subcommand_from watch' -s q -l quiet -d 'Don\'t log anything, not even errors'
+complete -c tempdir -n '__fish_seen_subcommand_from watch' -s v -l verbose -d 'Log more: -v for progress, -vv for details, -vvv for everything. Only warnings and errors are logged by default'
+complete -c tempdir -n '__fish_seen_subcommand_from watch' -l log-level -r -f -a 'off error warn info debug trace' -d 'Log messages of this level and above, overrides -q and -v'
+complete -c tempdir -n '__fish_seen_subcommand_from watch' -l store-path -r -d 'Directory to keep the meta data in, overrides TEMPDIR_STORE and the config file'
+complete -c tempdir -n '__fish_seen_subcommand_from watch' -l profile -r -d 'Use the store and directories of this profile of the config file. Profiles not in the config file keep their meta data in profiles/<name> inside the default store'
+complete -c tempdir -n '__fish_seen_subcommand_from watch' -l format -r -f -a 'text json' -d 'Output format of list, status, clean and history'
+complete -c tempdir -n '__fish_seen_subcommand_from watch' -l config -r -d 'Config file to use instead of ~/.config/tempdir/config.toml'
+complete -c tempdir -n '__fish_seen_subcommand_from watch' -s h -l help -d 'Print help information (use `--help` for more detail)'
+complete -c tempdir -n '__fish_seen_subcommand_from extend' -s n -l name -r -f -a '(tempdir list --names 2>/dev/null)' -d 'Name of the temporary folder to extend'
+complete -c tempdir -n '__fish_seen_subcommand_from extend' -s d -l duration -r -d 'Duration to add to the lifetime. Examples: 1d, 4w, 8m, 1h30min, P1DT2H'
+complete -c tempdir -n '__fish_seen_subcommand_from extend' -s p -l parent -r -d 'Parent directory of the temporary folder, needed when several folders with the same name are tracked'
+complete -c tempdir -n '__fish_seen_subcommand_from extend' -s q -l quiet -d 'Don\'t log anything, not even errors'
+complete -c tempdir -n '__fish_seen_subcommand_from extend' -s v -l verbose -d 'Log more: -v for progress, -vv for details, -vvv for everything. Only warnings and errors are logged by default'
+complete -c tempdir -n '__fish_seen_subcommand_from extend' -l log-level -r -f -a 'off error warn info debug trace' -d 'Log messages of this level and above, overrides -q and -v'
+complete -c tempdir -n '__fish_seen_subcommand_from extend' -l store-path -r -d 'Directory to keep the meta data in, overrides TEMPDIR_STORE and the config file'
+complete -c tempdir -n '__fish_seen_subcommand_from extend' -l profile -r -d 'Use the store and directories of this profile of the config file. Profiles not in the config file keep their meta data in profiles/<name> inside the default store'
+complete -c tempdir -n '__fish_seen_subcommand_from extend' -l format -r -f -a 'text json' -d 'Output format of list, status, clean and history'
+complete -c tempdir -n '__fish_seen_subcommand_from extend' -l config -r -d 'Config file to use instead of ~/.config/tempdir/config.toml'
+complete -c tempdir -n '__fish_seen_subcommand_from extend' -s h -l help -d 'Print help information (use `--help` for more detail)'
+complete -c tempdir -n '__fish_seen_subcommand_from touch' -s n -l name -r -f -a '(tempdir list --names 2>/dev/null)' -d 'Name of the temporary folder to touch'
+complete -c tempdir -n '__fish_seen_subcommand_from touch' -s p -l parent -r -d 'Parent directory of the temporary folder, needed when several folders with the same name are tracked'
+complete -c tempdir -n '__fish_seen_subcommand_from touch' -s q -l quiet -d 'Don\'t log anything, not even errors'
+complete -c tempdir -n '__fish_seen_subcommand_from touch' -s v -l verbose -d 'Log more: -v for progress, -vv for details, -vvv for everything. Only warnings and errors are logged by default'
+complete -c tempdir -n '__fish_seen_subcommand_from touch' -l log-level -r -f -a 'off error warn info debug trace' -d 'Log messages of this level and above, overrides -q and -v'
+complete -c tempdir -n '__fish_seen_subcommand_from touch' -l store-path -r -d 'Directory to keep the meta data in, overrides TEMPDIR_STORE and the config file'
+complete -c tempdir -n '__fish_seen_subcommand_from touch' -l profile -r -d 'Use the store and directories of this profile of the config file. Profiles not in the config file keep their meta data in profiles/<name> inside the default store'
+complete -c tempdir -n '__fish_seen_subcommand_from touch' -l format -r -f -a 'text json' -d 'Output format of list, status, clean and history'
+complete -c tempdir -n '__fish_seen_subcommand_from touch' -l config -r -d 'Config file to use instead of ~/.config/tempdir/config.toml'
+complete -c tempdir -n '__fish_seen_subcommand_from touch' -s h -l help -d 'Print help information (use `--help` for more detail)'
+complete -c tempdir -n '__fish_seen_subcommand_from remove' -s n -l name -r -f -a '(tempdir list --names 2>/dev/null)' -d 'Name of the temporary folder to remove'
+complete -c tempdir -n '__fish_seen_subcommand_from remove' -s p -l parent -r -d 'Parent directory of the temporary folder, needed when several folders with the same name are tracked'
+complete -c tempdir -n '__fish_seen_subcommand_from remove' -s f -l force -d 'Don\'t ask for confirmation'
+complete -c tempdir -n '__fish_seen_subcommand_from remove' -s q -l quiet -d 'Don\'t log anything, not even errors'
+complete -c tempdir -n '__fish_seen_subcommand_from remove' -s v -l verbose -d 'Log more: -v for progress, -vv for details, -vvv for everything. Only warnings and errors are logged by default'
+complete -c tempdir -n '__fish_seen_subcommand_from remove' -l log-level -r -f -a 'off error warn info debug trace' -d 'Log messages of this level and above, overrides -q and -v'
+complete -c tempdir -n '__fish_seen_subcommand_from remove' -l store-path -r -d 'Directory to keep the meta data in, overrides TEMPDIR_STORE and the config file'
+complete -c tempdir -n '__fish_seen_subcommand_from remove' -l profile -r -d 'Use the store and directories of this profile of the config file. Profiles not in the config file keep their meta data in profiles/<name> inside the default store'
+complete -c tempdir -n '__fish_seen_subcommand_from remove' -l format -r -f -a 'text json' -d 'Output format of list, status, clean and history'
+complete -c tempdir -n '__fish_seen_subcommand_from remove' -l config -r -d 'Config file to use instead of ~/.config/tempdir/config.toml'
+complete -c tempdir -n '__fish_seen_subcommand_from remove' -s h -l help -d 'Print help information (use `--help` for more detail)'
+complete -c tempdir -n '__fish_seen_subcommand_from list' -l show-size -d 'Show the disk space each directory uses'
+complete -c tempdir -n '__fish_seen_subcommand_from list' -l tag -r -d 'Only list directories with this tag, can be given several times'
+complete -c tempdir -n '__fish_seen_subcommand_from list' -l sort -r -f -a 'name remaining created size' -d 'Order to list directories in'
+complete -c tempdir -n '__fish_seen_subcommand_from list' -l reverse -d 'Reverse the order'
+complete -c tempdir -n '__fish_seen_subcommand_from list' -l filter -r -d 'Only list directories whose name matches this glob, e.g. "build-*"'
+complete -c tempdir -n '__fish_seen_subcommand_from list' -l regex -r -d 'Only list directories whose name matches this regular expression'
+complete -c tempdir -n '__fish_seen_subcommand_from list' -l expired -d 'Only list expired directories'
+complete -c tempdir -n '__fish_seen_subcommand_from list' -l active -d 'Only list directories that haven\'t expired yet'
+complete -c tempdir -n '__fish_seen_subcommand_from list' -l names -d 'Only print the names, one per line, for scripts and shell completion'
+complete -c tempdir -n '__fish_seen_subcommand_from list' -s q -l quiet -d 'Don\'t log anything, not even errors'
+complete -c tempdir -n '__fish_seen_subcommand_from list' -s v -l verbose -d 'Log more: -v for progress, -vv for details, -vvv for everything. Only warnings and errors are logged by default'
+complete -c tempdir -n '__fish_seen_subcommand_from list' -l log-level -r -f -a 'off error warn info debug trace' -d 'Log messages of this level and above, overrides -q and -v'
+complete -c tempdir -n '__fish_seen_subcommand_from list' -l store-path -r -d 'Directory to keep the meta data in, overrides TEMPDIR_STORE and the config file'
+complete -c tempdir -n '__fish_seen_subcommand_from list' -l profile -r -d 'Use the store and directories of this profile of the config file. Profiles not in the config file keep their meta data in profiles/<name> inside the default store'
+complete -c tempdir -n '__fish_seen_subcommand_from list' -l format -r -f -a 'text json' -d 'Output format of list, status, clean and history'
+complete -c tempdir -n '__fish_seen_subcommand_from list' -l config -r -d 'Config file to use instead of ~/.config/tempdir/config.toml'
+complete -c tempdir -n '__fish_seen_subcommand_from list' -s h -l help -d 'Print help information (use `--help` for more detail)'
+complete -c tempdir -n '__fish_seen_subcommand_from status' -s n -l name -r -f -a '(tempdir list --names 2>/dev/null)' -d 'Name of the temporary folder to show'
+complete -c tempdir -n '__fish_seen_subcommand_from status' -s p -l parent -r -d 'Parent directory of the temporary folder, needed when several folders with the same name are tracked'
+complete -c tempdir -n '__fish_seen_subcommand_from status' -s q -l quiet -d 'Don\'t log anything, not even errors'
+complete -c tempdir -n '__fish_seen_subcommand_from status' -s v -l verbose -d 'Log more: -v for progress, -vv for details, -vvv for everything. Only warnings and errors are logged by default'
+complete -c tempdir -n '__fish_seen_subcommand_from status' -l log-level -r -f -a 'off error warn info debug trace' -d 'Log messages of this level and above, overrides -q and -v'
+complete -c tempdir -n '__fish_seen_subcommand_from status' -l store-path -r -d 'Directory to keep the meta data in, overrides TEMPDIR_STORE and the config file'
+complete -c tempdir -n '__fish_seen_subcommand_from status' -l profile -r -d 'Use the store and directories of this profile of the config file. Profiles not in the config file keep their meta data in profiles/<name> inside the default store'
+complete -c tempdir -n '__fish_seen_subcommand_from status' -l format -r -f -a 'text json' -d 'Output format of list, status, clean and history'
+complete -c tempdir -n '__fish_seen_subcommand_from status' -l config -r -d 'Config file to use instead of ~/.config/tempdir/config.toml'
+complete -c tempdir -n '__fish_seen_subcommand_from status' -s h -l help -d 'Print help information (use `--help` for more detail)'
+complete -c tempdir -n '__fish_seen_subcommand_from repair' -s q -l quiet -d 'Don\'t log anything, not even errors'
+complete -c tempdir -n '__fish_seen_subcommand_from repair' -s v -l verbose -d 'Log more: -v for progress, -vv for details, -vvv for everything. Only warnings and errors are logged by default'
+complete -c tempdir -n '__fish_seen_subcommand_from repair' -l log-level -r -f -a 'off error warn info debug trace' -d 'Log messages of this level and above, overrides -q and -v'
+complete -c tempdir -n '__fish_seen_subcommand_from repair' -l store-path -r -d 'Directory to keep the meta data in, overrides TEMPDIR_STORE and the config file'
+complete -c tempdir -n '__fish_seen_subcommand_from repair' -l profile -r -d 'Use the store and directories of this profile of the config file. Profiles not in the config file keep their meta data in profiles/<name> inside the default store'
+complete -c tempdir -n '__fish_seen_subcommand_from repair' -l format -r -f -a 'text json' -d 'Output format of list, status, clean and history'
+complete -c tempdir -n '__fish_seen_subcommand_from repair' -l config -r -d 'Config file to use instead of ~/.config/tempdir/config.toml'
+complete -c tempdir -n '__fish_seen_subcommand_from repair' -s h -l help -d 'Print help information (use `--help` for more detail)'
+complete -c tempdir -n '__fish_seen_subcommand_from doctor' -l root -r -d 'Directory tracked directories have to lie in. Defaults to the configured roots, or else the creation parent and base directory'
+complete -c tempdir -n '__fish_seen_subcommand_from doctor' -l fix -d 'Remove records of missing directories and duplicate records, like `repair`. Other problems are only reported'
+complete -c tempdir -n '__fish_seen_subcommand_from doctor' -s q -l quiet -d 'Don\'t log anything, not even errors'
+complete -c tempdir -n '__fish_seen_subcommand_from doctor' -s v -l verbose -d 'Log more: -v for progress, -vv for details, -vvv for everything. Only warnings and errors are logged by default'
+complete -c tempdir -n '__fish_seen_subcommand_from doctor' -l log-level -r -f -a 'off error warn info debug trace' -d 'Log messages of this level and above, overrides -q and -v'
+complete -c tempdir -n '__fish_seen_subcommand_from doctor' -l store-path -r -d 'Directory to keep the meta data in, overrides TEMPDIR_STORE and the config file'
+complete -c tempdir -n '__fish_seen_subcommand_from doctor' -l profile -r -d 'Use the store and directories of this profile of the config file. Profiles not in the config file keep their meta data in profiles/<name> inside the default store'
+complete -c tempdir -n '__fish_seen_subcommand_from doctor' -l format -r -f -a 'text json' -d 'Output format of list, status, clean and history'
+complete -c tempdir -n '__fish_seen_subcommand_from doctor' -l config -r -d 'Config file to use instead of ~/.config/tempdir/config.toml'
+complete -c tempdir -n '__fish_seen_subcommand_from doctor' -s h -l help -d 'Print help information (use `--help` for more detail)'
+complete -c tempdir -n '__fish_seen_subcommand_from adopt' -s p -l path -r -d 'Directory to adopt'
+complete -c tempdir -n '__fish_seen_subcommand_from adopt' -s d -l duration -r -d 'Duration the directory will live, defaults to the `default_duration` of the config file. Examples: 1d, 4w, 8m, 1h30min, P1DT2H'
+complete -c tempdir -n '__fish_seen_subcommand_from adopt' -s q -l quiet -d 'Don\'t log anything, not even errors'
+complete -c tempdir -n '__fish_seen_subcommand_from adopt' -s v -l verbose -d 'Log more: -v for progress, -vv for details, -vvv for everything. Only warnings and errors are logged by default'
+complete -c tempdir -n '__fish_seen_subcommand_from adopt' -l log-level -r -f -a 'off error warn info debug trace' -d 'Log messages of this level and above, overrides -q and -v'
+complete -c tempdir -n '__fish_seen_subcommand_from adopt' -l store-path -r -d 'Directory to keep the meta data in, overrides TEMPDIR_STORE and the config file'
+complete -c tempdir -n '__fish_seen_subcommand_from adopt' -l profile -r -d 'Use the store and directories of this profile of the config file. Profiles not in the config file keep their meta data in profiles/<name> inside the default store'
+complete -c tempdir -n '__fish_seen_subcommand_from adopt' -l format -r -f -a 'text json' -d 'Output format of list, status, clean and history'
+complete -c tempdir -n '__fish_seen_subcommand_from adopt' -l config -r -d 'Config file to use instead of ~/.config/tempdir/config.toml'
+complete -c tempdir -n '__fish_seen_subcommand_from adopt' -s h -l help -d 'Print help information (use `--help` for more detail)'
+complete -c tempdir -n '__fish_seen_subcommand_from gc' -l orphans -d 'Also look for directories without meta data in the creation parent (TEMPDIR_PARENT or the config file), or in --root'
+complete -c tempdir -n '__fish_seen_subcommand_from gc' -l root -r -d 'Directory to look for directories without meta data in'
+complete -c tempdir -n '__fish_seen_subcommand_from gc' -l adopt -d 'Adopt every directory without meta data instead of asking'
+complete -c tempdir -n '__fish_seen_subcommand_from gc' -l delete -d 'Delete every directory without meta data instead of asking'
+complete -c tempdir -n '__fish_seen_subcommand_from gc' -s d -l duration -r -d 'Lifetime of adopted directories, defaults to the `default_duration` of the config file'
+complete -c tempdir -n '__fish_seen_subcommand_from gc' -s q -l quiet -d 'Don\'t log anything, not even errors'
+complete -c tempdir -n '__fish_seen_subcommand_from gc' -s v -l verbose -d 'Log more: -v for progress, -vv for details, -vvv for everything. Only warnings and errors are logged by default'
+complete -c tempdir -n '__fish_seen_subcommand_from gc' -l log-level -r -f -a 'off error warn info debug trace' -d 'Log messages of this level and above, overrides -q and -v'
+complete -c tempdir -n '__fish_seen_subcommand_from gc' -l store-path -r -d 'Directory to keep the meta data in, overrides TEMPDIR_STORE and the config file'
+complete -c tempdir -n '__fish_seen_subcommand_from gc' -l profile -r -d 'Use the store and directories of this profile of the config file. Profiles not in the config file keep their meta data in profiles/<name> inside the default store'
+complete -c tempdir -n '__fish_seen_subcommand_from gc' -l format -r -f -a 'text json' -d 'Output format of list, status, clean and history'
+complete -c tempdir -n '__fish_seen_subcommand_from gc' -l config -r -d 'Config file to use instead of ~/.config/tempdir/config.toml'
+complete -c tempdir -n '__fish_seen_subcommand_from gc' -s h -l help -d 'Print help information (use `--help` for more detail)'
+complete -c tempdir -n '__fish_seen_subcommand_from pin' -s n -l name -r -f -a '(tempdir list --names 2>/dev/null)' -d 'Name of the temporary folder to pin'
+complete -c tempdir -n '__fish_seen_subcommand_from pin' -s p -l parent -r -d 'Parent directory of the temporary folder, needed when several folders with the same name are tracked'
+complete -c tempdir -n '__fish_seen_subcommand_from pin' -s q -l quiet -d 'Don\'t log anything, not even errors'
+complete -c tempdir -n '__fish_seen_subcommand_from pin' -s v -l verbose -d 'Log more: -v for progress, -vv for details, -vvv for everything. Only warnings and errors are logged by default'
+complete -c tempdir -n '__fish_seen_subcommand_from pin' -l log-level -r -f -a 'off error warn info debug trace' -d 'Log messages of this level and above, overrides -q and -v'
+complete -c tempdir -n '__fish_seen_subcommand_from pin' -l store-path -r -d 'Directory to keep the meta data in, overrides TEMPDIR_STORE and the config file'
+complete -c tempdir -n '__fish_seen_subcommand_from pin' -l profile -r -d 'Use the store and directories of this profile of the config file. Profiles not in the config file keep their meta data in profiles/<name> inside the default store'
+complete -c tempdir -n '__fish_seen_subcommand_from pin' -l format -r -f -a 'text json' -d 'Output format of list, status, clean and history'
+complete -c tempdir -n '__fish_seen_subcommand_from pin' -l config -r -d 'Config file to use instead of ~/.config/tempdir/config.toml'
+complete -c tempdir -n '__fish_seen_subcommand_from pin' -s h -l help -d 'Print help information (use `--help` for more detail)'
+complete -c tempdir -n '__fish_seen_subcommand_from unpin' -s n -l name -r -f -a '(tempdir list --names 2>/dev/null)' -d 'Name of the temporary folder to unpin'
+complete -c tempdir -n '__fish_seen_subcommand_from unpin' -s p -l parent -r -d 'Parent directory of the temporary folder, needed when several folders with the same name are tracked'
+complete -c tempdir -n '__fish_seen_subcommand_from unpin' -s q -l quiet -d 'Don\'t log anything, not even errors'
+complete -c tempdir -n '__fish_seen_subcommand_from unpin' -s v -l verbose -d 'Log more: -v for progress, -vv for details, -vvv for everything. Only warnings and errors are logged by default'
+complete -c tempdir -n '__fish_seen_subcommand_from unpin' -l log-level -r -f -a 'off error warn info debug trace' -d 'Log messages of this level and above, overrides -q and -v'
+complete -c tempdir -n '__fish_seen_subcommand_from unpin' -l store-path -r -d 'Directory to keep the meta data in, overrides TEMPDIR_STORE and the config file'
+complete -c tempdir -n '__fish_seen_subcommand_from unpin' -l profile -r -d 'Use the store and directories of this profile of the config file. Profiles not in the config file keep their meta data in profiles/<name> inside the default store'
+complete -c tempdir -n '__fish_seen_subcommand_from unpin' -l format -r -f -a 'text json' -d 'Output format of list, status, clean and history'
+complete -c tempdir -n '__fish_seen_subcommand_from unpin' -l config -r -d 'Config file to use instead of ~/.config/tempdir/config.toml'
+complete -c tempdir -n '__fish_seen_subcommand_from unpin' -s h -l help -d 'Print help information (use `--help` for more detail)'
+complete -c tempdir -n '__fish_seen_subcommand_from keep' -s n -l name -r -f -a '(tempdir list --names 2>/dev/null)' -d 'Name of the temporary folder to keep'
+complete -c tempdir -n '__fish_seen_subcommand_from keep' -s p -l parent -r -d 'Parent directory of the temporary folder, needed when several folders with the same name are tracked'
+complete -c tempdir -n '__fish_seen_subcommand_from keep' -s q -l quiet -d 'Don\'t log anything, not even errors'
+complete -c tempdir -n '__fish_seen_subcommand_from keep' -s v -l verbose -d 'Log more: -v for progress, -vv for details, -vvv for everything. Only warnings and errors are logged by default'
+complete -c tempdir -n '__fish_seen_subcommand_from keep' -l log-level -r -f -a 'off error warn info debug trace' -d 'Log messages of this level and above, overrides -q and -v'
+complete -c tempdir -n '__fish_seen_subcommand_from keep' -l store-path -r -d 'Directory to keep the meta data in, overrides TEMPDIR_STORE and the config file'
+complete -c tempdir -n '__fish_seen_subcommand_from keep' -l profile -r -d 'Use the store and directories of this profile of the config file. Profiles not in the config file keep their meta data in profiles/<name> inside the default store'
+complete -c tempdir -n '__fish_seen_subcommand_from keep' -l format -r -f -a 'text json' -d 'Output format of list, status, clean and history'
+complete -c tempdir -n '__fish_seen_subcommand_from keep' -l config -r -d 'Config file to use instead of ~/.config/tempdir/config.toml'
+complete -c tempdir -n '__fish_seen_subcommand_from keep' -s h -l help -d 'Print help information (use `--help` for more detail)'
+complete -c tempdir -n '__fish_seen_subcommand_from history' -s n -l name -r -f -a '(tempdir list --names 2>/dev/null)' -d 'Only show the events of temporary folders with this name'
+complete -c tempdir -n '__fish_seen_subcommand_from history' -s q -l quiet -d 'Don\'t log anything, not even errors'
+complete -c tempdir -n '__fish_seen_subcommand_from history' -s v -l verbose -d 'Log more: -v for progress, -vv for details, -vvv for everything. Only warnings and errors are logged by default'
+complete -c tempdir -n '__fish_seen_subcommand_from history' -l log-level -r -f -a 'off error warn info debug trace' -d 'Log messages of this level and above, overrides -q and -v'
+complete -c tempdir -n '__fish_seen_subcommand_from history' -l store-path -r -d 'Directory to keep the meta data in, overrides TEMPDIR_STORE and the config file'
+complete -c tempdir -n '__fish_seen_subcommand_from history' -l profile -r -d 'Use the store and directories of this profile of the config file. Profiles not in the config file keep their meta data in profiles/<name> inside the default store'
+complete -c tempdir -n '__fish_seen_subcommand_from history' -l format -r -f -a 'text json' -d 'Output format of list, status, clean and history'
+complete -c tempdir -n '__fish_seen_subcommand_from history' -l config -r -d 'Config file to use instead of ~/.config/tempdir/config.toml'
+complete -c tempdir -n '__fish_seen_subcommand_from history' -s h -l help -d 'Print help information (use `--help` for more detail)'
+complete -c tempdir -n '__fish_seen_subcommand_from completions' -s q -l quiet -d 'Don\'t log anything, not even errors'
+complete -c tempdir -n '__fish_seen_subcommand_from completions' -s v -l verbose -d 'Log more: -v for progress, -vv for details, -vvv for everything. Only warnings and errors are logged by default'
+complete -c tempdir -n '__fish_seen_subcommand_from completions' -l log-level -r -f -a 'off error warn info debug trace' -d 'Log messages of this level and above, overrides -q and -v'
+complete -c tempdir -n '__fish_seen_subcommand_from completions' -l store-path -r -d 'Directory to keep the meta data in, overrides TEMPDIR_STORE and the config file'
+complete -c tempdir -n '__fish_seen_subcommand_from completions' -l profile -r -d 'Use the store and directories of this profile of the config file. Profiles not in the config file keep their meta data in profiles/<name> inside the default store'
+complete -c tempdir -n '__fish_seen_subcommand_from completions' -l format -r -f -a 'text json' -d 'Output format of list, status, clean and history'
+complete -c tempdir -n '__fish_seen_subcommand_from completions' -l config -r -d 'Config file to use instead of ~/.config/tempdir/config.toml'
+complete -c tempdir -n '__fish_seen_subcommand_from completions' -s h -l help -d 'Print help information (use `--help` for more detail)'
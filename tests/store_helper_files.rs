@@ -0,0 +1,49 @@
+use std::env;
+use std::fs;
+use tempdir::clean_directories;
+
+/// Collects the error messages the library logs
+#[cfg(feature = "log")]
+mod capture {
+    use std::sync::Mutex;
+
+    pub static ERRORS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    struct Capture;
+    impl log::Log for Capture {
+        fn enabled(&self, metadata: &log::Metadata) -> bool {
+            metadata.level() <= log::Level::Error
+        }
+
+        fn log(&self, record: &log::Record) {
+            if self.enabled(record.metadata()) {
+                ERRORS.lock().unwrap().push(record.args().to_string());
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    pub fn install() {
+        log::set_logger(&Capture).unwrap();
+        log::set_max_level(log::LevelFilter::Error);
+    }
+}
+
+#[test]
+fn clean_ignores_lock_file_in_store() {
+    #[cfg(feature = "log")]
+    capture::install();
+    let store = env::temp_dir().join(format!("tempdir-helper-files-{}", std::process::id()));
+    fs::create_dir_all(&store).unwrap();
+    fs::write(store.join("tempdir.lock"), "").unwrap();
+    env::set_var("TEMPDIR_STORE", &store);
+
+    let report = clean_directories().unwrap();
+
+    assert!(report.errors.is_empty());
+    assert!(store.join("tempdir.lock").is_file());
+    #[cfg(feature = "log")]
+    assert_eq!(*capture::ERRORS.lock().unwrap(), Vec::<String>::new());
+    fs::remove_dir_all(&store).unwrap();
+}
@@ -0,0 +1,22 @@
+mod common;
+
+use std::env;
+use std::fs;
+use tempdir::{clean_directories, create_temp_dir, TempDirErrors};
+
+#[test]
+fn store_path_occupied_by_file_is_reported() {
+    let sandbox = common::sandbox("store-is-file");
+    let store = sandbox.join("store");
+    fs::write(&store, "not a directory").unwrap();
+    env::set_var("TEMPDIR_STORE", &store);
+    env::set_var("TEMPDIR_PARENT", &sandbox);
+
+    let created = create_temp_dir("occupied-store", "1h");
+    let cleaned = clean_directories();
+
+    assert!(matches!(created, Err(TempDirErrors::StoreFolderError(_))));
+    assert!(matches!(cleaned, Err(TempDirErrors::StoreFolderError(_))));
+    assert!(!sandbox.join("occupied-store").exists());
+    fs::remove_dir_all(&sandbox).unwrap();
+}
@@ -0,0 +1,43 @@
+#![cfg(unix)]
+
+mod common;
+
+use std::fs;
+use tempdir::{clean_directories_with, create_temp_dir, CleanOptions, ScopedTempDir};
+
+#[test]
+fn recursive_deletion_does_not_follow_symlinks() {
+    let sandbox = common::sandboxed_store("symlinks");
+    let external = sandbox.join("external");
+    fs::create_dir_all(&external).unwrap();
+    fs::write(external.join("precious"), "keep me").unwrap();
+
+    let scoped = ScopedTempDir::scoped("linking", "1h").unwrap();
+    let path = scoped.directory().path().unwrap();
+    std::os::unix::fs::symlink(&external, path.join("link")).unwrap();
+    fs::write(path.join("scratch"), "junk").unwrap();
+    drop(scoped);
+
+    assert!(!path.exists());
+    assert!(external.join("precious").is_file());
+}
+
+#[test]
+fn recursive_clean_does_not_follow_symlinks() {
+    let sandbox = common::sandboxed_store("symlinks");
+    let external = sandbox.join("external-clean");
+    fs::create_dir_all(&external).unwrap();
+    fs::write(external.join("precious"), "keep me").unwrap();
+
+    let tempdir = create_temp_dir("expiring-link", "1s").unwrap();
+    let path = tempdir.path().unwrap();
+    std::os::unix::fs::symlink(&external, path.join("link")).unwrap();
+    fs::write(path.join("scratch"), "junk").unwrap();
+
+    let options = CleanOptions { recursive: true, ..common::clean_options_after(2) };
+    let report = clean_directories_with(&options).unwrap();
+
+    assert!(report.errors.is_empty());
+    assert!(!path.exists());
+    assert!(external.join("precious").is_file());
+}
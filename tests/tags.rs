@@ -0,0 +1,32 @@
+mod common;
+
+use tempdir::{clean_directories_with, CleanOptions, TemporaryDirectory};
+
+#[test]
+fn tags_are_trimmed_and_lowercased() {
+    let tempdir = TemporaryDirectory::new("tagged".to_string(), "1h".to_string())
+        .unwrap()
+        .with_tags(vec!["Build ".to_string(), "build".to_string(), " CI".to_string(), " ".to_string()]);
+
+    assert_eq!(tempdir.tags(), ["build", "ci"]);
+    assert!(tempdir.has_tags(&["BUILD".to_string(), "ci ".to_string()]));
+    assert!(!tempdir.has_tags(&["release".to_string()]));
+}
+
+#[test]
+fn dry_run_clean_matches_normalized_tags() {
+    common::sandboxed_store("tags");
+
+    TemporaryDirectory::new("tagged-build".to_string(), "1s".to_string())
+        .unwrap()
+        .with_tags(vec!["Build ".to_string()])
+        .create()
+        .unwrap();
+
+    let dry_run = |tag: &str| CleanOptions { dry_run: true, tags: vec![tag.to_string()], ..common::clean_options_after(2) };
+    let matching = clean_directories_with(&dry_run("build")).unwrap();
+    let other = clean_directories_with(&dry_run("ci")).unwrap();
+
+    assert!(matching.deleted.iter().any(|cleaned| cleaned.directory.name() == "tagged-build"));
+    assert!(other.deleted.is_empty());
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use std::fs;
+use tempdir::{TempDirErrors, TemporaryDirectory};
+
+#[test]
+fn template_tree_is_copied_with_placeholders_expanded() {
+    let sandbox = common::sandboxed_store("template");
+    let template = sandbox.join("skeleton");
+    fs::create_dir_all(template.join("src")).unwrap();
+    fs::write(template.join("README.md"), "# {{name}}\ncreated {{date}}\n").unwrap();
+    fs::write(template.join("src").join("main.py"), "print('{{name}}')\n").unwrap();
+    fs::write(template.join("data.bin"), [0xff, 0xfe, b'{', b'{']).unwrap();
+
+    let expanded = TemporaryDirectory::new("scratch".to_string(), "1h".to_string())
+        .unwrap()
+        .with_template(Some(template.clone()))
+        .with_template_placeholders(true)
+        .try_create()
+        .unwrap()
+        .path()
+        .unwrap();
+    let readme = fs::read_to_string(expanded.join("README.md")).unwrap();
+    assert!(readme.starts_with("# scratch\ncreated 20"));
+    assert!(!readme.contains("{{date}}"));
+    assert_eq!(fs::read_to_string(expanded.join("src").join("main.py")).unwrap(), "print('scratch')\n");
+    assert_eq!(fs::read(expanded.join("data.bin")).unwrap(), [0xff, 0xfe, b'{', b'{']);
+
+    let verbatim = TemporaryDirectory::new("verbatim".to_string(), "1h".to_string())
+        .unwrap()
+        .with_template(Some(template.clone()))
+        .try_create()
+        .unwrap()
+        .path()
+        .unwrap();
+    assert_eq!(fs::read_to_string(verbatim.join("src").join("main.py")).unwrap(), "print('{{name}}')\n");
+}
+
+#[test]
+fn missing_template_creates_nothing() {
+    let sandbox = common::sandboxed_store("template");
+
+    let created = TemporaryDirectory::new("no-template".to_string(), "1h".to_string())
+        .unwrap()
+        .with_template(Some(sandbox.join("missing")))
+        .try_create();
+    assert!(matches!(created, Err(TempDirErrors::TemplateCopyFailed(_))));
+    assert!(!sandbox.join("no-template").exists());
+    assert!(TemporaryDirectory::load("no-template", None).is_err());
+}
@@ -0,0 +1,33 @@
+mod common;
+
+use tempdir::{create_temp_dir, TemporaryDirectory};
+
+#[test]
+fn timestamps_are_stored_as_rfc_3339() {
+    let sandbox = common::sandboxed_store("timestamps");
+
+    let tempdir = create_temp_dir("rfc-3339", "1h").unwrap();
+
+    let record = common::meta_data_records(&sandbox.join("store"), "rfc-3339").remove(0);
+    let end_time = record["end_time"].as_str().unwrap();
+    assert!(end_time.ends_with('Z'));
+    assert_eq!(chrono::DateTime::parse_from_rfc3339(end_time).unwrap().timestamp(), tempdir.end_time());
+}
+
+#[test]
+fn integer_timestamps_are_still_read() {
+    let record = serde_json::json!({
+        "name": "legacy",
+        "duration": "1h",
+        "created_at": 1700000000,
+        "end_time": 1700003600,
+        "path": null,
+    });
+
+    let tempdir: TemporaryDirectory = serde_json::from_value(record).unwrap();
+
+    assert_eq!(tempdir.created_at(), 1700000000);
+    assert_eq!(tempdir.end_time(), 1700003600);
+    let record = serde_json::to_value(&tempdir).unwrap();
+    assert_eq!(record["created_at"], "2023-11-14T22:13:20Z");
+}
@@ -0,0 +1,17 @@
+mod common;
+
+use tempdir::{create_temp_dir, TemporaryDirectory};
+
+#[test]
+fn touch_restarts_the_original_lifetime() {
+    common::sandboxed_store("touch");
+
+    let mut tempdir = create_temp_dir("touched", "1h").unwrap();
+    tempdir.extend("1d").unwrap();
+    assert!(tempdir.remaining_seconds() > 86400);
+
+    tempdir.touch().unwrap();
+
+    let reloaded = TemporaryDirectory::load("touched", None).unwrap();
+    assert!((3590..=3600).contains(&reloaded.remaining_seconds()));
+}
@@ -0,0 +1,31 @@
+#![cfg(all(unix, not(target_os = "macos")))]
+
+mod common;
+
+use std::env;
+use std::fs;
+use tempdir::{clean_directories_with, TemporaryDirectory};
+
+#[test]
+fn expired_directory_is_moved_to_the_trash() {
+    let sandbox = common::sandboxed_store("trash");
+    let trash = sandbox.join("data").join("Trash");
+    env::set_var("XDG_DATA_HOME", sandbox.join("data"));
+
+    let tempdir = TemporaryDirectory::new("trashed".to_string(), "1s".to_string())
+        .unwrap()
+        .with_trash(true)
+        .try_create()
+        .unwrap();
+    let path = tempdir.path().unwrap();
+    fs::write(path.join("result"), "might still be needed").unwrap();
+
+    let report = clean_directories_with(&common::clean_options_after(2)).unwrap();
+
+    assert!(report.errors.is_empty(), "{:?}", report.errors);
+    assert!(!path.exists());
+    assert!(trash.join("files").join("trashed").join("result").is_file());
+    let info = fs::read_to_string(trash.join("info").join("trashed.trashinfo")).unwrap();
+    assert!(info.starts_with("[Trash Info]\n"));
+    assert!(info.contains(&format!("Path={}", path.canonicalize().unwrap_or(path).display())));
+}
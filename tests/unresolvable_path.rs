@@ -0,0 +1,28 @@
+mod common;
+
+use std::env;
+use std::error::Error;
+use std::fs;
+use tempdir::{create_temp_dir, TempDirErrors, TemporaryDirectory};
+
+#[test]
+fn no_meta_data_is_written_when_path_cannot_be_resolved() {
+    let store = common::sandbox("unresolvable-store");
+    env::set_var("TEMPDIR_STORE", &store);
+
+    // Removing the working directory makes it impossible to resolve
+    let sandbox = common::sandbox("unresolvable");
+    env::set_current_dir(&sandbox).unwrap();
+    fs::remove_dir(&sandbox).unwrap();
+
+    let created = TemporaryDirectory::new("unresolvable".to_string(), "1h".to_string())
+        .unwrap()
+        .create();
+    match created {
+        Err(err @ TempDirErrors::DirectoryCreationFailed(_)) => assert!(err.source().is_some()),
+        _ => panic!("creating inside a removed directory should fail"),
+    }
+    assert!(create_temp_dir("unresolvable", "1h").is_err());
+
+    assert!(common::meta_data_records(&store, "unresolvable").is_empty());
+}